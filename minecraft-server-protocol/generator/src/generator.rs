@@ -1,5 +1,5 @@
 use crate::schema::{
-    ArrayCount, Field, FieldSwitch, FieldType, Packet, PacketDirection, ProtocolVersion,
+    ArrayCount, BitfieldMember, FieldSwitch, FieldType, Packet, PacketDirection, ProtocolVersion,
     VoidableType,
 };
 use displaydoc::Display;
@@ -10,6 +10,7 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::process::Command;
 use thiserror::Error;
@@ -25,6 +26,8 @@ pub struct StateGenerator {
     file_path: PathBuf,
     current_mod: Option<PacketDirection>,
     protocol_version: u32,
+    /// Lowercase
+    state: String,
 }
 
 #[derive(Debug, Display, Error)]
@@ -52,39 +55,123 @@ pub struct RustfmtExit(Option<i32>);
 pub type GeneratorResult<T> = Result<T, GeneratorError>;
 
 #[derive(Debug)]
-enum VariantKind {
-    Default,
-    Standard,
+struct Variant {
+    name: String,
+    /// Switch key and the pattern matching it, None for the default
+    key: Option<(String, String)>,
+    /// None for a void case
+    ty: Option<RustType>,
 }
 
+/// Enum field type generated per switch field, read given the value of the compared field
 #[derive(Debug)]
-enum VariantType {
-    Unit,
-    Unimplemented,
-    Present(RustType),
+struct VariableType {
+    ident: String,
+    compare_to: String,
+    /// Type of the compared field's value
+    dependency: String,
+    /// Expression for the value the keys are matched against
+    selector: String,
+    variants: Vec<Variant>,
 }
 
-/// Enum field type generated per switch field
+/// Struct generated per bitfield field, wrapping `BitfieldField` with getters per member
 #[derive(Debug)]
-struct VariableType {
+struct BitfieldType<'a> {
+    ident: String,
+    bytes: u32,
+    members: &'a [BitfieldMember],
+}
+
+/// Struct generated per container, deriving `Field`
+#[derive(Debug)]
+struct ContainerType<'a> {
     ident: String,
-    /// ((field name, filter value), _, _)
-    variants: Vec<((String, String), VariantType, VariantKind)>,
-    needs_default_unit: bool,
+    fields: Vec<(&'a str, &'a FieldType, RustType)>,
+    /// Type of the parent container field its fields depend on
+    parent_dependency: Option<String>,
 }
 
+/// Type generated for a field, emitted after its packet
 #[derive(Debug)]
-enum RustType {
-    Plain(Cow<'static, str>),
-    Generated(VariableType),
+enum GeneratedType<'a> {
+    Switch(VariableType),
+    Bitfield(BitfieldType<'a>),
+    Container(ContainerType<'a>),
+}
+
+/// Earlier field needed to read a type, see `DependentField`
+#[derive(Debug, Clone, PartialEq)]
+struct Dependency {
+    /// Containers above the one the type is used in that hold the field
+    ups: usize,
+    field: String,
+    /// Type of the field's value
+    ty: String,
+}
+
+#[derive(Debug)]
+struct RustType {
+    ident: Cow<'static, str>,
+    dependency: Option<Dependency>,
+}
+
+/// Fields of a packet or container, for finding the fields that switches compare to
+struct Scope<'a> {
+    name: String,
+    fields: Vec<(&'a str, &'a FieldType)>,
 }
 
-// TODO need to use chat types instead of string types
+/// Resolves field types to Rust types, collecting the types that need generating
+struct TypeResolver<'a> {
+    protocol_version: u32,
+    generated: Vec<GeneratedType<'a>>,
+    /// Packet then the containers being resolved, innermost last
+    scopes: Vec<Scope<'a>>,
+    /// Packet name then the names of the fields being resolved
+    path: Vec<&'a str>,
+    chat_direction: bool,
+}
+
+/// String fields holding JSON chat, as `packet.field.field` paths through containers, and the
+/// first protocol version they're chat in. Only clientbound, as the client sends plain text
+const CHAT_FIELDS: &[(&str, u32)] = &[
+    ("disconnect.reason", 0),
+    ("kick_disconnect.reason", 0),
+    ("chat.message", 0),
+    ("boss_bar.title", 0),
+    ("combat_event.message", 0),
+    ("death_combat_event.message", 0),
+    ("open_window.windowTitle", 0),
+    ("player_info.data.displayName", 0),
+    ("playerlist_header.header", 0),
+    ("playerlist_header.footer", 0),
+    ("title.text", 0),
+    ("set_title_text.text", 0),
+    ("set_title_subtitle.text", 0),
+    ("action_bar.text", 0),
+    ("advancements.advancementMapping.value.displayData.title", 0),
+    (
+        "advancements.advancementMapping.value.displayData.description",
+        0,
+    ),
+    // 1.13
+    ("scoreboard_objective.displayText", 393),
+    ("teams.name", 393),
+    ("teams.prefix", 393),
+    ("teams.suffix", 393),
+    ("map.icons.displayName", 393),
+    ("tab_complete.matches.tooltip", 393),
+];
+
+/// Arrays the schema gives varint elements that are varlongs in the protocol, as `packet.field`
+/// paths, and the first protocol version they're varlongs in
+const VARLONG_ARRAYS: &[(&str, u32)] = &[("multi_block_change.records", 751)];
 
 const COMMON_HEADER: &str = "// autogenerated file\n#![allow(unused_imports)]\n";
 const INCLUDES: &str = r#"
 use crate::types::*;
-use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+use minecraft_server_protocol_derive::{ClientBoundPacket, Field, ServerBoundPacket};
 use async_std::io::{Cursor, prelude::*};
 use std::fmt::{Display, Formatter};
 use async_trait::async_trait;
@@ -155,6 +242,7 @@ pub const PROTOCOL_VERSION: u32 = {};
 }
 
 const VOID_VARIANT: &str = "NotPresent";
+const DEFAULT_VARIANT: &str = "Default";
 
 impl StateGenerator {
     fn new(mod_dir: &Path, state: &str, version: &ProtocolVersion) -> std::io::Result<Self> {
@@ -176,6 +264,7 @@ impl StateGenerator {
             file_path,
             current_mod: None,
             protocol_version: version.version,
+            state: state.to_lowercase(),
         })
     }
 
@@ -200,17 +289,20 @@ impl StateGenerator {
             PacketDirection::Serverbound => "ServerBoundPacket",
         };
 
+        let mut resolver = TypeResolver::new(self.protocol_version, packet);
         let field_types = packet
             .fields
             .iter()
             .map(|f| {
-                RustType::from_field(
-                    FieldTypeSerialization::GenerateVariableTypes(&f, packet),
-                    self.protocol_version,
-                )
+                let name = format!("{}_{}", packet.name, f.name);
+                resolver.resolve_field(&f.name, &f.r#type, &name)
             })
             .collect::<Vec<_>>();
-        let is_incomplete = field_types.iter().any(|ty| ty.is_none());
+        // packets have nothing above them to depend on
+        let is_incomplete = field_types.iter().any(|ty| match ty {
+            None => true,
+            Some(ty) => ty.dependency.iter().any(|d| d.ups > 0),
+        });
 
         if is_incomplete {
             writeln!(&mut self.file, "/* TODO incomplete struct {}", struct_name)?;
@@ -225,97 +317,349 @@ impl StateGenerator {
         )?;
 
         for (field, ty) in packet.fields.iter().zip(field_types.iter()) {
-            let field_name: syn::Ident = {
-                let name = field.name.to_snake_case();
-                syn::parse_str(&name)
-                    .or_else(|_| syn::parse_str(&format!("r#{}", name)))
-                    .map_err(|_| GeneratorError::BadIdent(field.name.to_owned()))?
-            };
+            self.emit_field(&field.name, &field.r#type, ty.as_ref())?;
+        }
+
+        let suffix = if is_incomplete { "*/" } else { "" };
+        writeln!(&mut self.file, "}}{}\n", suffix)?;
+
+        if !is_incomplete {
+            if self.state == "login" && packet.name == "success" {
+                self.emit_login_success(&field_types)?;
+            }
+
+            for ty in &resolver.generated {
+                match ty {
+                    GeneratedType::Switch(switch) => self.emit_switch(switch)?,
+                    GeneratedType::Bitfield(bitfield) => self.emit_bitfield(bitfield)?,
+                    GeneratedType::Container(container) => self.emit_container(container)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Emits a constructor hiding whether the UUID is a string or binary in this version
+    fn emit_login_success(&mut self, field_types: &[Option<RustType>]) -> GeneratorResult<()> {
+        let uuid = match field_types {
+            [Some(uuid), Some(username)] if username.ident == "StringField" => {
+                match uuid.ident.as_ref() {
+                    "StringField" => "uuid: StringField::new(uuid.to_string())",
+                    "UuidField" => "uuid",
+                    _ => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            &mut self.file,
+            "impl Success {{
+                pub fn new(uuid: UuidField, username: impl Into<String>) -> Self {{
+                    Self {{
+                        {},
+                        username: StringField::new(username.into()),
+                    }}
+                }}
+            }}\n",
+            uuid
+        )?;
+        Ok(())
+    }
+
+    /// Emits a struct field, commented out if its type is not implemented
+    fn emit_field(
+        &mut self,
+        name: &str,
+        ty: &FieldType,
+        rust_ty: Option<&RustType>,
+    ) -> GeneratorResult<()> {
+        let field_name = ident(name)?;
+        let comment = if rust_ty.is_none() {
+            // not implemented
+            "// TODO "
+        } else {
+            ""
+        };
 
-            let comment = if ty.is_none() {
-                // not implemented
-                "// TODO "
-            } else {
-                ""
+        if let Some(dependency) = rust_ty.and_then(|ty| ty.dependency.as_ref()) {
+            let field = match dependency.ups {
+                0 => ident(&dependency.field)?.to_string(),
+                _ => "..".to_owned(),
             };
+            writeln!(&mut self.file, "\t{}#[depends_on = {:?}]", comment, field)?;
+        }
+
+        write!(&mut self.file, "\t{}pub {}: ", comment, field_name)?;
+
+        match rust_ty {
+            Some(rust_ty) => writeln!(&mut self.file, "{},", rust_ty.ident),
+            None => writeln!(&mut self.file, "{},", ty),
+        }?;
+
+        Ok(())
+    }
+
+    fn emit_container(&mut self, container: &ContainerType) -> GeneratorResult<()> {
+        writeln!(&mut self.file, "#[derive(Field)]")?;
+        if let Some(dependency) = &container.parent_dependency {
+            writeln!(&mut self.file, "#[parent_dependency = {:?}]", dependency)?;
+        }
+        writeln!(&mut self.file, "pub struct {} {{", container.ident)?;
+
+        for (name, ty, rust_ty) in &container.fields {
+            self.emit_field(name, ty, Some(rust_ty))?;
+        }
 
-            write!(&mut self.file, "\t{}pub {}: ", comment, field_name)?;
+        writeln!(&mut self.file, "}}\n")?;
+        Ok(())
+    }
 
-            match ty {
+    fn emit_switch(&mut self, switch: &VariableType) -> GeneratorResult<()> {
+        writeln!(
+            &mut self.file,
+            "/// The variant must be the one {} selects, or writing the packet fails",
+            switch.compare_to
+        )?;
+        writeln!(&mut self.file, "pub enum {} {{", switch.ident)?;
+        for variant in &switch.variants {
+            match &variant.key {
+                Some((key, _)) => writeln!(&mut self.file, "/// {}={}", switch.compare_to, key),
+                None => writeln!(&mut self.file, "/// Any other {}", switch.compare_to),
+            }?;
+            match &variant.ty {
+                Some(ty) => writeln!(&mut self.file, "{}({}),", variant.name, ty.ident),
+                None => writeln!(&mut self.file, "{},", variant.name),
+            }?;
+        }
+        writeln!(&mut self.file, "}}\n")?;
+
+        let (mut display, mut size, mut write, mut read) =
+            (String::new(), String::new(), String::new(), String::new());
+        let (mut write_dependent, mut selected, mut actual) =
+            (String::new(), String::new(), String::new());
+        for variant in &switch.variants {
+            let name = &variant.name;
+            let pattern = variant.key.as_ref().map_or("_", |(_, pattern)| pattern);
+            selected += &format!("{} => \"{}\",\n", pattern, name);
+            match &variant.ty {
                 Some(ty) => {
-                    writeln!(&mut self.file, "{},", ty.ident())
+                    display += &format!(
+                        "Self::{0}(value) => write!(f, \"{0}({{}})\", value.value()),\n",
+                        name
+                    );
+                    size += &format!("Self::{}(value) => value.size(),\n", name);
+                    write += &format!("Self::{}(value) => value.write_field(w).await,\n", name);
+                    write_dependent += &match ty.dependency {
+                        Some(_) => format!(
+                            "Self::{}(value) => value.write_dependent(w, dependency).await,\n",
+                            name
+                        ),
+                        None => format!("Self::{}(value) => value.write_field(w).await,\n", name),
+                    };
+                    actual += &format!("Self::{0}(_) => \"{0}\",\n", name);
+                    // the variant gives the type to read
+                    let read_value = match ty.dependency {
+                        Some(_) => "DependentField::read_dependent(r, dependency)",
+                        None => "Field::read_field(r)",
+                    };
+                    read += &format!("{} => Self::{}({}.await?),\n", pattern, name, read_value);
                 }
                 None => {
-                    writeln!(&mut self.file, "{},", field.r#type)
+                    display += &format!("Self::{0} => f.write_str(\"{0}\"),\n", name);
+                    size += &format!("Self::{} => 0,\n", name);
+                    write += &format!("Self::{} => Ok(()),\n", name);
+                    write_dependent += &format!("Self::{} => Ok(()),\n", name);
+                    actual += &format!("Self::{0} => \"{0}\",\n", name);
+                    read += &format!("{} => Self::{},\n", pattern, name);
                 }
-            }?;
+            }
         }
 
-        let suffix = if is_incomplete { "*/" } else { "" };
-        writeln!(&mut self.file, "}}{}\n", suffix)?;
+        // unused if every case is void
+        let (r, w) = if switch.variants.iter().any(|v| v.ty.is_some()) {
+            ("r", "w")
+        } else {
+            ("_r", "_w")
+        };
 
-        // generate types for variable fields
-        // TODO docstring
-        for var_type in field_types.iter().filter_map(|f| {
-            if let Some(RustType::Generated(variable)) = f {
-                Some(variable)
-            } else {
-                None
-            }
-        }) {
-            // writeln!(&mut self.file, "/// {:?}", var_type)?;
-            writeln!(&mut self.file, "pub enum {} {{", var_type.ident)?;
-
-            // TODO need to implement Field for enums
-            const VARIANT_NAMES: [&str; 6] = ["One", "Two", "Three", "Four", "Five", "Six"];
-            let mut variant_names = VARIANT_NAMES.iter();
-            for ((predicate_field, filter), ty, kind) in &var_type.variants {
-                let variant_name = match kind {
-                    VariantKind::Standard => {
-                        *variant_names.next().expect("ran out of variant names")
-                    }
-                    VariantKind::Default => "Default",
-                };
-                match ty {
-                    VariantType::Unimplemented => {
-                        writeln!(&mut self.file, "// TODO {}(...)", variant_name)
-                    }
-                    VariantType::Present(ty) => writeln!(
-                        &mut self.file,
-                        "/// {}={}\n{}({}),",
-                        predicate_field,
-                        filter,
-                        variant_name,
-                        ty.ident()
-                    ),
-                    VariantType::Unit => writeln!(
-                        &mut self.file,
-                        "/// {}={}\n{},",
-                        predicate_field, filter, variant_name
-                    ),
-                }?
-            }
+        writeln!(
+            &mut self.file,
+            r#"
+impl Display for {ident} {{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+            {display}
+        }}
+    }}
+}}
 
-            if var_type.needs_default_unit {
-                writeln!(&mut self.file, "{},", VOID_VARIANT)?;
-            }
+#[async_trait]
+impl Field for {ident} {{
+    type Displayable = Self;
 
-            writeln!(&mut self.file, "}}\n")?;
+    fn value(&self) -> &Self::Displayable {{
+        self
+    }}
+
+    fn size(&self) -> usize {{
+        match self {{
+            {size}
+        }}
+    }}
+
+    async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {{
+        Err(PacketError::MissingDependency("{ident}"))
+    }}
+
+    async fn write_field<W: Write + Unpin + Send>(&self, {w}: &mut W) -> PacketResult<()> {{
+        match self {{
+            {write}
+        }}
+    }}
+}}
+
+#[async_trait]
+impl DependentField for {ident} {{
+    type Dependency = {dependency};
+
+    async fn read_dependent<R: Read + Unpin + Send>(
+        {r}: &mut R,
+        dependency: &Self::Dependency,
+    ) -> PacketResult<Self> {{
+        Ok(match {selector} {{
+            {read}
+        }})
+    }}
+
+    async fn write_dependent<W: Write + Unpin + Send>(
+        &self,
+        {w}: &mut W,
+        dependency: &Self::Dependency,
+    ) -> PacketResult<()> {{
+        let selected = match {selector} {{
+            {selected}
+        }};
+        let variant = match self {{
+            {actual}
+        }};
+        if variant != selected {{
+            return Err(PacketError::SwitchMismatch {{
+                field: "{ident}",
+                variant,
+            }});
+        }}
+
+        match self {{
+            {write_dependent}
+        }}
+    }}
+}}
+"#,
+            ident = switch.ident,
+            display = display,
+            size = size,
+            write = write,
+            read = read,
+            selected = selected,
+            actual = actual,
+            write_dependent = write_dependent,
+            r = r,
+            w = w,
+            dependency = switch.dependency,
+            selector = switch.selector,
+        )?;
+
+        Ok(())
+    }
+
+    fn emit_bitfield(&mut self, bitfield: &BitfieldType) -> GeneratorResult<()> {
+        let members = bitfield
+            .members
+            .iter()
+            .filter(|m| !m.name.trim_start_matches('_').starts_with("unused"))
+            .collect::<Vec<_>>();
+
+        writeln!(
+            &mut self.file,
+            "#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]\npub struct {}(BitfieldField<{}>);\n",
+            bitfield.ident, bitfield.bytes
+        )?;
+
+        writeln!(&mut self.file, "impl {} {{", bitfield.ident)?;
+        for member in &members {
+            let name = ident(&member.name)?;
+            let setter = ident(&format!("set_{}", member.name))?;
+            let (shift, size) = (member.shift, member.size);
+
+            let (ty, getter, setter_value) = match (size, member.signed) {
+                (1, false) => (
+                    "bool",
+                    format!("self.0.unsigned({}, 1) != 0", shift),
+                    "u64::from(value)",
+                ),
+                (_, false) => {
+                    let ty = int_type('u', size);
+                    let getter = format!("self.0.unsigned({}, {})", shift, size);
+                    if ty == "u64" {
+                        (ty, getter, "value")
+                    } else {
+                        (ty, format!("{} as {}", getter, ty), "u64::from(value)")
+                    }
+                }
+                (_, true) => {
+                    let ty = int_type('i', size);
+                    let getter = format!("self.0.signed({}, {})", shift, size);
+                    let getter = if ty == "i64" {
+                        getter
+                    } else {
+                        format!("{} as {}", getter, ty)
+                    };
+                    (ty, getter, "value as u64")
+                }
+            };
 
             writeln!(
                 &mut self.file,
                 r#"
+pub fn {name}(&self) -> {ty} {{
+    {getter}
+}}
+
+pub fn {setter}(&mut self, value: {ty}) {{
+    self.0.set({shift}, {size}, {setter_value})
+}}"#,
+                name = name,
+                setter = setter,
+                ty = ty,
+                getter = getter,
+                shift = shift,
+                size = size,
+                setter_value = setter_value,
+            )?;
+        }
+        writeln!(&mut self.file, "}}\n")?;
+
+        let format = members
+            .iter()
+            .map(|m| format!("{}={{}}", m.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let args = members
+            .iter()
+            .map(|m| ident(&m.name).map(|name| format!(", self.{}()", name)))
+            .collect::<GeneratorResult<String>>()?;
+
+        writeln!(
+            &mut self.file,
+            r#"
 impl Display for {0} {{
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {{
-        f.write_str("{0}") // TODO better display for autogenerated types
+        write!(f, "({1})"{2})
     }}
-}}"#,
-                var_type.ident
-            )?;
+}}
 
-            writeln!(
-                &mut self.file,
-                r#"
 #[async_trait]
 impl Field for {0} {{
     type Displayable = Self;
@@ -325,21 +669,20 @@ impl Field for {0} {{
     }}
 
     fn size(&self) -> usize {{
-        todo!()
+        self.0.size()
     }}
 
-    async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {{
-        todo!()
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {{
+        BitfieldField::read_field(r).await.map(Self)
     }}
 
-    async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {{
-        todo!()
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {{
+        self.0.write_field(w).await
     }}
-
-}}"#,
-                var_type.ident
-            )?;
-        }
+}}
+"#,
+            bitfield.ident, format, args
+        )?;
 
         Ok(())
     }
@@ -356,9 +699,30 @@ impl Field for {0} {{
     }
 }
 
+fn ident(name: &str) -> GeneratorResult<syn::Ident> {
+    let name = name.to_snake_case();
+    syn::parse_str(&name)
+        .or_else(|_| syn::parse_str(&format!("r#{}", name)))
+        .map_err(|_| GeneratorError::BadIdent(name))
+}
+
+/// Smallest integer type holding `bits`
+fn int_type(sign: char, bits: u32) -> &'static str {
+    match (sign, bits) {
+        ('u', 0..=8) => "u8",
+        ('u', 9..=16) => "u16",
+        ('u', 17..=32) => "u32",
+        ('u', _) => "u64",
+        (_, 0..=8) => "i8",
+        (_, 9..=16) => "i16",
+        (_, 17..=32) => "i32",
+        _ => "i64",
+    }
+}
+
 fn rustfmt(file: &Path) -> GeneratorResult<()> {
     let exit = Command::new("rustfmt")
-        .args(&["--edition", "2018"])
+        .args(["--edition", "2018"])
         .arg(file)
         .spawn()
         .and_then(|mut child| child.wait())
@@ -381,39 +745,77 @@ impl Display for RustfmtExit {
     }
 }
 
-enum FieldTypeSerialization<'a> {
-    GenerateVariableTypes(&'a Field<'a>, &'a Packet<'a>),
-    /// e.g. nested array type. field 0 overrides field.type
-    GenerateVariableTypesCompound(&'a FieldType, &'a Field<'a>, &'a Packet<'a>),
-    PlainOnly(&'a FieldType),
+/// Name for the element type of an array named `name`
+fn element_name(name: &str) -> String {
+    let singular = name.to_singular();
+    // inflector mangles latin plurals, e.g. data to daum
+    if singular == name || name.ends_with('a') {
+        format!("{}_entry", name)
+    } else {
+        singular
+    }
 }
 
-impl<'a> FieldTypeSerialization<'a> {
-    fn nest(&self, nested_type: &'a FieldType) -> Self {
-        use FieldTypeSerialization::*;
-        match self {
-            GenerateVariableTypes(field, packet)
-            | GenerateVariableTypesCompound(_, field, packet) => {
-                GenerateVariableTypesCompound(nested_type, field, packet)
-            }
-            PlainOnly(_) => PlainOnly(nested_type),
+impl<'a> TypeResolver<'a> {
+    fn new(protocol_version: u32, packet: &'a Packet) -> Self {
+        let scope = Scope {
+            name: packet.name.to_owned(),
+            fields: packet
+                .fields
+                .iter()
+                .map(|f| (f.name.as_ref(), &f.r#type))
+                .collect(),
+        };
+
+        Self {
+            protocol_version,
+            generated: Vec::new(),
+            scopes: vec![scope],
+            path: vec![&packet.name],
+            chat_direction: packet.direction == PacketDirection::Clientbound,
         }
     }
-}
 
-impl RustType {
-    /// Serializes to Rust type, returns None if not implemented
-    fn from_field(field: FieldTypeSerialization, protocol_version: u32) -> Option<Self> {
-        use FieldType::*;
-        use FieldTypeSerialization::*;
+    /// As [resolve](Self::resolve) for the field `field` of the innermost container
+    fn resolve_field(&mut self, field: &'a str, ty: &'a FieldType, name: &str) -> Option<RustType> {
+        self.path.push(field);
+        let resolved = self.resolve(ty, name);
+        self.path.pop();
+        resolved
+    }
 
-        let ty = match field {
-            GenerateVariableTypes(field, _) => &field.r#type,
-            GenerateVariableTypesCompound(ty, _, _) | PlainOnly(ty) => ty,
-        };
+    /// Whether the field being resolved is JSON chat rather than a plain string
+    /// Whether the current field is in a table of paths and the versions they apply from
+    fn path_in(&self, table: &[(&str, u32)]) -> bool {
+        let path = self.path.join(".");
+        table
+            .iter()
+            .any(|(field, since)| *field == path && self.protocol_version >= *since)
+    }
+
+    fn is_chat(&self) -> bool {
+        self.chat_direction && self.path_in(CHAT_FIELDS)
+    }
+
+    /// Rust type for `ty`, or None if not implemented. Types generated for it are named after
+    /// `name`
+    fn resolve(&mut self, ty: &'a FieldType, name: &str) -> Option<RustType> {
+        let generated = self.generated.len();
+        let resolved = self.resolve_inner(ty, name);
+        if resolved.is_none() {
+            // drop types generated for a partially implemented type
+            self.generated.truncate(generated);
+        }
+        resolved
+    }
+
+    fn resolve_inner(&mut self, ty: &'a FieldType, name: &str) -> Option<RustType> {
+        use FieldType::*;
 
+        let protocol_version = self.protocol_version;
         Some(match ty {
             Varint => "VarIntField".into(),
+            Varlong => "VarLongField".into(),
             U16 => "UShortField".into(),
             U8 => "UByteField".into(),
             I64 => "LongField".into(),
@@ -423,128 +825,337 @@ impl RustType {
             F32 => "FloatField".into(),
             F64 => "DoubleField".into(),
             Bool => "BoolField".into(),
+            String if self.is_chat() => format!("ChatField<{}>", protocol_version).into(),
             String => "StringField".into(),
             Buffer { count_ty } if matches!(**count_ty, FieldType::Varint) => {
                 "VarIntThenByteArrayField".into()
             }
             Array { count, elem_ty } => {
-                let elem_ty = Self::from_field(field.nest(elem_ty), protocol_version)?;
-                match count {
-                    ArrayCount::Constant(n) => format!("[{}; {}]", elem_ty.ident(), n),
-                    ArrayCount::Prefixed(n) if matches!(**n, FieldType::Varint) => {
-                        format!("PrefixedArrayField<VarIntField, {}>", elem_ty.ident())
+                let elem_ty = match **elem_ty {
+                    Varint if self.path_in(VARLONG_ARRAYS) => "VarLongField".into(),
+                    _ => self.resolve(elem_ty, &element_name(name))?,
+                };
+                let ident = match count {
+                    ArrayCount::Constant(n) if elem_ty.dependency.is_none() => {
+                        format!("FixedArrayField<{}, {}>", elem_ty.ident, n)
+                    }
+                    ArrayCount::Prefixed(n)
+                        if matches!(**n, Varint | I8 | I16 | I32 | U8 | U16) =>
+                    {
+                        let count_ty = self.resolve(n, name)?;
+                        format!("PrefixedArrayField<{}, {}>", count_ty.ident, elem_ty.ident)
                     }
                     _ => return None,
+                };
+                RustType {
+                    ident: ident.into(),
+                    dependency: elem_ty.dependency,
                 }
-                .into()
             }
             Void => "()".into(), // TODO voidfield
-            // Uuid => {}
-            // EntityMetadata => {}
+            Uuid => "UuidField".into(),
+            EntityMetadata => format!("EntityMetadataField<{}>", protocol_version).into(),
             Position => format!("PositionField<{}>", protocol_version).into(),
+            Slot => format!("SlotField<{}>", protocol_version).into(),
             RestOfBuffer => "RestOfPacketByteArrayField".into(),
             Nbt => "NbtField".into(),
-            // OptionalNbt => {}
+            OptionalNbt => "OptionalNbtField".into(),
             Switch(switch) => {
-                if let FieldTypeSerialization::GenerateVariableTypes(field, packet) = field {
-                    RustType::Generated(VariableType::from_switch(
-                        switch,
-                        field,
-                        packet,
-                        protocol_version,
-                    ))
-                } else {
+                let (switch, dependency) = VariableType::from_switch(switch, name, self)?;
+                let ident = switch.ident.clone();
+                self.generated.push(GeneratedType::Switch(switch));
+                RustType {
+                    ident: ident.into(),
+                    dependency: Some(dependency),
+                }
+            }
+            Container(fields) => {
+                self.scopes.push(Scope {
+                    name: name.to_owned(),
+                    fields: fields.iter().map(|(f, ty)| (f.as_str(), ty)).collect(),
+                });
+                let fields = fields
+                    .iter()
+                    .filter(|(_, ty)| !matches!(ty, Void))
+                    .map(|(field, ty)| {
+                        let rust_ty =
+                            self.resolve_field(field, ty, &format!("{}_{}", name, field))?;
+                        Some((field.as_str(), ty, rust_ty))
+                    })
+                    .collect::<std::option::Option<Vec<_>>>();
+                self.scopes.pop();
+                let fields = fields?;
+
+                // fields depending on a field outside the container all need the same one
+                let mut dependency = None;
+                for dep in fields
+                    .iter()
+                    .filter_map(|(_, _, ty)| ty.dependency.as_ref())
+                {
+                    if dep.ups == 0 {
+                        continue;
+                    }
+
+                    let parent = Dependency {
+                        ups: dep.ups - 1,
+                        ..dep.clone()
+                    };
+                    match &dependency {
+                        None => dependency = Some(parent),
+                        Some(existing) if *existing == parent => {}
+                        Some(_) => return None,
+                    }
+                }
+
+                let ident = name.to_pascal_case();
+                self.generated.push(GeneratedType::Container(ContainerType {
+                    ident: ident.clone(),
+                    fields,
+                    parent_dependency: dependency.as_ref().map(|d| d.ty.clone()),
+                }));
+                RustType {
+                    ident: ident.into(),
+                    dependency,
+                }
+            }
+            ParticleData { compare_to } => RustType {
+                ident: format!("ParticleDataField<{}>", protocol_version).into(),
+                dependency: Some(Dependency {
+                    ups: 0,
+                    field: compare_to.clone(),
+                    ty: "i32".to_owned(),
+                }),
+            },
+            Option(inner) => {
+                let inner = self.resolve(inner, name)?;
+                if inner.dependency.is_some() {
                     return None;
                 }
+                format!("OptionField<{}>", inner.ident).into()
             }
-            // Container(fields) => {}
-            // Slot => {}
-            // ParticleData => {}
-            // Option(_) => {}
-            // Bitfield => {}
-            // TopBitSetTerminatedArray => {}
+            Bitfield(members) => {
+                let bits = members.iter().map(|m| m.size).sum::<u32>();
+                let ident = name.to_pascal_case();
+                self.generated.push(GeneratedType::Bitfield(BitfieldType {
+                    ident: ident.clone(),
+                    bytes: bits / 8,
+                    members,
+                }));
+                ident.into()
+            }
+            // only used for entity equipment
+            TopBitSetTerminatedArray { elem_ty } => match &**elem_ty {
+                Container(fields)
+                    if matches!(
+                        fields.as_slice(),
+                        [(slot, I8), (item, Slot)] if slot == "slot" && item == "item"
+                    ) =>
+                {
+                    format!("EquipmentField<{}>", protocol_version).into()
+                }
+                _ => return None,
+            },
             _ => return None,
         })
     }
 
-    fn ident(&self) -> &str {
-        match self {
-            RustType::Plain(s) => s,
-            RustType::Generated(var) => &var.ident,
-        }
+    /// Schema type of the field `ups` containers above the innermost, and the type of its value
+    /// when passed as a dependency
+    fn dependency_type(&self, ups: usize, field: &str) -> Option<(&'a FieldType, String)> {
+        use FieldType::*;
+
+        let scope = self.scopes.iter().rev().nth(ups)?;
+        let (_, ty) = scope.fields.iter().find(|(name, _)| *name == field)?;
+        let value_ty = match ty {
+            Varint | I32 => "i32".to_owned(),
+            I8 => "i8".to_owned(),
+            I16 => "i16".to_owned(),
+            I64 | Varlong => "i64".to_owned(),
+            U8 => "u8".to_owned(),
+            U16 => "u16".to_owned(),
+            Bool => "bool".to_owned(),
+            String => "String".to_owned(),
+            // generated as for any other field of the scope
+            Bitfield(_) => format!("{}_{}", scope.name, field).to_pascal_case(),
+            _ => return None,
+        };
+
+        Some((ty, value_ty))
     }
 }
 
+/// How switch keys are matched against the compared value
+enum SwitchKeys {
+    Bool,
+    Int { signed: bool },
+    Str,
+}
+
 impl VariableType {
-    fn from_switch(
-        switch: &FieldSwitch,
-        field: &Field,
-        packet: &Packet,
-        protocol_version: u32,
-    ) -> Self {
-        let ident = format!("{}_{}", packet.name, field.name).to_pascal_case();
-
-        let mut variants = switch
-            .cases
-            .iter()
-            .map(|(val, ty)| {
-                let predicate = (switch.predicate_field.clone(), val.to_owned());
-                match ty {
-                    VoidableType::Void => {
-                        // unit
-                        (predicate, VariantType::Unit, VariantKind::Standard)
-                    }
-                    VoidableType::Present(ty) => {
-                        // TODO ensure variant types arent generated multiple times - move this into generator and track mapping of (packet,field)->ident
-                        let ty = RustType::from_field(
-                            FieldTypeSerialization::GenerateVariableTypesCompound(
-                                ty, field, packet,
-                            ),
-                            protocol_version,
-                        );
-                        let ty = match ty {
-                            None => VariantType::Unimplemented,
-                            Some(ty) => VariantType::Present(ty),
-                        };
-
-                        (predicate, ty, VariantKind::Standard)
+    /// None if the compared field or any case isn't supported
+    fn from_switch<'a>(
+        switch: &'a FieldSwitch,
+        name: &str,
+        resolver: &mut TypeResolver<'a>,
+    ) -> Option<(Self, Dependency)> {
+        use FieldType::*;
+
+        let (compare_ty, dependency_ty) =
+            resolver.dependency_type(switch.predicate_parents, &switch.predicate_field)?;
+        let (keys, selector, compare_name) = match (&switch.predicate_member, compare_ty) {
+            (None, Bool) => (SwitchKeys::Bool, "*dependency".to_owned(), ""),
+            (None, String) => (SwitchKeys::Str, "dependency.as_str()".to_owned(), ""),
+            (None, Varint | Varlong | I8 | I16 | I32 | I64) => (
+                SwitchKeys::Int { signed: true },
+                "*dependency".to_owned(),
+                "",
+            ),
+            (None, U8 | U16) => (
+                SwitchKeys::Int { signed: false },
+                "*dependency".to_owned(),
+                "",
+            ),
+            (Some(member), Bitfield(members)) => {
+                let member = members.iter().find(|m| &m.name == member)?;
+                let keys = match (member.size, member.signed) {
+                    (1, false) => SwitchKeys::Bool,
+                    (_, signed) => SwitchKeys::Int { signed },
+                };
+                let getter = ident(&member.name).ok()?;
+                (
+                    keys,
+                    format!("dependency.{}()", getter),
+                    member.name.as_str(),
+                )
+            }
+            _ => return None,
+        };
+        let compare_name = match compare_name {
+            "" => switch.predicate_field.to_pascal_case(),
+            member => member.to_pascal_case(),
+        };
+
+        let dependency = Dependency {
+            ups: switch.predicate_parents,
+            field: switch.predicate_field.clone(),
+            ty: dependency_ty,
+        };
+
+        // cases can only be given the same dependency as the switch
+        let mut resolve_case = |ty: &'a VoidableType, variant_name: &str| match ty {
+            VoidableType::Void => Some(None),
+            VoidableType::Present(ty) => {
+                let ty = resolver.resolve(ty, &format!("{}_{}", name, variant_name))?;
+                match &ty.dependency {
+                    Some(dep) if (dep.ups, &dep.field) != (dependency.ups, &dependency.field) => {
+                        None
                     }
+                    _ => Some(Some(ty)),
                 }
-            })
-            .collect::<Vec<_>>();
+            }
+        };
+
+        let mut names = HashSet::new();
+        let mut variants = Vec::with_capacity(switch.cases.len() + 1);
+        for (key, ty) in &switch.cases {
+            let (pattern, variant_name) = match keys {
+                SwitchKeys::Bool => {
+                    let value = match key.as_str() {
+                        "true" | "1" => true,
+                        "false" | "0" => false,
+                        _ => return None,
+                    };
+                    let name = if value {
+                        compare_name.clone()
+                    } else {
+                        format!("Not{}", compare_name)
+                    };
+                    (value.to_string(), name)
+                }
+                SwitchKeys::Int { signed } => {
+                    let value = key.parse::<i64>().ok().filter(|n| signed || *n >= 0)?;
+                    let name = if value < 0 {
+                        format!("{}Minus{}", compare_name, -value)
+                    } else {
+                        format!("{}{}", compare_name, value)
+                    };
+                    (value.to_string(), name)
+                }
+                SwitchKeys::Str => {
+                    // drop the namespace, e.g. brigadier:double
+                    let location = key.rsplit(':').next().unwrap_or(key);
+                    let name = location.to_pascal_case();
+                    let name = if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                        name
+                    } else {
+                        format!("{}{}", compare_name, name)
+                    };
+                    (format!("{:?}", key), name)
+                }
+            };
 
-        if let Some(VoidableType::Present(default_ty)) = &switch.default {
-            // TODO ensure variant types arent generated multiple times - move this into generator and track mapping of (packet,field)->ident
-            let ty = RustType::from_field(
-                FieldTypeSerialization::GenerateVariableTypesCompound(default_ty, field, packet),
-                protocol_version,
-            );
-
-            let idx = variants.len();
-            variants.push((
-                (String::new(), String::new()),
-                VariantType::Unimplemented,
-                VariantKind::Default,
-            ));
-            variants.swap(0, idx); // move to front
+            let mut variant_name = variant_name;
+            if !names.insert(variant_name.clone()) {
+                variant_name = format!("{}{}", variant_name, variants.len() + 1);
+                names.insert(variant_name.clone());
+            }
+
+            variants.push(Variant {
+                ty: resolve_case(ty, &variant_name)?,
+                name: variant_name,
+                key: Some((key.to_owned(), pattern)),
+            });
         }
 
-        Self {
-            ident,
-            variants,
-            needs_default_unit: matches!(switch.default, Some(VoidableType::Void)),
+        let exhaustive = matches!(keys, SwitchKeys::Bool)
+            && ["true", "false"].iter().all(|b| {
+                variants
+                    .iter()
+                    .any(|v| matches!(&v.key, Some((_, p)) if p == b))
+            });
+        if !exhaustive {
+            let (name, ty) = match &switch.default {
+                Some(ty @ VoidableType::Present(_)) => {
+                    (DEFAULT_VARIANT, resolve_case(ty, DEFAULT_VARIANT)?)
+                }
+                // absent defaults are void too
+                _ => (VOID_VARIANT, None),
+            };
+            variants.push(Variant {
+                name: name.to_owned(),
+                key: None,
+                ty,
+            });
         }
+
+        let switch = Self {
+            ident: name.to_pascal_case(),
+            compare_to: match &switch.predicate_member {
+                Some(member) => format!("{}/{}", switch.predicate_field, member),
+                None => switch.predicate_field.clone(),
+            },
+            dependency: dependency.ty.clone(),
+            selector,
+            variants,
+        };
+        Some((switch, dependency))
     }
 }
 
 impl From<&'static str> for RustType {
     fn from(str: &'static str) -> Self {
-        Self::Plain(Cow::Borrowed(str))
+        Self {
+            ident: Cow::Borrowed(str),
+            dependency: None,
+        }
     }
 }
 
 impl From<String> for RustType {
     fn from(str: String) -> Self {
-        Self::Plain(Cow::Owned(str))
+        Self {
+            ident: Cow::Owned(str),
+            dependency: None,
+        }
     }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::io::Read;
@@ -50,6 +51,9 @@ pub enum SchemaError {
 
     /// No such field '{0}' referenced in switch
     BadSwitchField(String),
+
+    /// Bitfield of {0} bits is not a whole number of bytes up to 64 bits
+    BadBitfieldSize(u32),
 }
 
 mod raw {
@@ -60,8 +64,6 @@ mod raw {
 
     #[derive(Deserialize)]
     pub struct ProtocolRoot {
-        pub types: BTreeMap<String, Value>,
-
         pub handshaking: ProtocolState,
         pub status: ProtocolState,
         pub login: ProtocolState,
@@ -83,8 +85,7 @@ mod raw {
     pub struct PacketTypes(pub BTreeMap<String, Value>);
 
     #[derive(Deserialize, Debug)]
-    pub struct PacketDefinition<'a> {
-        pub name: &'a str,
+    pub struct PacketDefinition {
         pub r#type: Value,
     }
 
@@ -109,6 +110,13 @@ mod raw {
         pub r#type: Value,
     }
 
+    #[derive(Deserialize, Debug)]
+    pub struct BitfieldMember {
+        pub name: String,
+        pub size: u32,
+        pub signed: bool,
+    }
+
     #[derive(Deserialize, Debug)]
     pub struct BufferType<'a> {
         #[serde(rename = "countType")]
@@ -127,7 +135,7 @@ mod raw {
     pub enum ArrayCount<'a> {
         PrefixedBy(&'a str),
         Constant(usize),
-        Field(String),
+        Field,
     }
 
     #[derive(Deserialize, Debug, Clone)]
@@ -140,13 +148,13 @@ mod raw {
     }
 
     impl<'a> ArrayType<'a> {
-        pub fn count(&self) -> Result<ArrayCount, SchemaError> {
+        pub fn count(&self) -> Result<ArrayCount<'_>, SchemaError> {
             match (self.count.as_ref(), self.count_type) {
                 (Some(Value::Number(num)), None) if num.is_u64() => {
                     let n = num.as_u64().unwrap();
                     Ok(ArrayCount::Constant(n as usize))
                 }
-                (Some(Value::String(s)), None) => Ok(ArrayCount::Field(s.clone())),
+                (Some(Value::String(_)), None) => Ok(ArrayCount::Field),
                 (None, Some(n)) => Ok(ArrayCount::PrefixedBy(n)),
                 _ => Err(SchemaError::Deserializing(
                     serde_json::Error::custom("expected either array countType or count"),
@@ -170,7 +178,11 @@ pub enum VoidableType {
 
 #[derive(Debug)]
 pub struct FieldSwitch {
+    /// Number of containers above the switch's own that the predicate field is in, from `../`
+    pub predicate_parents: usize,
     pub predicate_field: String,
+    /// Bitfield member of the predicate field, from the `field/member` syntax
+    pub predicate_member: Option<String>,
     pub cases: Vec<(String, VoidableType)>,
     /// None if not specified
     pub default: Option<VoidableType>,
@@ -194,7 +206,7 @@ struct CurrentErrorContext<'a> {
 
 #[derive(Error, Debug, Display)]
 /// {0}
-pub struct ContextError(Box<String>);
+pub struct ContextError(String);
 
 #[derive(Debug, Display)]
 /// {0}
@@ -340,14 +352,28 @@ impl<'a> State<'a> {
                 context.currently_with("parsing field", field);
                 let field =
                     raw::Field::deserialize(field).map_err(|e| Deserializing(e, "field"))?;
-                let field_ty = FieldType::try_from(&field.r#type, &packet)?;
+                let field_ty = FieldType::try_from(&field.r#type)?;
+                if let FieldType::Switch(switch) = &field_ty {
+                    switch.validate(&packet.fields)?;
+                }
                 assert!(field.name.is_some() || field.anon); // TODO result
 
-                // TODO multiple anons?
-                packet.fields.push(Field {
-                    name: field.name.unwrap_or("anon"),
-                    r#type: field_ty,
-                })
+                match (field.name, field_ty) {
+                    (None, FieldType::Container(anon)) => {
+                        // flatten into the packet
+                        packet
+                            .fields
+                            .extend(anon.into_iter().map(|(name, ty)| Field {
+                                name: Cow::Owned(name),
+                                r#type: ty,
+                            }))
+                    }
+                    // TODO multiple anons?
+                    (name, ty) => packet.fields.push(Field {
+                        name: Cow::Borrowed(name.unwrap_or("anon")),
+                        r#type: ty,
+                    }),
+                }
             }
 
             f(packet)?;
@@ -359,7 +385,7 @@ impl<'a> State<'a> {
 }
 
 impl ErrorContext {
-    fn current(&mut self) -> CurrentErrorContext {
+    fn current(&mut self) -> CurrentErrorContext<'_> {
         CurrentErrorContext {
             ctx: self,
             wat: None,
@@ -368,7 +394,7 @@ impl ErrorContext {
     }
 
     fn take(mut self) -> Option<ContextError> {
-        self.0.take().map(|s| ContextError(Box::new(s)))
+        self.0.take().map(ContextError)
     }
 }
 
@@ -403,7 +429,7 @@ impl Drop for CurrentErrorContext<'_> {
 fn extract(val: &Value) -> Option<(&str, &Value)> {
     match val {
         Value::Array(array) if array.len() == 2 && array[0].is_string() => {
-            Some((&array[0].as_str().unwrap(), &array[1]))
+            Some((array[0].as_str().unwrap(), &array[1]))
         }
         _ => None,
     }
@@ -418,13 +444,14 @@ fn extract_specific<'a>(key: &'static str, val: &'a Value) -> Option<&'a Value>
 pub enum ArrayCount {
     Prefixed(Box<FieldType>),
     Constant(usize),
-    /// Name of field
-    Field(String),
+    /// Counted by an earlier field, not supported yet
+    Field,
 }
 
 #[derive(Debug)]
 pub enum FieldType {
     Varint,
+    Varlong,
     U16,
     U8,
     I64,
@@ -452,18 +479,33 @@ pub enum FieldType {
     Option(Box<FieldType>),
     Container(Vec<(String, FieldType)>),
     Void,
-    Slot,                     // TODO
-    ParticleData,             // TODO
-    Bitfield,                 // TODO
-    TopBitSetTerminatedArray, // TODO
-    Tags,                     // TODO
-    SmeltingRecipe,           // TODO
-    Ingredient,               // TODO
+    Slot, // TODO
+    ParticleData {
+        /// Name of the particle ID field
+        compare_to: String,
+    },
+    Bitfield(Vec<BitfieldMember>),
+    TopBitSetTerminatedArray {
+        elem_ty: Box<FieldType>,
+    },
+    Tags,           // TODO
+    SmeltingRecipe, // TODO
+    Ingredient,     // TODO
+}
+
+#[derive(Debug)]
+pub struct BitfieldMember {
+    pub name: String,
+    /// Bits
+    pub size: u32,
+    pub signed: bool,
+    /// Bits below this member
+    pub shift: u32,
 }
 
 #[derive(Debug)]
 pub struct Field<'a> {
-    pub name: &'a str,
+    pub name: Cow<'a, str>,
     pub r#type: FieldType,
 }
 
@@ -498,8 +540,36 @@ impl VarintMappings {
     }
 }
 
+impl FieldSwitch {
+    /// Ensures a predicate naming an earlier packet field exists, including its bitfield member.
+    /// Predicates relative to a parent container are not checked
+    fn validate(&self, fields: &[Field]) -> Result<(), SchemaError> {
+        if self.predicate_parents > 0 {
+            return Ok(());
+        }
+
+        let field = fields
+            .iter()
+            .find(|f| f.name == self.predicate_field)
+            .ok_or_else(|| SchemaError::BadSwitchField(self.predicate_field.clone()))?;
+
+        match (&self.predicate_member, &field.r#type) {
+            (None, _) => Ok(()),
+            (Some(member), FieldType::Bitfield(members))
+                if members.iter().any(|m| &m.name == member) =>
+            {
+                Ok(())
+            }
+            (Some(member), _) => Err(SchemaError::BadSwitchField(format!(
+                "{}/{}",
+                self.predicate_field, member
+            ))),
+        }
+    }
+}
+
 impl FieldType {
-    fn try_from(value: &Value, packet: &Packet) -> Result<Self, SchemaError> {
+    fn try_from(value: &Value) -> Result<Self, SchemaError> {
         if let Some(str) = value.as_str() {
             str.parse()
         } else if let Some(kv) = extract(value) {
@@ -516,17 +586,21 @@ impl FieldType {
                     let switch = raw::Switch::deserialize(obj)
                         .map_err(|e| SchemaError::Deserializing(e, "switch"))?;
 
-                    // ensure field exists
-                    // TODO handle bitfields slash syntax
-                    // let _compare_field = packet
-                    //     .fields
-                    //     .iter()
-                    //     .find(|f| f.name == switch.predicate_field)
-                    //     .ok_or(SchemaError::BadSwitchField(switch.predicate_field))?;
+                    let mut predicate = switch.predicate_field.as_str();
+                    let mut predicate_parents = 0;
+                    while let Some(rest) = predicate.strip_prefix("../") {
+                        predicate = rest;
+                        predicate_parents += 1;
+                    }
+
+                    let (predicate_field, predicate_member) = match predicate.split_once('/') {
+                        Some((field, member)) => (field.to_owned(), Some(member.to_owned())),
+                        None => (predicate.to_owned(), None),
+                    };
 
                     let default = match switch.default {
                         Some(val) => {
-                            let resolved_ty = FieldType::try_from(&val, packet)?;
+                            let resolved_ty = FieldType::try_from(&val)?;
                             Some(VoidableType::from(resolved_ty))
                         }
                         None => None,
@@ -536,12 +610,14 @@ impl FieldType {
                         .fields
                         .into_iter()
                         .map(|(val, ty)| {
-                            FieldType::try_from(&ty, packet).map(|ty| (val, VoidableType::from(ty)))
+                            FieldType::try_from(&ty).map(|ty| (val, VoidableType::from(ty)))
                         })
                         .collect::<Result<_, _>>()?;
 
                     Ok(FieldType::Switch(FieldSwitch {
-                        predicate_field: switch.predicate_field,
+                        predicate_parents,
+                        predicate_field,
+                        predicate_member,
                         cases,
                         default,
                     }))
@@ -555,10 +631,10 @@ impl FieldType {
                             ArrayCount::Prefixed(Box::new(ty.parse()?))
                         }
                         raw::ArrayCount::Constant(n) => ArrayCount::Constant(n),
-                        raw::ArrayCount::Field(f) => ArrayCount::Field(f),
+                        raw::ArrayCount::Field => ArrayCount::Field,
                     };
 
-                    let elem_ty = Box::new(FieldType::try_from(&array.r#type, packet)?);
+                    let elem_ty = Box::new(FieldType::try_from(&array.r#type)?);
 
                     Ok(FieldType::Array { count, elem_ty })
                 }
@@ -566,27 +642,64 @@ impl FieldType {
                     let vals = obj
                         .as_array()
                         .ok_or(SchemaError::BadStructure("container"))?;
-                    let fields = vals
-                        .iter()
-                        .map(|val| match raw::Field::deserialize(val) {
-                            Ok(field) => {
-                                let ty = FieldType::try_from(&field.r#type, &packet)?;
-                                Ok((field.name.expect("missing field name").to_owned(), ty))
-                                // TODO result
-                            }
-                            Err(e) => Err(SchemaError::Deserializing(e, "container field")),
-                        })
-                        .collect::<Result<_, _>>()?;
+                    let mut fields = Vec::with_capacity(vals.len());
+                    for val in vals {
+                        let field = raw::Field::deserialize(val)
+                            .map_err(|e| SchemaError::Deserializing(e, "container field"))?;
+                        let ty = FieldType::try_from(&field.r#type)?;
+                        match (field.name, ty) {
+                            (None, FieldType::Container(anon)) if field.anon => fields.extend(anon),
+                            (Some(name), ty) => fields.push((name.to_owned(), ty)),
+                            (None, _) => return Err(SchemaError::BadStructure("container field")),
+                        }
+                    }
 
                     Ok(FieldType::Container(fields))
                 }
-                ("particleData", _obj) => Ok(FieldType::ParticleData),
+                ("particleData", obj) => {
+                    let compare_to = obj
+                        .get("compareTo")
+                        .and_then(Value::as_str)
+                        .ok_or(SchemaError::BadStructure("particleData"))?;
+                    Ok(FieldType::ParticleData {
+                        compare_to: compare_to.to_owned(),
+                    })
+                }
                 ("option", obj) => {
-                    let resolved_ty = Self::try_from(obj, packet)?;
+                    let resolved_ty = Self::try_from(obj)?;
                     Ok(FieldType::Option(Box::new(resolved_ty)))
                 }
-                ("bitfield", _obj) => Ok(FieldType::Bitfield),
-                ("topBitSetTerminatedArray", _obj) => Ok(FieldType::TopBitSetTerminatedArray),
+                ("bitfield", obj) => {
+                    let members = Vec::<raw::BitfieldMember>::deserialize(obj)
+                        .map_err(|e| SchemaError::Deserializing(e, "bitfield"))?;
+
+                    let bits = members.iter().map(|m| m.size).sum::<u32>();
+                    if bits == 0 || bits > 64 || bits % 8 != 0 {
+                        return Err(SchemaError::BadBitfieldSize(bits));
+                    }
+
+                    let mut shift = bits;
+                    let members = members
+                        .into_iter()
+                        .map(|m| {
+                            shift -= m.size;
+                            BitfieldMember {
+                                name: m.name,
+                                size: m.size,
+                                signed: m.signed,
+                                shift,
+                            }
+                        })
+                        .collect();
+                    Ok(FieldType::Bitfield(members))
+                }
+                ("topBitSetTerminatedArray", obj) => {
+                    let elem_ty = obj
+                        .get("type")
+                        .ok_or(SchemaError::BadStructure("topBitSetTerminatedArray"))?;
+                    let elem_ty = Box::new(FieldType::try_from(elem_ty)?);
+                    Ok(FieldType::TopBitSetTerminatedArray { elem_ty })
+                }
                 (k, _) => Err(SchemaError::UnknownFieldType(k.into())),
             }
         } else {
@@ -601,6 +714,7 @@ impl FromStr for FieldType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "varint" => Self::Varint,
+            "varlong" => Self::Varlong,
             "u16" => Self::U16,
             "u8" => Self::U8,
             "i64" => Self::I64,
@@ -647,6 +761,7 @@ impl Display for FieldType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             FieldType::Varint => write!(f, "Varint"),
+            FieldType::Varlong => write!(f, "Varlong"),
             FieldType::U16 => write!(f, "U16"),
             FieldType::U8 => write!(f, "U8"),
             FieldType::I64 => write!(f, "I64"),
@@ -670,9 +785,9 @@ impl Display for FieldType {
             FieldType::Container(_) => write!(f, "Container"),
             FieldType::Void => write!(f, "Void"),
             FieldType::Slot => write!(f, "Slot"),
-            FieldType::ParticleData => write!(f, "ParticleData"),
-            FieldType::Bitfield => write!(f, "Bitfield"),
-            FieldType::TopBitSetTerminatedArray => write!(f, "TopBitSetTerminatedArray"),
+            FieldType::ParticleData { .. } => write!(f, "ParticleData"),
+            FieldType::Bitfield(_) => write!(f, "Bitfield"),
+            FieldType::TopBitSetTerminatedArray { .. } => write!(f, "TopBitSetTerminatedArray"),
             FieldType::Tags => write!(f, "Tags"),
             FieldType::SmeltingRecipe => write!(f, "SmeltingRecipe"),
             FieldType::Ingredient => write!(f, "Ingredient"),
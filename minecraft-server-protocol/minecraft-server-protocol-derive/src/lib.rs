@@ -30,6 +30,61 @@ fn extract_packet_id(item: &DeriveInput) -> i32 {
     }
 }
 
+enum Dependency {
+    /// Earlier field given in `#[depends_on = "field"]`
+    Field(Ident),
+    /// The struct's own dependency, given in `#[depends_on = ".."]`
+    Parent,
+}
+
+fn extract_dependency(field: &syn::Field) -> Option<Dependency> {
+    let attribute = field.attrs.iter().find(|a| a.path.is_ident("depends_on"))?;
+    let span = attribute.span();
+
+    match attribute.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue {
+            lit: syn::Lit::Str(name),
+            ..
+        })) if name.value() == ".." => Some(Dependency::Parent),
+        Ok(Meta::NameValue(MetaNameValue {
+            lit: syn::Lit::Str(name),
+            ..
+        })) => {
+            Some(Dependency::Field(name.parse().unwrap_or_else(|_| {
+                abort!(span, "bad dependency field name")
+            })))
+        }
+        _ => abort!(span, "expected #[depends_on = \"field\"]"),
+    }
+}
+
+/// Type given in `#[parent_dependency = "Type"]`, the dependency of the whole struct
+fn extract_parent_dependency(item: &DeriveInput) -> Option<Type> {
+    let attribute = item
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("parent_dependency"))?;
+    let span = attribute.span();
+
+    match attribute.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue {
+            lit: syn::Lit::Str(ty),
+            ..
+        })) => Some(
+            ty.parse()
+                .unwrap_or_else(|_| abort!(span, "bad parent dependency type")),
+        ),
+        _ => abort!(span, "expected #[parent_dependency = \"Type\"]"),
+    }
+}
+
+fn extract_dependencies(item: &DeriveInput) -> Vec<Option<Dependency>> {
+    match &item.data {
+        Data::Struct(r#struct) => r#struct.fields.iter().map(extract_dependency).collect(),
+        _ => abort_call_site!("Packet must be a struct"),
+    }
+}
+
 fn extract_fields(
     item: &DeriveInput,
 ) -> (Vec<&Ident>, impl Iterator<Item = Cow<'_, syn::Type>> + '_) {
@@ -94,13 +149,60 @@ fn impl_display(name: &Ident, field_names: &[&Ident]) -> impl ToTokens {
     out
 }
 
-#[proc_macro_derive(ServerBoundPacket, attributes(packet_id))]
+/// Reads of each field from `reader`, in order
+fn field_reads<'a>(
+    item: &'a DeriveInput,
+    field_types: impl Iterator<Item = Cow<'a, syn::Type>> + 'a,
+    reader: impl ToTokens + 'a,
+) -> impl Iterator<Item = impl ToTokens> + 'a {
+    field_types
+        .zip(extract_dependencies(item))
+        .map(move |(ty, dependency)| match dependency {
+            Some(Dependency::Field(dependency)) => quote! {
+                <#ty as DependentField>::read_dependent(#reader, Field::value(&#dependency))
+            },
+            Some(Dependency::Parent) => quote! {
+                <#ty as DependentField>::read_dependent(#reader, dependency)
+            },
+            None => quote! { <#ty>::read_field(#reader) },
+        })
+}
+
+/// Writes of each field to `w`, in order. Fields depending on the struct's own dependency are
+/// only given it when `parent` is set, and are written on their own otherwise
+fn field_writes<'a>(
+    item: &'a DeriveInput,
+    field_names: &'a [&'a Ident],
+    parent: bool,
+) -> impl Iterator<Item = impl ToTokens> + 'a {
+    field_names
+        .iter()
+        .zip(extract_dependencies(item))
+        .map(move |(name, dependency)| match dependency {
+            Some(Dependency::Field(dependency)) => quote! {
+                DependentField::write_dependent(&self.#name, w, Field::value(&self.#dependency))
+            },
+            Some(Dependency::Parent) if parent => quote! {
+                DependentField::write_dependent(&self.#name, w, dependency)
+            },
+            _ => quote! { self.#name.write_field(w) },
+        })
+}
+
+#[proc_macro_derive(ServerBoundPacket, attributes(packet_id, depends_on))]
 #[proc_macro_error]
 pub fn server_packet(input: TokenStream) -> TokenStream {
     let item: DeriveInput = syn::parse(input.clone()).expect("failed to parse input");
 
     let packet_id = extract_packet_id(&item);
     let (field_names, field_types) = extract_fields(&item);
+    if extract_dependencies(&item)
+        .iter()
+        .any(|d| matches!(d, Some(Dependency::Parent)))
+    {
+        abort_call_site!("packets have no parent dependency");
+    }
+    let field_reads = field_reads(&item, field_types, quote! { &mut cursor });
     let name = &item.ident;
     let display = impl_display(name, &field_names);
     let result = quote! {
@@ -120,7 +222,7 @@ pub fn server_packet(input: TokenStream) -> TokenStream {
 
                 let mut cursor = Cursor::new(body.body);
 
-                #( let #field_names = <#field_types>::read_field(&mut cursor).await?;)*
+                #( let #field_names = #field_reads.await?;)*
 
                 let packet = Self {
                     #( #field_names ),*
@@ -148,13 +250,14 @@ pub fn server_packet(input: TokenStream) -> TokenStream {
     result.into()
 }
 
-#[proc_macro_derive(ClientBoundPacket, attributes(packet_id))]
+#[proc_macro_derive(ClientBoundPacket, attributes(packet_id, depends_on))]
 #[proc_macro_error]
 pub fn client_packet(input: TokenStream) -> TokenStream {
     let item: DeriveInput = syn::parse(input.clone()).expect("failed to parse input");
 
     let packet_id = extract_packet_id(&item);
     let (field_names, _field_types) = extract_fields(&item);
+    let field_writes = field_writes(&item, &field_names, false);
 
     let name = &item.ident;
     let display = impl_display(name, &field_names);
@@ -176,7 +279,7 @@ pub fn client_packet(input: TokenStream) -> TokenStream {
                 len.write_field(w).await?;
                 packet_id.write_field(w).await?;
 
-                #( self.#field_names.write_field(w).await?; )*
+                #( #field_writes.await?; )*
 
                 Ok(())
 
@@ -199,3 +302,93 @@ pub fn client_packet(input: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Field made up of the struct's fields in order, e.g. a generated container. Fields with
+/// `#[depends_on = ".."]` are given the struct's own dependency, with the type in
+/// `#[parent_dependency = "Type"]`
+#[proc_macro_derive(Field, attributes(depends_on, parent_dependency))]
+#[proc_macro_error]
+pub fn field(input: TokenStream) -> TokenStream {
+    let item: DeriveInput = syn::parse(input.clone()).expect("failed to parse input");
+
+    let (field_names, field_types) = extract_fields(&item);
+    let field_reads = field_reads(&item, field_types, quote! { r }).collect::<Vec<_>>();
+    let parent_writes = field_writes(&item, &field_names, true);
+    let field_writes = field_writes(&item, &field_names, false);
+    let name = &item.ident;
+
+    let (read, dependent) = match extract_parent_dependency(&item) {
+        Some(dependency) => (
+            quote! {
+                Err(PacketError::MissingDependency(stringify!(#name)))
+            },
+            quote! {
+                #[async_trait::async_trait]
+                impl DependentField for #name {
+                    type Dependency = #dependency;
+
+                    async fn read_dependent<R: Read + Unpin + Send>(
+                        r: &mut R,
+                        dependency: &Self::Dependency,
+                    ) -> PacketResult<Self> {
+                        #( let #field_names = #field_reads.await?; )*
+
+                        Ok(Self {
+                            #( #field_names ),*
+                        })
+                    }
+
+                    async fn write_dependent<W: Write + Unpin + Send>(
+                        &self,
+                        w: &mut W,
+                        dependency: &Self::Dependency,
+                    ) -> PacketResult<()> {
+                        #( #parent_writes.await?; )*
+                        Ok(())
+                    }
+                }
+            },
+        ),
+        None => (
+            quote! {
+                #( let #field_names = #field_reads.await?; )*
+
+                Ok(Self {
+                    #( #field_names ),*
+                })
+            },
+            quote! {},
+        ),
+    };
+
+    let display = impl_display(name, &field_names);
+    let result = quote! {
+        #[async_trait::async_trait]
+        impl Field for #name {
+            type Displayable = Self;
+
+            fn value(&self) -> &Self::Displayable {
+                self
+            }
+
+            fn size(&self) -> usize {
+                0 #( + self.#field_names.size() )*
+            }
+
+            #[allow(unused_variables)]
+            async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+                #read
+            }
+
+            async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+                #( #field_writes.await?; )*
+                Ok(())
+            }
+        }
+
+        #dependent
+        #display
+    };
+
+    result.into()
+}
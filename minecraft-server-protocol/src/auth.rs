@@ -0,0 +1,457 @@
+//! Online mode authentication. After the client's `EncryptionBegin`, it has told the session
+//! server it's joining a server identified by [server_hash], which the server checks with
+//! [SessionServer::has_joined].
+//!
+//! Offline mode servers trust the name in `LoginStart` and derive the UUID from it with
+//! [offline_uuid].
+
+use crate::encryption::{generate_verify_token, ServerKey, SHARED_SECRET_LENGTH};
+use crate::types::{PacketError, PacketResult, UuidField};
+use async_std::future;
+use md5::Md5;
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// Mojang's session server
+pub const MOJANG_SESSION_SERVER: &str = "https://sessionserver.mojang.com";
+
+/// How long [SessionServer::has_joined] waits for a response unless given another timeout
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sent in `EncryptionBegin.server_id`, vanilla leaves it empty
+pub const SERVER_ID: &str = "";
+
+pub const MIN_USERNAME_LENGTH: usize = 3;
+pub const MAX_USERNAME_LENGTH: usize = 16;
+
+pub struct SessionServer {
+    base_url: String,
+    timeout: Duration,
+}
+
+/// Player as authenticated by the session server
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameProfile {
+    pub id: UuidField,
+    pub name: String,
+    /// Skin and cape textures, sent on to clients in `PlayerInfo`
+    pub properties: Vec<ProfileProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProfileProperty {
+    pub name: String,
+    /// Base64
+    pub value: String,
+    /// Base64 signature of `value` by Mojang
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Server side of an online mode login, from `LoginStart` until the client's `EncryptionBegin`
+/// has been checked
+pub struct OnlineLogin {
+    username: String,
+    verify_token: [u8; 4],
+}
+
+/// Mojang's hex SHA-1 of the server ID, shared secret and public key, as a signed two's
+/// complement number
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key_der: &[u8]) -> String {
+    let mut digest: [u8; 20] = Sha1::new()
+        .chain_update(server_id)
+        .chain_update(shared_secret)
+        .chain_update(public_key_der)
+        .finalize()
+        .into();
+
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            let (negated, overflow) = (!*byte).overflowing_add(carry as u8);
+            *byte = negated;
+            carry = overflow;
+        }
+    }
+
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    match hex.trim_start_matches('0') {
+        "" => "0".to_owned(),
+        hex if negative => format!("-{}", hex),
+        hex => hex.to_owned(),
+    }
+}
+
+/// Version 3 UUID of `OfflinePlayer:<username>`, as vanilla assigns in offline mode
+pub fn offline_uuid(username: &str) -> UuidField {
+    let mut bytes: [u8; 16] = Md5::new()
+        .chain_update("OfflinePlayer:")
+        .chain_update(username)
+        .finalize()
+        .into();
+    bytes[6] = bytes[6] & 0x0f | 0x30;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+
+    UuidField::new(u128::from_be_bytes(bytes))
+}
+
+/// 3 to 16 of `[A-Za-z0-9_]`
+pub fn validate_username(username: &str) -> PacketResult<()> {
+    let valid = (MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&username.len())
+        && username
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(PacketError::BadUsername(username.to_owned()))
+    }
+}
+
+impl GameProfile {
+    /// Offline mode profile, without skin textures
+    pub fn offline(username: &str) -> PacketResult<Self> {
+        validate_username(username)?;
+        Ok(Self {
+            id: offline_uuid(username),
+            name: username.to_owned(),
+            properties: Vec::new(),
+        })
+    }
+}
+
+impl SessionServer {
+    /// `base_url` without a trailing `/session/...`, e.g. [MOJANG_SESSION_SERVER]
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            timeout: DEFAULT_SESSION_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Looks up the player that joined with `server_hash`, or `None` if they didn't. `ip` is only
+    /// checked if given, as with vanilla's `prevent-proxy-connections`
+    pub async fn has_joined(
+        &self,
+        username: &str,
+        server_hash: &str,
+        ip: Option<IpAddr>,
+    ) -> PacketResult<Option<GameProfile>> {
+        let url = format!(
+            "{}/session/minecraft/hasJoined",
+            self.base_url.trim_end_matches('/')
+        );
+        let mut url =
+            surf::Url::parse(&url).map_err(|e| PacketError::SessionServer(e.to_string()))?;
+        url.query_pairs_mut()
+            .append_pair("username", username)
+            .append_pair("serverId", server_hash);
+        if let Some(ip) = ip {
+            url.query_pairs_mut().append_pair("ip", &ip.to_string());
+        }
+
+        let body = future::timeout(self.timeout, get(url))
+            .await
+            .map_err(|_| {
+                PacketError::SessionServer(format!("no response within {:?}", self.timeout))
+            })??;
+        match body {
+            Some(body) => serde_json::from_str(&body)
+                .map(Some)
+                .map_err(PacketError::BadProfile),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Body of a 200 response, or `None` for 204
+async fn get(url: surf::Url) -> PacketResult<Option<String>> {
+    let request_error = |e: surf::Error| PacketError::SessionServer(e.to_string());
+
+    let mut response = surf::get(url).await.map_err(request_error)?;
+    match response.status() {
+        surf::StatusCode::Ok => {}
+        surf::StatusCode::NoContent => return Ok(None),
+        status => {
+            return Err(PacketError::SessionServer(format!(
+                "unexpected status {}",
+                status
+            )))
+        }
+    }
+
+    response
+        .body_string()
+        .await
+        .map(Some)
+        .map_err(request_error)
+}
+
+impl Default for SessionServer {
+    fn default() -> Self {
+        Self::new(MOJANG_SESSION_SERVER)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameProfile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            id: String,
+            name: String,
+            #[serde(default)]
+            properties: Vec<ProfileProperty>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        Ok(GameProfile {
+            id: raw.id.parse().map_err(de::Error::custom)?,
+            name: raw.name,
+            properties: raw.properties,
+        })
+    }
+}
+
+impl OnlineLogin {
+    /// On receiving `LoginStart`
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            verify_token: generate_verify_token(),
+        }
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Sent in `EncryptionBegin` along with [SERVER_ID] and [ServerKey::public_key_der]
+    pub fn verify_token(&self) -> &[u8] {
+        &self.verify_token
+    }
+
+    /// Checks the client's encrypted `EncryptionBegin` fields and that it joined through the
+    /// session server, returning the shared secret to enable encryption with
+    pub async fn verify(
+        &self,
+        key: &ServerKey,
+        session: &SessionServer,
+        shared_secret: &[u8],
+        verify_token: &[u8],
+        ip: Option<IpAddr>,
+    ) -> PacketResult<([u8; SHARED_SECRET_LENGTH], GameProfile)> {
+        let shared_secret =
+            key.decrypt_response(shared_secret, verify_token, &self.verify_token)?;
+        let hash = server_hash(SERVER_ID, &shared_secret, key.public_key_der());
+
+        match session.has_joined(&self.username, &hash, ip).await? {
+            Some(profile) => Ok((shared_secret, profile)),
+            None => Err(PacketError::NotAuthenticated(self.username.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::prelude::*;
+    use async_std::net::TcpListener;
+    use async_std::task::{self, JoinHandle};
+    use futures_await_test::async_test;
+    use rand::rngs::OsRng;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::{Pkcs1v15Encrypt, RsaPublicKey};
+
+    const PROFILE: &str = r#"{
+        "id": "069a79f444e94726a5befca90e38aaf5",
+        "name": "Notch",
+        "properties": [{"name": "textures", "value": "e30=", "signature": "c2ln"}]
+    }"#;
+
+    /// Answers one request with `status` and `body`, returning the request line
+    async fn mock_session_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (SessionServer, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = task::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+
+            let request = String::from_utf8(request).unwrap();
+            request.lines().next().unwrap().to_owned()
+        });
+
+        (SessionServer::new(base_url), handle)
+    }
+
+    #[test]
+    fn offline() {
+        assert_eq!(
+            offline_uuid("Notch").to_string(),
+            "b50ad385-829d-3141-a216-7e7d7539ba7f"
+        );
+        assert_eq!(
+            offline_uuid("jeb_").to_string(),
+            "a762f560-4fce-3236-812a-b80efff0b62b"
+        );
+
+        for name in ["abc", "Notch", "jeb_", "___", "ABCDEFGHIJKLMNOP"].iter() {
+            assert!(validate_username(name).is_ok(), "{}", name);
+        }
+        for name in ["", "ab", "ABCDEFGHIJKLMNOPQ", "a b", "Notch!", "Nötch"].iter() {
+            assert!(
+                matches!(validate_username(name), Err(PacketError::BadUsername(n)) if n == *name),
+                "{}",
+                name
+            );
+        }
+
+        let profile = GameProfile::offline("Notch").unwrap();
+        assert_eq!(profile.id, offline_uuid("Notch"));
+        assert!(profile.properties.is_empty());
+        assert!(GameProfile::offline("no").is_err());
+    }
+
+    #[cfg(feature = "v1-15-2")]
+    #[async_test]
+    async fn login_success() -> PacketResult<()> {
+        use crate::types::ClientBound;
+        use crate::v1_15_2::login::client::Success;
+
+        let profile = GameProfile::offline("Notch")?;
+        let packet = Success::new(profile.id, profile.name);
+        let mut buf = vec![0u8; packet.full_size()];
+        packet
+            .write_packet(&mut async_std::io::Cursor::new(buf.as_mut_slice()))
+            .await?;
+        assert_eq!(
+            buf,
+            b"\x2c\x02\x24b50ad385-829d-3141-a216-7e7d7539ba7f\x05Notch".to_vec()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hashes() {
+        let hash = |name: &str| server_hash(name, &[], &[]);
+        assert_eq!(hash("Notch"), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+        assert_eq!(hash("jeb_"), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+        assert_eq!(hash("simon"), "88e16a1019277b15d58faf0541e11910eb756f6");
+    }
+
+    #[async_test]
+    async fn has_joined() -> PacketResult<()> {
+        let (session, request) = mock_session_server("200 OK", PROFILE).await;
+        let profile = session
+            .has_joined("Notch", "-abc", Some("127.0.0.1".parse().unwrap()))
+            .await?
+            .unwrap();
+        assert_eq!(
+            request.await,
+            "GET /session/minecraft/hasJoined?username=Notch&serverId=-abc&ip=127.0.0.1 HTTP/1.1"
+        );
+        assert_eq!(
+            profile.id,
+            UuidField::new(0x069a79f444e94726a5befca90e38aaf5)
+        );
+        assert_eq!(profile.name, "Notch");
+        assert_eq!(
+            profile.properties,
+            vec![ProfileProperty {
+                name: "textures".to_owned(),
+                value: "e30=".to_owned(),
+                signature: Some("c2ln".to_owned()),
+            }]
+        );
+
+        let (session, _) = mock_session_server("204 No Content", "").await;
+        assert!(session.has_joined("Notch", "abc", None).await?.is_none());
+
+        let (session, _) =
+            mock_session_server("200 OK", r#"{"id": "nope", "name": "Notch"}"#).await;
+        assert!(matches!(
+            session.has_joined("Notch", "abc", None).await,
+            Err(PacketError::BadProfile(_))
+        ));
+
+        let (session, _) = mock_session_server("500 Internal Server Error", "").await;
+        assert!(matches!(
+            session.has_joined("Notch", "abc", None).await,
+            Err(PacketError::SessionServer(_))
+        ));
+
+        // accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let session = SessionServer::new(format!("http://{}", listener.local_addr().unwrap()))
+            .with_timeout(Duration::from_millis(100));
+        assert!(matches!(
+            session.has_joined("Notch", "abc", None).await,
+            Err(PacketError::SessionServer(e)) if e.starts_with("no response")
+        ));
+        Ok(())
+    }
+
+    #[async_test]
+    async fn online_login() -> PacketResult<()> {
+        let key = ServerKey::generate()?;
+        let login = OnlineLogin::new("Notch");
+
+        // as the client does
+        let secret = [7u8; SHARED_SECRET_LENGTH];
+        let public = RsaPublicKey::from_public_key_der(key.public_key_der()).unwrap();
+        let encrypted_secret = public.encrypt(&mut OsRng, Pkcs1v15Encrypt, &secret)?;
+        let encrypted_token = public.encrypt(&mut OsRng, Pkcs1v15Encrypt, login.verify_token())?;
+
+        let (session, request) = mock_session_server("200 OK", PROFILE).await;
+        let (shared_secret, profile) = login
+            .verify(&key, &session, &encrypted_secret, &encrypted_token, None)
+            .await?;
+        assert_eq!(shared_secret, secret);
+        assert_eq!(profile.name, "Notch");
+
+        let hash = server_hash("", &secret, key.public_key_der());
+        assert!(request.await.contains(&format!("serverId={}", hash)));
+
+        let (session, _) = mock_session_server("204 No Content", "").await;
+        assert!(matches!(
+            login
+                .verify(&key, &session, &encrypted_secret, &encrypted_token, None)
+                .await,
+            Err(PacketError::NotAuthenticated(name)) if name == "Notch"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+//! Online mode encryption. The server sends its RSA public key and a verify token in
+//! `EncryptionBegin`, the client replies with a random shared secret and the token both encrypted
+//! with that key, then both sides switch to AES/CFB8 keyed by the shared secret.
+
+use crate::types::{PacketError, PacketResult};
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use aes::Aes128;
+use async_std::io::{self, Read, Write};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rsa::pkcs8::EncodePublicKey;
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub const SHARED_SECRET_LENGTH: usize = 16;
+
+/// Vanilla's key size
+const KEY_BITS: usize = 1024;
+
+/// Server key pair, generated once on startup
+pub struct ServerKey {
+    private: RsaPrivateKey,
+    public_der: Vec<u8>,
+}
+
+/// Decrypts everything read once enabled
+pub struct EncryptedReader<R> {
+    inner: R,
+    cipher: Option<cfb8::Decryptor<Aes128>>,
+}
+
+/// Encrypts everything written once enabled
+pub struct EncryptedWriter<W> {
+    inner: W,
+    cipher: Option<cfb8::Encryptor<Aes128>>,
+    /// Encrypted but not yet accepted by `inner`
+    pending: Vec<u8>,
+}
+
+impl ServerKey {
+    pub fn generate() -> PacketResult<Self> {
+        let private = RsaPrivateKey::new(&mut OsRng, KEY_BITS)?;
+        let public_der = RsaPublicKey::from(&private)
+            .to_public_key_der()
+            .expect("RSA public keys always encode")
+            .into_vec();
+
+        Ok(Self {
+            private,
+            public_der,
+        })
+    }
+
+    /// X.509 SubjectPublicKeyInfo, as sent in `EncryptionBegin.public_key`
+    pub fn public_key_der(&self) -> &[u8] {
+        &self.public_der
+    }
+
+    /// PKCS#1 v1.5
+    pub fn decrypt(&self, encrypted: &[u8]) -> PacketResult<Vec<u8>> {
+        Ok(self.private.decrypt(Pkcs1v15Encrypt, encrypted)?)
+    }
+
+    /// Decrypts the fields of the client's `EncryptionBegin`, checking it echoed `verify_token`,
+    /// and returns the shared secret
+    pub fn decrypt_response(
+        &self,
+        shared_secret: &[u8],
+        verify_token: &[u8],
+        expected_token: &[u8],
+    ) -> PacketResult<[u8; SHARED_SECRET_LENGTH]> {
+        if self.decrypt(verify_token)? != expected_token {
+            return Err(PacketError::BadVerifyToken);
+        }
+
+        let shared_secret = self.decrypt(shared_secret)?;
+        let mut out = [0u8; SHARED_SECRET_LENGTH];
+        if shared_secret.len() != out.len() {
+            return Err(PacketError::BadSharedSecret(shared_secret.len()));
+        }
+        out.copy_from_slice(&shared_secret);
+        Ok(out)
+    }
+}
+
+/// Random token for `EncryptionBegin.verify_token`
+pub fn generate_verify_token() -> [u8; 4] {
+    let mut token = [0u8; 4];
+    OsRng.fill_bytes(&mut token);
+    token
+}
+
+/// The shared secret is both the key and IV
+fn cipher<C: KeyIvInit>(shared_secret: &[u8]) -> PacketResult<C> {
+    C::new_from_slices(shared_secret, shared_secret)
+        .map_err(|_| PacketError::BadSharedSecret(shared_secret.len()))
+}
+
+impl<R> EncryptedReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cipher: None,
+        }
+    }
+
+    /// Decrypts from the next byte read
+    pub fn enable(&mut self, shared_secret: &[u8]) -> PacketResult<()> {
+        self.cipher = Some(cipher(shared_secret)?);
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<W> EncryptedWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            cipher: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Encrypts from the next byte written
+    pub fn enable(&mut self, shared_secret: &[u8]) -> PacketResult<()> {
+        self.cipher = Some(cipher(shared_secret)?);
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<R: Read + Unpin> Read for EncryptedReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let read = match Pin::new(&mut this.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(read)) => read,
+            other => return other,
+        };
+
+        if let Some(cipher) = &mut this.cipher {
+            for byte in &mut buf[..read] {
+                cipher.decrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+            }
+        }
+
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl<W: Write + Unpin> Write for EncryptedWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let Self {
+            inner,
+            cipher,
+            pending,
+        } = self.get_mut();
+        let cipher = match cipher {
+            Some(cipher) => cipher,
+            None => return Pin::new(inner).poll_write(cx, buf),
+        };
+
+        match poll_pending(inner, pending, cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        // the cipher state moves on with every byte, so they're accepted once encrypted and
+        // written later if the inner writer isn't ready
+        pending.extend_from_slice(buf);
+        for byte in pending.iter_mut() {
+            cipher.encrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+        }
+
+        match poll_pending(inner, pending, cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            _ => Poll::Ready(Ok(buf.len())),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match poll_pending(&mut this.inner, &mut this.pending, cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_flush(cx),
+            other => other,
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match poll_pending(&mut this.inner, &mut this.pending, cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_close(cx),
+            other => other,
+        }
+    }
+}
+
+/// Writes `pending` until it's empty or `inner` isn't ready
+fn poll_pending<W: Write + Unpin>(
+    inner: &mut W,
+    pending: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    while !pending.is_empty() {
+        match Pin::new(&mut *inner).poll_write(cx, pending) {
+            Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+            Poll::Ready(Ok(written)) => {
+                pending.drain(..written);
+            }
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+
+    Poll::Ready(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{FrameReader, FrameWriter};
+    use crate::types::PacketBody;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+    use rsa::pkcs8::DecodePublicKey;
+
+    const SECRET: [u8; SHARED_SECRET_LENGTH] = *b"0123456789abcdef";
+
+    #[async_test]
+    async fn encrypted_frames() -> PacketResult<()> {
+        let frame = |id| PacketBody {
+            id,
+            body: b"hello".to_vec(),
+        };
+
+        let mut writer = FrameWriter::new(EncryptedWriter::new(Vec::new()));
+        writer.write_frame(&frame(1)).await?;
+        writer.get_mut().enable(&SECRET)?;
+        writer.write_frame(&frame(2)).await?;
+        writer.write_frame(&frame(3)).await?;
+        writer.flush().await?;
+
+        let bytes = writer.into_inner().into_inner();
+        assert_eq!(&bytes[..7], b"\x06\x01hello");
+        assert_ne!(&bytes[7..14], b"\x06\x02hello");
+
+        let mut reader = FrameReader::new(EncryptedReader::new(Cursor::new(bytes)));
+        assert_eq!(reader.read_frame().await?.unwrap().id, 1);
+        reader.get_mut().enable(&SECRET)?;
+        for id in 2..=3 {
+            let read = reader.read_frame().await?.unwrap();
+            assert_eq!((read.id, read.body), (id, b"hello".to_vec()));
+        }
+        assert!(reader.read_frame().await?.is_none());
+
+        assert!(matches!(
+            EncryptedReader::new(()).enable(&SECRET[..8]),
+            Err(PacketError::BadSharedSecret(8))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn key_exchange() -> PacketResult<()> {
+        let key = ServerKey::generate()?;
+        let token = generate_verify_token();
+
+        // as the client does
+        let public = RsaPublicKey::from_public_key_der(key.public_key_der()).unwrap();
+        let encrypt = |data: &[u8]| public.encrypt(&mut OsRng, Pkcs1v15Encrypt, data);
+        let encrypted_secret = encrypt(&SECRET)?;
+
+        assert_eq!(
+            key.decrypt_response(&encrypted_secret, &encrypt(&token)?, &token)?,
+            SECRET
+        );
+        assert!(matches!(
+            key.decrypt_response(&encrypted_secret, &encrypt(b"nope")?, &token),
+            Err(PacketError::BadVerifyToken)
+        ));
+        assert!(matches!(
+            key.decrypt_response(&encrypt(&SECRET[..8])?, &encrypt(&token)?, &token),
+            Err(PacketError::BadSharedSecret(8))
+        ));
+        assert!(matches!(
+            key.decrypt_response(&encrypted_secret, &token, &token),
+            Err(PacketError::Rsa(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,456 @@
+//! Packet framing over a stream: each packet is a varint length followed by that many bytes,
+//! starting with the varint packet ID.
+//!
+//! Once compression is enabled by the login `Compress` packet, the length is followed by the
+//! varint length of the uncompressed ID and body, or 0 if they're below the threshold and sent
+//! uncompressed, then the zlib compressed ID and body.
+
+use crate::types::{ClientBound, Field, PacketBody, PacketError, PacketResult, VarIntField};
+use async_std::io::prelude::*;
+use async_std::io::Cursor;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use std::io::Write as _;
+use std::mem;
+
+/// Longest frame vanilla accepts, the max of a 3 byte varint
+pub const MAX_FRAME_LENGTH: usize = 2_097_151;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Compression {
+    /// Packets with an ID and body of at least this many bytes are compressed
+    pub threshold: usize,
+    /// zlib level from 0 to 9
+    pub level: u32,
+}
+
+/// Reads [PacketBody]s from a stream
+pub struct FrameReader<R> {
+    inner: R,
+    max_length: usize,
+    compression: Option<Compression>,
+}
+
+/// Writes length prefixed packets to a stream
+pub struct FrameWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    compression: Option<Compression>,
+}
+
+impl Compression {
+    /// Default zlib level
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            level: flate2::Compression::default().level(),
+        }
+    }
+
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+impl<R: Read + Unpin + Send> FrameReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            max_length: MAX_FRAME_LENGTH,
+            compression: None,
+        }
+    }
+
+    /// Switches the format of the following frames, to match the writer
+    pub fn set_compression(&mut self, compression: Option<Compression>) {
+        self.compression = compression;
+    }
+
+    /// Frames longer than `max_length`, or that inflate to more than it, are rejected before being
+    /// read
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Reads the next frame, or `None` if the stream ended cleanly before it
+    pub async fn read_frame(&mut self) -> PacketResult<Option<PacketBody>> {
+        let mut first = [0u8; 1];
+        if self.inner.read(&mut first).await.map_err(PacketError::Io)? == 0 {
+            return Ok(None);
+        }
+
+        let length = VarIntField::read_field(&mut (&first[..]).chain(&mut self.inner))
+            .await?
+            .value();
+        if length <= 0 {
+            return Err(PacketError::BadFrameLength(length));
+        }
+        if length as usize > self.max_length {
+            return Err(PacketError::FrameTooLong {
+                length: length as usize,
+                max: self.max_length,
+            });
+        }
+
+        let mut frame = vec![0u8; length as usize];
+        self.inner
+            .read_exact(&mut frame)
+            .await
+            .map_err(PacketError::Io)?;
+
+        if let Some(compression) = self.compression {
+            frame = self.inflate(frame, compression.threshold).await?;
+        }
+
+        let mut cursor = Cursor::new(frame);
+        let id = VarIntField::read_field(&mut cursor).await?.value();
+        let id_size = cursor.position() as usize;
+        let mut body = cursor.into_inner();
+        body.drain(..id_size);
+
+        Ok(Some(PacketBody { id, body }))
+    }
+
+    /// ID and body of a compressed frame
+    async fn inflate(&self, frame: Vec<u8>, threshold: usize) -> PacketResult<Vec<u8>> {
+        use std::io::Read;
+
+        let mut cursor = Cursor::new(frame);
+        let data_length = VarIntField::read_field(&mut cursor).await?.value();
+        let compressed_start = cursor.position() as usize;
+        let mut frame = cursor.into_inner();
+
+        if data_length == 0 {
+            frame.drain(..compressed_start);
+            return Ok(frame);
+        }
+        if data_length < 0 || data_length as usize > self.max_length {
+            return Err(PacketError::BadDataLength(data_length));
+        }
+        let data_length = data_length as usize;
+        if data_length < threshold {
+            return Err(PacketError::CompressedBelowThreshold {
+                length: data_length,
+                threshold,
+            });
+        }
+
+        // reading one byte past the declared length is enough to catch a mismatch
+        let mut data = Vec::with_capacity(data_length);
+        ZlibDecoder::new(&frame[compressed_start..])
+            .take(data_length as u64 + 1)
+            .read_to_end(&mut data)?;
+        if data.len() != data_length {
+            return Err(PacketError::DataLengthMismatch {
+                declared: data_length,
+                actual: data.len(),
+            });
+        }
+
+        Ok(data)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<W: Write + Unpin + Send> FrameWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            compression: None,
+        }
+    }
+
+    /// Switches the format of the following frames, e.g. after writing the `Compress` packet
+    pub fn set_compression(&mut self, compression: Option<Compression>) {
+        self.compression = compression;
+    }
+
+    pub async fn write_packet<P: ClientBound + ?Sized>(&mut self, packet: &P) -> PacketResult<()> {
+        let size = packet.full_size();
+        self.buf.clear();
+        self.buf.resize(size, 0);
+
+        let mut cursor = Cursor::new(self.buf.as_mut_slice());
+        packet.write_packet(&mut cursor).await?;
+        if cursor.position() as usize != size {
+            return Err(PacketError::PacketLengthMismatch {
+                length: size,
+                written: cursor.position() as usize,
+            });
+        }
+
+        if self.compression.is_none() {
+            return self
+                .inner
+                .write_all(&self.buf)
+                .await
+                .map_err(PacketError::Io);
+        }
+
+        let buf = mem::take(&mut self.buf);
+        let length_size = VarIntField::new(packet.length() as i32).size();
+        let result = self.write_payload(&buf[length_size..]).await;
+        self.buf = buf;
+        result
+    }
+
+    /// Writes an already encoded packet
+    pub async fn write_frame(&mut self, body: &PacketBody) -> PacketResult<()> {
+        let mut buf = mem::take(&mut self.buf);
+        buf.clear();
+        VarIntField::new(body.id).write_field(&mut buf).await?;
+        buf.extend_from_slice(&body.body);
+
+        let result = self.write_payload(&buf).await;
+        self.buf = buf;
+        result
+    }
+
+    /// Frames and writes an ID and body
+    async fn write_payload(&mut self, payload: &[u8]) -> PacketResult<()> {
+        let compression = match self.compression {
+            None => {
+                VarIntField::new(payload.len() as i32)
+                    .write_field(&mut self.inner)
+                    .await?;
+                return self.inner.write_all(payload).await.map_err(PacketError::Io);
+            }
+            Some(compression) => compression,
+        };
+
+        if payload.len() < compression.threshold {
+            let data_length = VarIntField::new(0);
+            VarIntField::new((data_length.size() + payload.len()) as i32)
+                .write_field(&mut self.inner)
+                .await?;
+            data_length.write_field(&mut self.inner).await?;
+            return self.inner.write_all(payload).await.map_err(PacketError::Io);
+        }
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::new(compression.level));
+        encoder.write_all(payload)?;
+        let compressed = encoder.finish()?;
+
+        let data_length = VarIntField::new(payload.len() as i32);
+        VarIntField::new((data_length.size() + compressed.len()) as i32)
+            .write_field(&mut self.inner)
+            .await?;
+        data_length.write_field(&mut self.inner).await?;
+        self.inner
+            .write_all(&compressed)
+            .await
+            .map_err(PacketError::Io)
+    }
+
+    pub async fn flush(&mut self) -> PacketResult<()> {
+        self.inner.flush().await.map_err(PacketError::Io)
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use async_std::io;
+    use futures_await_test::async_test;
+    use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+    use std::fmt::{Display, Formatter};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x1a]
+    struct Disconnect {
+        reason: StringField,
+    }
+
+    #[derive(ServerBoundPacket)]
+    #[packet_id = 0x1a]
+    struct DisconnectEcho {
+        reason: StringField,
+    }
+
+    /// Reads a byte at a time
+    struct Trickle(Cursor<Vec<u8>>);
+
+    impl Read for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let len = buf.len().min(1);
+            Pin::new(&mut self.0).poll_read(cx, &mut buf[..len])
+        }
+    }
+
+    #[async_test]
+    async fn round_trip() -> PacketResult<()> {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer
+            .write_packet(&Disconnect {
+                reason: StringField::new("bye".to_owned()),
+            })
+            .await?;
+        writer
+            .write_frame(&PacketBody {
+                id: 0x200,
+                body: vec![1, 2, 3],
+            })
+            .await?;
+        let bytes = writer.into_inner();
+        assert_eq!(bytes, b"\x05\x1a\x03bye\x05\x80\x04\x01\x02\x03");
+
+        let mut reader = FrameReader::new(Trickle(Cursor::new(bytes)));
+        let packet = DisconnectEcho::read_packet(reader.read_frame().await?.unwrap()).await?;
+        assert_eq!(packet.reason.value(), "bye");
+
+        let frame = reader.read_frame().await?.unwrap();
+        assert_eq!(frame.id, 0x200);
+        assert_eq!(frame.body, vec![1, 2, 3]);
+
+        assert!(reader.read_frame().await?.is_none());
+        Ok(())
+    }
+
+    #[async_test]
+    async fn compressed_round_trip() -> PacketResult<()> {
+        let compression = Compression::new(64).with_level(9);
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.set_compression(Some(compression));
+        writer
+            .write_packet(&Disconnect {
+                reason: StringField::new("bye".to_owned()),
+            })
+            .await?;
+        let small = writer.get_ref().clone();
+        assert_eq!(small, b"\x06\x00\x1a\x03bye");
+
+        let long = "a".repeat(1000);
+        writer
+            .write_packet(&Disconnect {
+                reason: StringField::new(long.clone()),
+            })
+            .await?;
+        writer
+            .write_frame(&PacketBody {
+                id: 0x05,
+                body: vec![0; 100],
+            })
+            .await?;
+        let bytes = writer.into_inner();
+        assert!(bytes.len() < 1000);
+
+        let mut reader = FrameReader::new(Trickle(Cursor::new(bytes)));
+        reader.set_compression(Some(compression));
+        for expected in ["bye", long.as_str()].iter() {
+            let packet = DisconnectEcho::read_packet(reader.read_frame().await?.unwrap()).await?;
+            assert_eq!(packet.reason.value(), expected);
+        }
+
+        let frame = reader.read_frame().await?.unwrap();
+        assert_eq!((frame.id, frame.body), (0x05, vec![0; 100]));
+        assert!(reader.read_frame().await?.is_none());
+        Ok(())
+    }
+
+    #[async_test]
+    async fn bad_compressed_frames() -> PacketResult<()> {
+        let compressed = |data_length: i32| async move {
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&[0x05; 100])?;
+            let compressed = encoder.finish()?;
+
+            let mut frame = Vec::new();
+            let data_length = VarIntField::new(data_length);
+            VarIntField::new((data_length.size() + compressed.len()) as i32)
+                .write_field(&mut frame)
+                .await?;
+            data_length.write_field(&mut frame).await?;
+            frame.extend_from_slice(&compressed);
+
+            let mut reader = FrameReader::new(Cursor::new(frame));
+            reader.set_compression(Some(Compression::new(64)));
+            reader.read_frame().await
+        };
+
+        assert_eq!(compressed(100).await?.unwrap().body.len(), 99);
+        assert!(matches!(
+            compressed(101).await,
+            Err(PacketError::DataLengthMismatch {
+                declared: 101,
+                actual: 100
+            })
+        ));
+        assert!(matches!(
+            compressed(99).await,
+            Err(PacketError::DataLengthMismatch {
+                declared: 99,
+                actual: 100
+            })
+        ));
+        assert!(matches!(
+            compressed(10).await,
+            Err(PacketError::CompressedBelowThreshold {
+                length: 10,
+                threshold: 64
+            })
+        ));
+        assert!(matches!(
+            compressed(-1).await,
+            Err(PacketError::BadDataLength(-1))
+        ));
+        Ok(())
+    }
+
+    #[async_test]
+    async fn bad_frames() {
+        let read = |bytes: &[u8]| {
+            let mut reader = FrameReader::new(Cursor::new(bytes.to_vec())).with_max_length(4);
+            async move { reader.read_frame().await }
+        };
+
+        assert!(matches!(
+            read(b"\x05\x00\x00\x00\x00\x00").await,
+            Err(PacketError::FrameTooLong { length: 5, max: 4 })
+        ));
+        assert!(matches!(
+            read(b"\x00").await,
+            Err(PacketError::BadFrameLength(0))
+        ));
+        assert!(matches!(
+            read(b"\x03\x00\x00").await,
+            Err(PacketError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+        assert!(matches!(
+            read(b"\x80").await,
+            Err(PacketError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+}
@@ -1,8 +1,15 @@
+#[cfg(feature = "auth")]
+pub mod auth;
+#[cfg(feature = "auth")]
+pub mod encryption;
+pub mod frame;
 pub mod types;
 
 macro_rules! version {
     ($module:ident, $feature:expr) => {
         #[cfg(feature = $feature)]
+        // generated switch enums hold their cases inline, e.g. a 1024 int array next to a unit
+        #[allow(clippy::large_enum_variant)]
         pub mod $module;
     };
 }
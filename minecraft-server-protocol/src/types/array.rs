@@ -1,21 +1,40 @@
-use crate::types::field::Field;
-use crate::types::{NbtField, PacketError, PacketResult, VarIntField};
+use crate::types::field::{DependentField, Field};
+use crate::types::{
+    ByteField, IntField, PacketError, PacketResult, ShortField, UByteField, UShortField,
+    VarIntField,
+};
 use async_std::io::prelude::*;
 use async_trait::async_trait;
-use std::fmt::{Display, Formatter};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// Don't trust the length prefix with more than this many elements up front
+const MAX_PREALLOCATED: usize = 1024;
 
 pub struct VarIntThenByteArrayField {
     length: VarIntField,
     array: ByteArray,
 }
 
+/// Integer field that can prefix an array with its length
+pub trait ArrayLength: Field {
+    fn to_length(&self) -> PacketResult<usize>;
+    fn from_length(len: usize) -> PacketResult<Self>;
+}
+
+/// Array of `T` prefixed by its length as a `C`, usable as a slice
 pub struct PrefixedArrayField<C, T> {
-    count: C,
-    array: ByteArray,
-    phantom: PhantomData<T>,
+    elems: Vec<T>,
+    phantom: PhantomData<C>,
 }
 
+/// Array of exactly `N` elements with no length prefix
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedArrayField<T, const N: usize>(pub [T; N]);
+
 impl VarIntThenByteArrayField {
     pub fn new(buf: Vec<u8>) -> Self {
         Self {
@@ -93,23 +112,336 @@ impl Field for RestOfPacketByteArrayField {
     }
 }
 
+macro_rules! array_length {
+    ($($field:ty),*) => {
+        $(
+            impl ArrayLength for $field {
+                fn to_length(&self) -> PacketResult<usize> {
+                    let len = *Field::value(self);
+                    usize::try_from(len).map_err(|_| PacketError::BadArrayLength(len as i64))
+                }
+
+                fn from_length(len: usize) -> PacketResult<Self> {
+                    TryFrom::try_from(len)
+                        .map(Self::new)
+                        .map_err(|_| PacketError::ArrayTooLong(len))
+                }
+            }
+        )*
+    };
+}
+
+array_length!(
+    VarIntField,
+    ByteField,
+    ShortField,
+    IntField,
+    UByteField,
+    UShortField
+);
+
+impl<C, T> PrefixedArrayField<C, T> {
+    pub fn new(elems: Vec<T>) -> Self {
+        Self {
+            elems,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.elems.push(elem)
+    }
+
+    pub fn take(self) -> Vec<T> {
+        self.elems
+    }
+}
+
 #[async_trait]
-impl<C: Field + Send + Sync, T: Field + Send + Sync> Field for PrefixedArrayField<C, T> {
-    type Displayable = ByteArray; // TODO do better
+impl<C: ArrayLength + Send + Sync, T: Field + Send + Sync> Field for PrefixedArrayField<C, T> {
+    type Displayable = Self;
 
     fn value(&self) -> &Self::Displayable {
-        &self.array
+        self
     }
 
+    /// Only includes the length prefix if it fits in `C`
     fn size(&self) -> usize {
-        todo!()
+        let prefix = C::from_length(self.elems.len()).map_or(0, |len| len.size());
+        prefix + self.elems.iter().map(T::size).sum::<usize>()
     }
 
     async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
-        todo!()
+        let len = C::read_field(r).await?.to_length()?;
+        let mut elems = Vec::with_capacity(len.min(MAX_PREALLOCATED));
+        for _ in 0..len {
+            elems.push(T::read_field(r).await?);
+        }
+
+        Ok(Self::new(elems))
     }
 
     async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
-        todo!()
+        C::from_length(self.elems.len())?.write_field(w).await?;
+        for elem in &self.elems {
+            elem.write_field(w).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Each element is given the array's dependency
+#[async_trait]
+impl<C, T> DependentField for PrefixedArrayField<C, T>
+where
+    C: ArrayLength + Send + Sync,
+    T: DependentField + Send + Sync,
+{
+    type Dependency = T::Dependency;
+
+    async fn read_dependent<R: Read + Unpin + Send>(
+        r: &mut R,
+        dependency: &Self::Dependency,
+    ) -> PacketResult<Self> {
+        let len = C::read_field(r).await?.to_length()?;
+        let mut elems = Vec::with_capacity(len.min(MAX_PREALLOCATED));
+        for _ in 0..len {
+            elems.push(T::read_dependent(r, dependency).await?);
+        }
+
+        Ok(Self::new(elems))
+    }
+
+    async fn write_dependent<W: Write + Unpin + Send>(
+        &self,
+        w: &mut W,
+        dependency: &Self::Dependency,
+    ) -> PacketResult<()> {
+        C::from_length(self.elems.len())?.write_field(w).await?;
+        for elem in &self.elems {
+            elem.write_dependent(w, dependency).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, T> Default for PrefixedArrayField<C, T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<C, T: Clone> Clone for PrefixedArrayField<C, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.elems.clone())
+    }
+}
+
+impl<C, T: Debug> Debug for PrefixedArrayField<C, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.elems.fmt(f)
+    }
+}
+
+impl<C, T: PartialEq> PartialEq for PrefixedArrayField<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.elems == other.elems
+    }
+}
+
+impl<C, T> Deref for PrefixedArrayField<C, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.elems
+    }
+}
+
+impl<C, T> DerefMut for PrefixedArrayField<C, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.elems
+    }
+}
+
+impl<C, T> From<Vec<T>> for PrefixedArrayField<C, T> {
+    fn from(elems: Vec<T>) -> Self {
+        Self::new(elems)
+    }
+}
+
+impl<C, T> FromIterator<T> for PrefixedArrayField<C, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<C, T> IntoIterator for PrefixedArrayField<C, T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.into_iter()
+    }
+}
+
+impl<'a, C, T> IntoIterator for &'a PrefixedArrayField<C, T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter()
+    }
+}
+
+impl<C, T: Field> Display for PrefixedArrayField<C, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_elems(&self.elems, f)
+    }
+}
+
+fn fmt_elems<T: Field>(elems: &[T], f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.write_str("[")?;
+    for (i, elem) in elems.iter().enumerate() {
+        if i != 0 {
+            f.write_str(", ")?;
+        }
+        elem.value().fmt(f)?;
+    }
+    f.write_str("]")
+}
+
+impl<T, const N: usize> FixedArrayField<T, N> {
+    pub fn new(elems: [T; N]) -> Self {
+        Self(elems)
+    }
+
+    pub fn take(self) -> [T; N] {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<T: Field + Send + Sync, const N: usize> Field for FixedArrayField<T, N> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        self.0.iter().map(T::size).sum()
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut elems = Vec::with_capacity(N);
+        for _ in 0..N {
+            elems.push(T::read_field(r).await?);
+        }
+
+        Ok(Self(elems.try_into().unwrap_or_else(|_| unreachable!())))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        for elem in &self.0 {
+            elem.write_field(w).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Deref for FixedArrayField<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for FixedArrayField<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for FixedArrayField<T, N> {
+    fn from(elems: [T; N]) -> Self {
+        Self(elems)
+    }
+}
+
+impl<T: Field, const N: usize> Display for FixedArrayField<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_elems(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StringField;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    #[async_test]
+    async fn prefixed_array_round_trip() -> PacketResult<()> {
+        let mut array = PrefixedArrayField::<VarIntField, IntField>::default();
+        array.push(IntField::new(1));
+        array.push(IntField::new(-2));
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.to_string(), "[1, -2]");
+
+        let mut cursor = Cursor::new(Vec::new());
+        array.write_field(&mut cursor).await?;
+        assert_eq!(
+            cursor.get_ref().as_slice(),
+            b"\x02\x00\x00\x00\x01\xff\xff\xff\xfe"
+        );
+        assert_eq!(array.size(), 9);
+
+        cursor.set_position(0);
+        let read = PrefixedArrayField::<VarIntField, IntField>::read_field(&mut cursor).await?;
+        let values = read.iter().map(|i| *i.value()).collect::<Vec<_>>();
+        assert_eq!(values, vec![1, -2]);
+
+        let mut cursor = Cursor::new(b"\x00\x02\x01a\x02bc".to_vec());
+        let read = PrefixedArrayField::<ShortField, StringField>::read_field(&mut cursor).await?;
+        assert_eq!(read.to_string(), "[a, bc]");
+        assert_eq!(read.size(), 7);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn prefixed_array_bad_length() {
+        let mut cursor = Cursor::new(b"\xff\x00".to_vec());
+        assert!(matches!(
+            PrefixedArrayField::<ByteField, ByteField>::read_field(&mut cursor).await,
+            Err(PacketError::BadArrayLength(-1))
+        ));
+
+        let array = (0..200)
+            .map(|_| ByteField::new(0))
+            .collect::<PrefixedArrayField<ByteField, _>>();
+        let mut cursor = Cursor::new(Vec::new());
+        assert!(matches!(
+            array.write_field(&mut cursor).await,
+            Err(PacketError::ArrayTooLong(200))
+        ));
+    }
+
+    #[async_test]
+    async fn fixed_array() -> PacketResult<()> {
+        let mut cursor = Cursor::new(b"\x01\xff\x00".to_vec());
+        let array = FixedArrayField::<ByteField, 2>::read_field(&mut cursor).await?;
+        assert_eq!(array.to_string(), "[1, -1]");
+        assert_eq!(array.size(), 2);
+        assert_eq!(cursor.position(), 2);
+
+        let mut cursor = Cursor::new(Vec::new());
+        array.write_field(&mut cursor).await?;
+        assert_eq!(cursor.get_ref().as_slice(), b"\x01\xff");
+        Ok(())
     }
 }
@@ -0,0 +1,142 @@
+use crate::types::field::Field;
+use crate::types::{PacketError, PacketResult};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+
+/// Big-endian integer of `BYTES` bytes holding packed members, the first member in the most
+/// significant bits. Generated packets wrap this with typed getters per member
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct BitfieldField<const BYTES: usize>(u64);
+
+impl<const BYTES: usize> BitfieldField<BYTES> {
+    const BITS: u32 = BYTES as u32 * 8;
+
+    /// Bits above the field width are discarded
+    pub fn new(raw: u64) -> Self {
+        Self(raw & mask(Self::BITS))
+    }
+
+    pub fn raw(&self) -> u64 {
+        self.0
+    }
+
+    /// Member of `size` bits starting `shift` bits from the least significant end
+    pub fn unsigned(&self, shift: u32, size: u32) -> u64 {
+        debug_assert!(shift + size <= Self::BITS);
+        (self.0 >> shift) & mask(size)
+    }
+
+    /// As [unsigned](Self::unsigned) but sign extended from the top bit of the member
+    pub fn signed(&self, shift: u32, size: u32) -> i64 {
+        debug_assert!(size > 0);
+        let unused = 64 - size;
+        ((self.unsigned(shift, size) << unused) as i64) >> unused
+    }
+
+    /// Bits of `value` that don't fit in the member are discarded
+    pub fn set(&mut self, shift: u32, size: u32, value: u64) {
+        debug_assert!(shift + size <= Self::BITS);
+        let mask = mask(size) << shift;
+        self.0 = (self.0 & !mask) | ((value << shift) & mask);
+    }
+}
+
+fn mask(bits: u32) -> u64 {
+    match bits {
+        64 => u64::MAX,
+        n => (1 << n) - 1,
+    }
+}
+
+#[async_trait]
+impl<const BYTES: usize> Field for BitfieldField<BYTES> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        BYTES
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut buf = [0u8; 8];
+        r.read_exact(&mut buf[8 - BYTES..])
+            .await
+            .map_err(PacketError::Io)?;
+        Ok(Self(u64::from_be_bytes(buf)))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        let buf = self.0.to_be_bytes();
+        w.write_all(&buf[8 - BYTES..])
+            .await
+            .map_err(PacketError::Io)
+    }
+}
+
+impl<const BYTES: usize> Display for BitfieldField<BYTES> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#0width$x}", self.0, width = BYTES * 2 + 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+    use quickcheck_macros::quickcheck;
+
+    #[async_test]
+    async fn chunk_section_position() -> PacketResult<()> {
+        // x: i22, z: i22, y: u20 as in 1.16.2 multi block change
+        let mut field = BitfieldField::<8>::default();
+        field.set(42, 22, -3i64 as u64);
+        field.set(20, 22, 70);
+        field.set(0, 20, 4);
+        assert_eq!(field.signed(42, 22), -3);
+        assert_eq!(field.signed(20, 22), 70);
+        assert_eq!(field.unsigned(0, 20), 4);
+        assert_eq!(field.to_string(), "0xfffff40004600004");
+
+        let mut cursor = Cursor::new(Vec::new());
+        field.write_field(&mut cursor).await?;
+        assert_eq!(
+            cursor.get_ref().as_slice(),
+            b"\xff\xff\xf4\x00\x04\x60\x00\x04"
+        );
+
+        cursor.set_position(0);
+        assert_eq!(BitfieldField::<8>::read_field(&mut cursor).await?, field);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn single_byte_flags() -> PacketResult<()> {
+        // unused: 3, has_custom_suggestions, has_redirect_node, has_command, command_node_type: 2
+        let mut cursor = Cursor::new(vec![0b0000_1110u8]);
+        let field = BitfieldField::<1>::read_field(&mut cursor).await?;
+        assert_eq!(field.unsigned(4, 1), 0);
+        assert_eq!(field.unsigned(3, 1), 1);
+        assert_eq!(field.unsigned(2, 1), 1);
+        assert_eq!(field.unsigned(0, 2), 2);
+        assert_eq!(field.size(), 1);
+
+        assert_eq!(BitfieldField::<1>::new(0x1ff).raw(), 0xff);
+        Ok(())
+    }
+
+    #[quickcheck]
+    fn signed_members(value: i16, shift: u8) -> bool {
+        let shift = u32::from(shift % 17);
+        let mut field = BitfieldField::<4>::new(u64::MAX);
+        field.set(shift, 16, value as u64);
+
+        field.signed(shift, 16) == i64::from(value)
+            && field.unsigned(shift, 16) == u64::from(value as u16)
+            && field.raw() | (mask(16) << shift) == mask(32)
+    }
+}
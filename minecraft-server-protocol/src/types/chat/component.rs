@@ -0,0 +1,897 @@
+//! JSON chat components, as in `{"text": "hi", "color": "red", "extra": [...]}`.
+//!
+//! Decoding accepts anything vanilla does apart from NBT components: plain strings, arrays whose
+//! first element is the parent of the rest, and both the legacy `value` and 1.16 `contents` forms
+//! of hover events. Encoding is per protocol version, see [TextComponent::to_json_for].
+
+use crate::types::NbtCompound;
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// < only has named colours and a legacy `value` for hover events, >= adds hex colours, fonts and
+/// hover event `contents` (20w17a)
+pub(crate) const HEX_COLOR_CUTOFF: u32 = 713;
+
+/// Encodes for the latest version
+const LATEST: u32 = u32::MAX;
+
+/// Chat message with a style inherited by its `extra` children
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextComponent {
+    pub content: Content,
+    pub style: Style,
+    pub extra: Vec<TextComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    Text(String),
+    /// Translation key, with the components substituted into it
+    Translate {
+        key: String,
+        with: Vec<TextComponent>,
+    },
+    Score(Score),
+    /// Entity selector, e.g. `@p`
+    Selector(String),
+    /// Control shown as the key bound to it, e.g. `key.jump`
+    Keybind(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    /// Player name or selector
+    pub name: String,
+    pub objective: String,
+    /// Resolved by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// Unset fields are inherited from the parent component
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Style {
+    pub color: Option<Color>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+    /// Resource location of the font, from 1.16
+    pub font: Option<String>,
+    /// Inserted into the chat box when shift clicked
+    pub insertion: Option<String>,
+    pub click_event: Option<ClickEvent>,
+    pub hover_event: Option<HoverEvent>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Color {
+    Named(NamedColor),
+    /// `#rrggbb`, from 1.16
+    Hex(u32),
+    Reset,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NamedColor {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", content = "value", rename_all = "snake_case")]
+pub enum ClickEvent {
+    OpenUrl(String),
+    OpenFile(String),
+    RunCommand(String),
+    SuggestCommand(String),
+    /// Book page number
+    ChangePage(String),
+    CopyToClipboard(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoverEvent {
+    ShowText(Box<TextComponent>),
+    ShowItem(HoverItem),
+    ShowEntity(HoverEntity),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoverItem {
+    /// Resource location, e.g. `minecraft:stone`
+    pub id: String,
+    pub count: i32,
+    pub tag: Option<NbtCompound>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoverEntity {
+    /// Resource location of the entity type, e.g. `minecraft:pig`
+    pub kind: String,
+    /// UUID with hyphens
+    pub id: String,
+    pub name: Option<Box<TextComponent>>,
+}
+
+impl TextComponent {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::from(Content::Text(text.into()))
+    }
+
+    pub fn translate(key: impl Into<String>, with: Vec<TextComponent>) -> Self {
+        Self::from(Content::Translate {
+            key: key.into(),
+            with,
+        })
+    }
+
+    pub fn score(name: impl Into<String>, objective: impl Into<String>) -> Self {
+        Self::from(Content::Score(Score {
+            name: name.into(),
+            objective: objective.into(),
+            value: None,
+        }))
+    }
+
+    pub fn selector(selector: impl Into<String>) -> Self {
+        Self::from(Content::Selector(selector.into()))
+    }
+
+    pub fn keybind(key: impl Into<String>) -> Self {
+        Self::from(Content::Keybind(key.into()))
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.style.color = Some(color.into());
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.style.bold = Some(bold);
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = Some(italic);
+        self
+    }
+
+    pub fn underlined(mut self, underlined: bool) -> Self {
+        self.style.underlined = Some(underlined);
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.style.strikethrough = Some(strikethrough);
+        self
+    }
+
+    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+        self.style.obfuscated = Some(obfuscated);
+        self
+    }
+
+    pub fn font(mut self, font: impl Into<String>) -> Self {
+        self.style.font = Some(font.into());
+        self
+    }
+
+    pub fn insertion(mut self, insertion: impl Into<String>) -> Self {
+        self.style.insertion = Some(insertion.into());
+        self
+    }
+
+    pub fn on_click(mut self, event: ClickEvent) -> Self {
+        self.style.click_event = Some(event);
+        self
+    }
+
+    pub fn on_hover(mut self, event: HoverEvent) -> Self {
+        self.style.hover_event = Some(event);
+        self
+    }
+
+    /// Appends a child component
+    pub fn with_extra(mut self, child: impl Into<TextComponent>) -> Self {
+        self.extra.push(child.into());
+        self
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Encodes for the latest version
+    pub fn to_json(&self) -> String {
+        self.to_json_for(LATEST)
+    }
+
+    /// Encodes for protocol `version`. Before 1.16 hex colours become the nearest named colour,
+    /// fonts are dropped and hover events use the legacy `value` form
+    pub fn to_json_for(&self, version: u32) -> String {
+        serde_json::to_string(&Versioned(self, version)).expect("chat encoding can't fail")
+    }
+
+    /// Text content of this and its children, without styling
+    pub fn to_plain(&self) -> String {
+        let mut out = String::new();
+        self.write_plain(&mut out);
+        out
+    }
+
+    fn write_plain(&self, out: &mut String) {
+        out.push_str(self.content.as_plain());
+        for child in &self.extra {
+            child.write_plain(out);
+        }
+    }
+}
+
+impl Content {
+    /// Shown in place of this content, without its children
+    pub(crate) fn as_plain(&self) -> &str {
+        match self {
+            Content::Text(text) => text,
+            Content::Translate { key, .. } => key,
+            Content::Score(score) => score.value.as_deref().unwrap_or_default(),
+            Content::Selector(selector) => selector,
+            Content::Keybind(key) => key,
+        }
+    }
+}
+
+impl Default for Content {
+    fn default() -> Self {
+        Content::Text(String::new())
+    }
+}
+
+impl From<Content> for TextComponent {
+    fn from(content: Content) -> Self {
+        Self {
+            content,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for TextComponent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl Display for TextComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_plain())
+    }
+}
+
+impl NamedColor {
+    pub const ALL: [NamedColor; 16] = [
+        NamedColor::Black,
+        NamedColor::DarkBlue,
+        NamedColor::DarkGreen,
+        NamedColor::DarkAqua,
+        NamedColor::DarkRed,
+        NamedColor::DarkPurple,
+        NamedColor::Gold,
+        NamedColor::Gray,
+        NamedColor::DarkGray,
+        NamedColor::Blue,
+        NamedColor::Green,
+        NamedColor::Aqua,
+        NamedColor::Red,
+        NamedColor::LightPurple,
+        NamedColor::Yellow,
+        NamedColor::White,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            NamedColor::Black => "black",
+            NamedColor::DarkBlue => "dark_blue",
+            NamedColor::DarkGreen => "dark_green",
+            NamedColor::DarkAqua => "dark_aqua",
+            NamedColor::DarkRed => "dark_red",
+            NamedColor::DarkPurple => "dark_purple",
+            NamedColor::Gold => "gold",
+            NamedColor::Gray => "gray",
+            NamedColor::DarkGray => "dark_gray",
+            NamedColor::Blue => "blue",
+            NamedColor::Green => "green",
+            NamedColor::Aqua => "aqua",
+            NamedColor::Red => "red",
+            NamedColor::LightPurple => "light_purple",
+            NamedColor::Yellow => "yellow",
+            NamedColor::White => "white",
+        }
+    }
+
+    /// Legacy formatting code, following a `§`
+    pub fn code(self) -> char {
+        std::char::from_digit(self as u32, 16).expect("16 colours")
+    }
+
+    pub fn from_code(code: char) -> Option<Self> {
+        code.to_digit(16).map(|n| Self::ALL[n as usize])
+    }
+
+    /// `0xrrggbb` as rendered in chat
+    pub fn rgb(self) -> u32 {
+        match self {
+            NamedColor::Black => 0x000000,
+            NamedColor::DarkBlue => 0x0000aa,
+            NamedColor::DarkGreen => 0x00aa00,
+            NamedColor::DarkAqua => 0x00aaaa,
+            NamedColor::DarkRed => 0xaa0000,
+            NamedColor::DarkPurple => 0xaa00aa,
+            NamedColor::Gold => 0xffaa00,
+            NamedColor::Gray => 0xaaaaaa,
+            NamedColor::DarkGray => 0x555555,
+            NamedColor::Blue => 0x5555ff,
+            NamedColor::Green => 0x55ff55,
+            NamedColor::Aqua => 0x55ffff,
+            NamedColor::Red => 0xff5555,
+            NamedColor::LightPurple => 0xff55ff,
+            NamedColor::Yellow => 0xffff55,
+            NamedColor::White => 0xffffff,
+        }
+    }
+
+    /// Closest by distance in RGB space
+    pub fn nearest(rgb: u32) -> Self {
+        let channels = |rgb: u32| [(rgb >> 16) & 0xff, (rgb >> 8) & 0xff, rgb & 0xff];
+        let target = channels(rgb);
+
+        *Self::ALL
+            .iter()
+            .min_by_key(|color| {
+                channels(color.rgb())
+                    .iter()
+                    .zip(target.iter())
+                    .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+                    .sum::<i32>()
+            })
+            .expect("colours aren't empty")
+    }
+}
+
+impl Color {
+    /// Hex colours become the nearest named colour before 1.16
+    pub fn for_version(self, version: u32) -> Self {
+        match self {
+            Color::Hex(rgb) if version < HEX_COLOR_CUTOFF => Color::Named(NamedColor::nearest(rgb)),
+            color => color,
+        }
+    }
+}
+
+impl From<NamedColor> for Color {
+    fn from(color: NamedColor) -> Self {
+        Color::Named(color)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::Named(color) => f.write_str(color.name()),
+            Color::Hex(rgb) => write!(f, "#{:06x}", rgb),
+            Color::Reset => f.write_str("reset"),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "reset" {
+            return Ok(Color::Reset);
+        }
+
+        if let Some(hex) = s.strip_prefix('#') {
+            return match u32::from_str_radix(hex, 16) {
+                Ok(rgb) if hex.len() == 6 => Ok(Color::Hex(rgb)),
+                _ => Err(format!("bad hex colour '{}'", s)),
+            };
+        }
+
+        NamedColor::ALL
+            .iter()
+            .find(|color| color.name() == s)
+            .map(|color| Color::Named(*color))
+            .ok_or_else(|| format!("unknown colour '{}'", s))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl HoverItem {
+    fn to_snbt(&self) -> String {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("id", self.id.as_str());
+        nbt.insert("Count", self.count as i8);
+        if let Some(tag) = &self.tag {
+            nbt.insert("tag", tag.clone());
+        }
+        nbt.to_string()
+    }
+
+    fn from_snbt(snbt: &str) -> Result<Self, String> {
+        let nbt = snbt.parse::<NbtCompound>().map_err(|e| e.to_string())?;
+        Ok(Self {
+            id: nbt.get_string("id").ok_or("item has no id")?.clone(),
+            count: nbt.get_byte("Count").map_or(1, i32::from),
+            tag: nbt.get_compound("tag").cloned(),
+        })
+    }
+}
+
+impl HoverEntity {
+    fn to_snbt(&self, version: u32) -> String {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("type", self.kind.as_str());
+        nbt.insert("id", self.id.as_str());
+        if let Some(name) = &self.name {
+            nbt.insert("name", name.to_json_for(version));
+        }
+        nbt.to_string()
+    }
+
+    fn from_snbt(snbt: &str) -> Result<Self, String> {
+        let nbt = snbt.parse::<NbtCompound>().map_err(|e| e.to_string())?;
+        let name = nbt.get_string("name").map(|name| {
+            Box::new(TextComponent::from_json(name).unwrap_or_else(|_| TextComponent::text(name)))
+        });
+
+        Ok(Self {
+            kind: nbt.get_string("type").ok_or("entity has no type")?.clone(),
+            id: nbt.get_string("id").ok_or("entity has no id")?.clone(),
+            name,
+        })
+    }
+}
+
+/// Serializes `T` as sent to protocol version `.1`
+pub(super) struct Versioned<'a, T: ?Sized>(pub(super) &'a T, pub(super) u32);
+
+impl Serialize for TextComponent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Versioned(self, LATEST).serialize(serializer)
+    }
+}
+
+impl Serialize for Versioned<'_, TextComponent> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Versioned(component, version) = *self;
+        let mut map = serializer.serialize_map(None)?;
+
+        match &component.content {
+            Content::Text(text) => map.serialize_entry("text", text)?,
+            Content::Translate { key, with } => {
+                map.serialize_entry("translate", key)?;
+                if !with.is_empty() {
+                    map.serialize_entry("with", &Versioned(with.as_slice(), version))?;
+                }
+            }
+            Content::Score(score) => map.serialize_entry("score", score)?,
+            Content::Selector(selector) => map.serialize_entry("selector", selector)?,
+            Content::Keybind(key) => map.serialize_entry("keybind", key)?,
+        }
+
+        let style = &component.style;
+        if let Some(color) = style.color {
+            map.serialize_entry("color", &color.for_version(version).to_string())?;
+        }
+
+        let flags = [
+            ("bold", style.bold),
+            ("italic", style.italic),
+            ("underlined", style.underlined),
+            ("strikethrough", style.strikethrough),
+            ("obfuscated", style.obfuscated),
+        ];
+        for (name, flag) in flags.iter() {
+            if let Some(flag) = flag {
+                map.serialize_entry(name, flag)?;
+            }
+        }
+
+        if let Some(font) = style.font.as_ref().filter(|_| version >= HEX_COLOR_CUTOFF) {
+            map.serialize_entry("font", font)?;
+        }
+        if let Some(insertion) = &style.insertion {
+            map.serialize_entry("insertion", insertion)?;
+        }
+        if let Some(event) = &style.click_event {
+            map.serialize_entry("clickEvent", event)?;
+        }
+        if let Some(event) = &style.hover_event {
+            map.serialize_entry("hoverEvent", &Versioned(event, version))?;
+        }
+
+        if !component.extra.is_empty() {
+            map.serialize_entry("extra", &Versioned(component.extra.as_slice(), version))?;
+        }
+
+        map.end()
+    }
+}
+
+impl Serialize for Versioned<'_, [TextComponent]> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Versioned(components, version) = *self;
+        let mut seq = serializer.serialize_seq(Some(components.len()))?;
+        for component in components {
+            seq.serialize_element(&Versioned(component, version))?;
+        }
+        seq.end()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ItemContents {
+    id: String,
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    count: i32,
+    /// SNBT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EntityContents {
+    #[serde(rename = "type")]
+    kind: String,
+    id: String,
+    name: Option<TextComponent>,
+}
+
+fn one() -> i32 {
+    1
+}
+
+fn is_one(n: &i32) -> bool {
+    *n == 1
+}
+
+impl Serialize for Versioned<'_, HoverEvent> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Versioned(event, version) = *self;
+        let modern = version >= HEX_COLOR_CUTOFF;
+        let mut map = serializer.serialize_map(Some(2))?;
+
+        match event {
+            HoverEvent::ShowText(text) => {
+                map.serialize_entry("action", "show_text")?;
+                let key = if modern { "contents" } else { "value" };
+                map.serialize_entry(key, &Versioned(&**text, version))?;
+            }
+            HoverEvent::ShowItem(item) if modern => {
+                map.serialize_entry("action", "show_item")?;
+                let contents = ItemContents {
+                    id: item.id.clone(),
+                    count: item.count,
+                    tag: item.tag.as_ref().map(NbtCompound::to_string),
+                };
+                map.serialize_entry("contents", &contents)?;
+            }
+            HoverEvent::ShowItem(item) => {
+                map.serialize_entry("action", "show_item")?;
+                map.serialize_entry("value", &item.to_snbt())?;
+            }
+            HoverEvent::ShowEntity(entity) if modern => {
+                map.serialize_entry("action", "show_entity")?;
+                map.serialize_entry("contents", &EntityEntries(entity, version))?;
+            }
+            HoverEvent::ShowEntity(entity) => {
+                map.serialize_entry("action", "show_entity")?;
+                map.serialize_entry("value", &entity.to_snbt(version))?;
+            }
+        }
+
+        map.end()
+    }
+}
+
+/// 1.16 `contents` of an entity hover event
+struct EntityEntries<'a>(&'a HoverEntity, u32);
+
+impl Serialize for EntityEntries<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let EntityEntries(entity, version) = *self;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &entity.kind)?;
+        map.serialize_entry("id", &entity.id)?;
+        if let Some(name) = &entity.name {
+            map.serialize_entry("name", &Versioned(&**name, version))?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawComponent {
+    text: Option<String>,
+    translate: Option<String>,
+    #[serde(default)]
+    with: Vec<TextComponent>,
+    score: Option<Score>,
+    selector: Option<String>,
+    keybind: Option<String>,
+    #[serde(flatten)]
+    style: Style,
+    #[serde(default)]
+    extra: Vec<TextComponent>,
+}
+
+impl<'de> Deserialize<'de> for TextComponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Number(serde_json::Number),
+            Bool(bool),
+            /// First element is the parent of the rest
+            List(Vec<TextComponent>),
+            Object(Box<RawComponent>),
+        }
+
+        let raw = match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => return Ok(Self::text(text)),
+            Repr::Number(n) => return Ok(Self::text(n.to_string())),
+            Repr::Bool(b) => return Ok(Self::text(b.to_string())),
+            Repr::List(components) => {
+                let mut components = components.into_iter();
+                let mut parent = components
+                    .next()
+                    .ok_or_else(|| de::Error::invalid_length(0, &"at least one component"))?;
+                parent.extra.extend(components);
+                return Ok(parent);
+            }
+            Repr::Object(raw) => *raw,
+        };
+
+        // same precedence as vanilla
+        let content = if let Some(text) = raw.text {
+            Content::Text(text)
+        } else if let Some(key) = raw.translate {
+            Content::Translate {
+                key,
+                with: raw.with,
+            }
+        } else if let Some(score) = raw.score {
+            Content::Score(score)
+        } else if let Some(selector) = raw.selector {
+            Content::Selector(selector)
+        } else if let Some(key) = raw.keybind {
+            Content::Keybind(key)
+        } else {
+            return Err(de::Error::custom("component has no content"));
+        };
+
+        Ok(Self {
+            content,
+            style: raw.style,
+            extra: raw.extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for HoverEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RawHoverEvent {
+            action: String,
+            contents: Option<serde_json::Value>,
+            /// Before 1.16, SNBT for items and entities
+            value: Option<TextComponent>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawItem {
+            Id(String),
+            Item(ItemContents),
+        }
+
+        let raw = RawHoverEvent::deserialize(deserializer)?;
+        match (raw.action.as_str(), raw.contents, raw.value) {
+            ("show_text", Some(contents), _) => serde_json::from_value(contents)
+                .map(|text| HoverEvent::ShowText(Box::new(text)))
+                .map_err(de::Error::custom),
+            ("show_text", None, Some(value)) => Ok(HoverEvent::ShowText(Box::new(value))),
+            ("show_item", Some(contents), _) => {
+                let item = match serde_json::from_value(contents).map_err(de::Error::custom)? {
+                    RawItem::Id(id) => HoverItem {
+                        id,
+                        count: 1,
+                        tag: None,
+                    },
+                    RawItem::Item(item) => HoverItem {
+                        id: item.id,
+                        count: item.count,
+                        tag: match item.tag {
+                            Some(tag) => Some(tag.parse().map_err(de::Error::custom)?),
+                            None => None,
+                        },
+                    },
+                };
+                Ok(HoverEvent::ShowItem(item))
+            }
+            ("show_item", None, Some(value)) => HoverItem::from_snbt(&value.to_plain())
+                .map(HoverEvent::ShowItem)
+                .map_err(de::Error::custom),
+            ("show_entity", Some(contents), _) => {
+                let entity = serde_json::from_value::<EntityContents>(contents)
+                    .map_err(de::Error::custom)?;
+                Ok(HoverEvent::ShowEntity(HoverEntity {
+                    kind: entity.kind,
+                    id: entity.id,
+                    name: entity.name.map(Box::new),
+                }))
+            }
+            ("show_entity", None, Some(value)) => HoverEntity::from_snbt(&value.to_plain())
+                .map(HoverEvent::ShowEntity)
+                .map_err(de::Error::custom),
+            (action @ ("show_text" | "show_item" | "show_entity"), None, None) => Err(
+                de::Error::custom(format!("{} hover event has no contents", action)),
+            ),
+            (action, _, _) => Err(de::Error::unknown_variant(
+                action,
+                &["show_text", "show_item", "show_entity"],
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn styled_round_trip() {
+        let component = TextComponent::text("Hello ")
+            .color(NamedColor::Gold)
+            .bold(true)
+            .on_click(ClickEvent::RunCommand("/spawn".to_owned()))
+            .with_extra(
+                TextComponent::translate("chat.type.text", vec!["a".into(), "b".into()])
+                    .italic(false),
+            )
+            .with_extra(TextComponent::keybind("key.jump").color(Color::Hex(0x123456)));
+
+        let json = component.to_json();
+        assert_eq!(
+            json,
+            r##"{"text":"Hello ","color":"gold","bold":true,"clickEvent":{"action":"run_command","value":"/spawn"},"extra":[{"translate":"chat.type.text","with":[{"text":"a"},{"text":"b"}],"italic":false},{"keybind":"key.jump","color":"#123456"}]}"##
+        );
+        assert_eq!(TextComponent::from_json(&json).unwrap(), component);
+        assert_eq!(component.to_plain(), "Hello chat.type.textkey.jump");
+    }
+
+    #[test]
+    fn vanilla_shorthands() {
+        let list = TextComponent::from_json(r#"["a", {"text": "b", "color": "red"}, 3]"#).unwrap();
+        assert_eq!(
+            list,
+            TextComponent::text("a")
+                .with_extra(TextComponent::text("b").color(NamedColor::Red))
+                .with_extra("3")
+        );
+
+        let score =
+            TextComponent::from_json(r#"{"score": {"name": "@p", "objective": "kills"}}"#).unwrap();
+        assert_eq!(score, TextComponent::score("@p", "kills"));
+
+        // text wins over other content, as in vanilla
+        let both = TextComponent::from_json(r#"{"text": "x", "selector": "@a"}"#).unwrap();
+        assert_eq!(both.content, Content::Text("x".to_owned()));
+
+        assert!(TextComponent::from_json(r#"{"color": "red"}"#).is_err());
+        assert!(TextComponent::from_json(r#"{"text": "", "color": "pink"}"#).is_err());
+        assert!(TextComponent::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn hover_events() {
+        let item = HoverEvent::ShowItem(HoverItem {
+            id: "minecraft:stone".to_owned(),
+            count: 2,
+            tag: Some("{Damage:3}".parse().unwrap()),
+        });
+        let component = TextComponent::text("x").on_hover(item.clone());
+
+        let modern = component.to_json_for(HEX_COLOR_CUTOFF);
+        assert_eq!(
+            modern,
+            r#"{"text":"x","hoverEvent":{"action":"show_item","contents":{"id":"minecraft:stone","count":2,"tag":"{Damage:3}"}}}"#
+        );
+        let legacy = component.to_json_for(HEX_COLOR_CUTOFF - 1);
+        assert_eq!(
+            legacy,
+            r#"{"text":"x","hoverEvent":{"action":"show_item","value":"{id:\"minecraft:stone\",Count:2b,tag:{Damage:3}}"}}"#
+        );
+        for json in &[modern, legacy] {
+            let decoded = TextComponent::from_json(json).unwrap();
+            assert_eq!(decoded.style.hover_event, Some(item.clone()));
+        }
+
+        let entity = HoverEvent::ShowEntity(HoverEntity {
+            kind: "minecraft:pig".to_owned(),
+            id: "0f0e0d0c-0b0a-0908-0706-050403020100".to_owned(),
+            name: Some(Box::new(TextComponent::text("Babe"))),
+        });
+        let component = TextComponent::text("x").on_hover(entity.clone());
+        for version in &[HEX_COLOR_CUTOFF - 1, HEX_COLOR_CUTOFF] {
+            let decoded = TextComponent::from_json(&component.to_json_for(*version)).unwrap();
+            assert_eq!(decoded.style.hover_event, Some(entity.clone()));
+        }
+
+        let text = TextComponent::from_json(
+            r#"{"text": "", "hoverEvent": {"action": "show_text", "value": "hi"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            text.style.hover_event,
+            Some(HoverEvent::ShowText(Box::new("hi".into())))
+        );
+    }
+
+    #[test]
+    fn colours_before_hex() {
+        let component = TextComponent::text("x")
+            .color(Color::Hex(0xfe5050))
+            .font("minecraft:alt");
+        assert_eq!(
+            component.to_json_for(HEX_COLOR_CUTOFF - 1),
+            r#"{"text":"x","color":"red"}"#
+        );
+        assert_eq!(
+            component.to_json_for(HEX_COLOR_CUTOFF),
+            r##"{"text":"x","color":"#fe5050","font":"minecraft:alt"}"##
+        );
+
+        assert_eq!(NamedColor::nearest(0x000001), NamedColor::Black);
+        assert_eq!(NamedColor::nearest(0xffaa11), NamedColor::Gold);
+        assert_eq!("#FFAA00".parse(), Ok(Color::Hex(0xffaa00)));
+        assert!("#ffaa0".parse::<Color>().is_err());
+        assert_eq!("reset".parse(), Ok(Color::Reset));
+    }
+}
@@ -0,0 +1,272 @@
+//! Legacy `§` formatting codes, as in `§aHello §lworld`.
+//!
+//! A colour code clears the formatting codes before it and `§r` clears everything. Hex colours
+//! use the `§x§r§r§g§g§b§b` form.
+
+use super::{Color, NamedColor, Style, TextComponent};
+use std::mem;
+
+pub const SECTION_SIGN: char = '§';
+
+/// Effective style of a run of legacy text
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+struct Format {
+    color: Option<Color>,
+    obfuscated: bool,
+    bold: bool,
+    strikethrough: bool,
+    underlined: bool,
+    italic: bool,
+}
+
+impl TextComponent {
+    /// Parses `§` codes into a component with a child per differently formatted run. Unknown codes
+    /// are kept as text
+    pub fn from_legacy(legacy: &str) -> Self {
+        let chars = legacy.chars().collect::<Vec<_>>();
+        let mut runs = Vec::new();
+        let mut text = String::new();
+        let mut format = Format::default();
+
+        let mut i = 0;
+        while i < chars.len() {
+            let code = match chars.get(i + 1) {
+                Some(code) if chars[i] == SECTION_SIGN => code.to_ascii_lowercase(),
+                _ => {
+                    text.push(chars[i]);
+                    i += 1;
+                    continue;
+                }
+            };
+
+            let mut next = format;
+            let mut len = 2;
+            match code {
+                'k' => next.obfuscated = true,
+                'l' => next.bold = true,
+                'm' => next.strikethrough = true,
+                'n' => next.underlined = true,
+                'o' => next.italic = true,
+                'r' => next = Format::default(),
+                'x' if hex_color(&chars[i + 2..]).is_some() => {
+                    next = Format::colored(hex_color(&chars[i + 2..]).map(Color::Hex));
+                    len += 12;
+                }
+                code => match NamedColor::from_code(code) {
+                    Some(color) => next = Format::colored(Some(color.into())),
+                    None => {
+                        text.push(chars[i]);
+                        i += 1;
+                        continue;
+                    }
+                },
+            }
+
+            if next != format && !text.is_empty() {
+                runs.push(format.component(mem::take(&mut text)));
+            }
+            format = next;
+            i += len;
+        }
+
+        if !text.is_empty() {
+            runs.push(format.component(text));
+        }
+
+        match runs.len() {
+            0 => TextComponent::text(""),
+            1 => runs.pop().expect("one run"),
+            _ => TextComponent {
+                extra: runs,
+                ..TextComponent::text("")
+            },
+        }
+    }
+
+    /// Flattens to `§` codes as shown by protocol version `version`, dropping anything but colours
+    /// and formatting. Hex colours become the nearest named colour before 1.16
+    pub fn to_legacy_for(&self, version: u32) -> String {
+        let mut out = String::new();
+        self.write_legacy(Format::default(), &mut Format::default(), version, &mut out);
+        out
+    }
+
+    fn write_legacy(&self, parent: Format, written: &mut Format, version: u32, out: &mut String) {
+        let format = parent.inherit(&self.style, version);
+
+        let text = self.content.as_plain();
+        if !text.is_empty() {
+            format.write_codes(*written, out);
+            *written = format;
+            out.push_str(text);
+        }
+
+        for child in &self.extra {
+            child.write_legacy(format, written, version, out);
+        }
+    }
+}
+
+impl Format {
+    fn colored(color: Option<Color>) -> Self {
+        Self {
+            color,
+            ..Self::default()
+        }
+    }
+
+    fn inherit(self, style: &Style, version: u32) -> Self {
+        let color = match style.color.map(|color| color.for_version(version)) {
+            Some(Color::Reset) => None,
+            Some(color) => Some(color),
+            None => self.color,
+        };
+
+        Self {
+            color,
+            obfuscated: style.obfuscated.unwrap_or(self.obfuscated),
+            bold: style.bold.unwrap_or(self.bold),
+            strikethrough: style.strikethrough.unwrap_or(self.strikethrough),
+            underlined: style.underlined.unwrap_or(self.underlined),
+            italic: style.italic.unwrap_or(self.italic),
+        }
+    }
+
+    fn flags(self) -> [(bool, char); 5] {
+        [
+            (self.obfuscated, 'k'),
+            (self.bold, 'l'),
+            (self.strikethrough, 'm'),
+            (self.underlined, 'n'),
+            (self.italic, 'o'),
+        ]
+    }
+
+    /// Codes switching from `from` to this
+    fn write_codes(self, mut from: Format, out: &mut String) {
+        let cleared = from
+            .flags()
+            .iter()
+            .zip(self.flags().iter())
+            .any(|((was, _), (is, _))| *was && !*is);
+
+        if self.color != from.color || cleared {
+            match self.color {
+                Some(Color::Named(color)) => push_code(out, color.code()),
+                Some(Color::Hex(rgb)) => {
+                    push_code(out, 'x');
+                    for digit in format!("{:06x}", rgb).chars() {
+                        push_code(out, digit);
+                    }
+                }
+                Some(Color::Reset) | None => push_code(out, 'r'),
+            }
+            from = Format::colored(self.color);
+        }
+
+        for ((was, _), (is, code)) in from.flags().iter().zip(self.flags().iter()) {
+            if *is && !*was {
+                push_code(out, *code);
+            }
+        }
+    }
+
+    fn component(self, text: String) -> TextComponent {
+        let flag = |set: bool| if set { Some(true) } else { None };
+        TextComponent {
+            style: Style {
+                color: self.color,
+                obfuscated: flag(self.obfuscated),
+                bold: flag(self.bold),
+                strikethrough: flag(self.strikethrough),
+                underlined: flag(self.underlined),
+                italic: flag(self.italic),
+                ..Style::default()
+            },
+            ..TextComponent::text(text)
+        }
+    }
+}
+
+fn push_code(out: &mut String, code: char) {
+    out.push(SECTION_SIGN);
+    out.push(code);
+}
+
+/// `§r§r§g§g§b§b` at the start of `chars`
+fn hex_color(chars: &[char]) -> Option<u32> {
+    chars
+        .get(..12)?
+        .chunks(2)
+        .try_fold(0, |rgb, pair| match pair {
+            [SECTION_SIGN, digit] => digit.to_digit(16).map(|digit| rgb << 4 | digit),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_legacy() {
+        assert_eq!(
+            TextComponent::from_legacy("plain"),
+            TextComponent::text("plain")
+        );
+        assert_eq!(TextComponent::from_legacy(""), TextComponent::text(""));
+
+        let green = Color::Named(NamedColor::Green);
+        assert_eq!(
+            TextComponent::from_legacy("§aHello §lworld§r!"),
+            TextComponent::text("")
+                .with_extra(TextComponent::text("Hello ").color(green))
+                .with_extra(TextComponent::text("world").color(green).bold(true))
+                .with_extra("!")
+        );
+
+        // colours clear formatting, codes are case insensitive and unknown ones are text
+        assert_eq!(
+            TextComponent::from_legacy("§L§obig§Csmall §z§"),
+            TextComponent::text("")
+                .with_extra(TextComponent::text("big").bold(true).italic(true))
+                .with_extra(TextComponent::text("small §z§").color(NamedColor::Red))
+        );
+
+        assert_eq!(
+            TextComponent::from_legacy("§x§F§f§8§0§0§0orange§x§g"),
+            TextComponent::text("orange§x§g").color(Color::Hex(0xff8000))
+        );
+    }
+
+    #[test]
+    fn to_legacy() {
+        let motd = "§aHello §lworld§r!";
+        assert_eq!(TextComponent::from_legacy(motd).to_legacy_for(578), motd);
+
+        let component = TextComponent::text("a")
+            .bold(true)
+            .with_extra(TextComponent::text("b").italic(true))
+            .with_extra(
+                TextComponent::text("c")
+                    .bold(false)
+                    .color(Color::Hex(0xff0000)),
+            )
+            .with_extra(TextComponent::translate("d", vec![]).color(Color::Reset));
+        assert_eq!(component.to_legacy_for(751), "§la§ob§x§f§f§0§0§0§0c§r§ld");
+        assert_eq!(component.to_legacy_for(578), "§la§ob§4c§r§ld");
+    }
+
+    #[test]
+    fn legacy_to_json() {
+        let component = TextComponent::from_legacy("§x§f§f§0§0§0§0red");
+        assert_eq!(
+            component.to_json_for(751),
+            r##"{"text":"red","color":"#ff0000"}"##
+        );
+        assert_eq!(
+            component.to_json_for(578),
+            r#"{"text":"red","color":"dark_red"}"#
+        );
+    }
+}
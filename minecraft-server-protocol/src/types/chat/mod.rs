@@ -0,0 +1,134 @@
+use crate::types::field::Field;
+use crate::types::{PacketError, PacketResult, StringField, VarIntField};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+
+pub use component::{
+    ClickEvent, Color, Content, HoverEntity, HoverEvent, HoverItem, NamedColor, Score, Style,
+    TextComponent,
+};
+pub use legacy::SECTION_SIGN;
+pub use status::StatusResponse;
+
+mod component;
+mod legacy;
+mod status;
+
+/// JSON chat component, encoded for `VERSION`. A decoded component is written back as the JSON
+/// it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatField<const VERSION: u32> {
+    component: TextComponent,
+    json: String,
+}
+
+impl<const VERSION: u32> ChatField<VERSION> {
+    pub fn new(component: impl Into<TextComponent>) -> Self {
+        let component = component.into();
+        let json = component.to_json_for(VERSION);
+        Self { component, json }
+    }
+
+    pub fn component(&self) -> &TextComponent {
+        &self.component
+    }
+
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+
+    pub fn take(self) -> TextComponent {
+        self.component
+    }
+}
+
+impl<const VERSION: u32> From<TextComponent> for ChatField<VERSION> {
+    fn from(component: TextComponent) -> Self {
+        Self::new(component)
+    }
+}
+
+impl<const VERSION: u32> Default for ChatField<VERSION> {
+    fn default() -> Self {
+        Self::new(TextComponent::default())
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> Field for ChatField<VERSION> {
+    type Displayable = TextComponent;
+
+    fn value(&self) -> &Self::Displayable {
+        &self.component
+    }
+
+    fn size(&self) -> usize {
+        VarIntField::new(self.json.len() as i32).size() + self.json.len()
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let json = StringField::read_field(r).await?.take();
+        let component = TextComponent::from_json(&json).map_err(PacketError::BadChat)?;
+        Ok(Self { component, json })
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        VarIntField::new(self.json.len() as i32)
+            .write_field(w)
+            .await?;
+        w.write_all(self.json.as_bytes())
+            .await
+            .map_err(PacketError::Io)
+    }
+}
+
+impl<const VERSION: u32> Display for ChatField<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.component.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    #[async_test]
+    async fn chat_round_trip() -> PacketResult<()> {
+        let json = r##"{"text": "kicked", "color": "#ff0000"}"##;
+        let mut cursor = Cursor::new(Vec::new());
+        StringField::new(json.to_owned())
+            .write_field(&mut cursor)
+            .await?;
+
+        cursor.set_position(0);
+        let chat = ChatField::<751>::read_field(&mut cursor).await?;
+        assert_eq!(
+            chat.component(),
+            &TextComponent::text("kicked").color(Color::Hex(0xff0000))
+        );
+        assert_eq!(chat.to_string(), "kicked");
+        assert_eq!(chat.size(), json.len() + 1);
+
+        // written back untouched
+        let mut out = Cursor::new(Vec::new());
+        chat.write_field(&mut out).await?;
+        assert_eq!(out.get_ref(), cursor.get_ref());
+
+        // re-encoded for an older version
+        let old = ChatField::<340>::new(chat.take());
+        assert_eq!(old.json(), r#"{"text":"kicked","color":"dark_red"}"#);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn bad_chat() {
+        let mut cursor = Cursor::new(b"\x05{text".to_vec());
+        assert!(matches!(
+            ChatField::<751>::read_field(&mut cursor).await,
+            Err(PacketError::BadChat(_))
+        ));
+    }
+}
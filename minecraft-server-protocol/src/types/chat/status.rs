@@ -0,0 +1,97 @@
+use super::component::Versioned;
+use super::TextComponent;
+use crate::types::StringField;
+use serde_json::json;
+
+/// Server list response for `ServerInfo.response`, with the description encoded for `VERSION`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusResponse<const VERSION: u32> {
+    version_name: String,
+    description: TextComponent,
+    online_players: i32,
+    max_players: i32,
+}
+
+impl<const VERSION: u32> StatusResponse<VERSION> {
+    /// `version_name` is shown by clients on a different protocol version
+    pub fn new(version_name: impl Into<String>) -> Self {
+        Self {
+            version_name: version_name.into(),
+            description: TextComponent::text(""),
+            online_players: 0,
+            max_players: 0,
+        }
+    }
+
+    /// MOTD in legacy `§` codes
+    pub fn description(mut self, legacy: &str) -> Self {
+        self.description = TextComponent::from_legacy(legacy);
+        self
+    }
+
+    pub fn players(mut self, online: i32, max: i32) -> Self {
+        self.online_players = online;
+        self.max_players = max;
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        json!({
+            "version": {
+                "name": self.version_name,
+                "protocol": VERSION,
+            },
+            "players": {
+                "max": self.max_players,
+                "online": self.online_players,
+            },
+            "description": Versioned(&self.description, VERSION),
+        })
+        .to_string()
+    }
+
+    pub fn to_field(&self) -> StringField {
+        StringField::new(self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Field;
+    use serde_json::Value;
+
+    fn parse(json: &str) -> Value {
+        serde_json::from_str(json).expect("valid json")
+    }
+
+    #[test]
+    fn status_response() {
+        let motd = "§x§f§f§0§0§0§0Hello §lworld";
+
+        let old = StatusResponse::<578>::new("1.15.2")
+            .description(motd)
+            .players(3, 20);
+        assert_eq!(
+            parse(&old.to_json()),
+            json!({
+                "version": {"name": "1.15.2", "protocol": 578},
+                "players": {"max": 20, "online": 3},
+                "description": {
+                    "text": "",
+                    "extra": [
+                        {"text": "Hello ", "color": "dark_red"},
+                        {"text": "world", "color": "dark_red", "bold": true},
+                    ],
+                },
+            })
+        );
+
+        let new = StatusResponse::<751>::new("1.16.2").description(motd);
+        assert_eq!(
+            parse(&new.to_json())["description"]["extra"][0],
+            json!({"text": "Hello ", "color": "#ff0000"})
+        );
+        assert_eq!(new.to_field().value(), &new.to_json());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::types::field::Field;
+use crate::types::{ByteField, PacketError, PacketResult, SlotField};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// Set on an entry's slot byte if another entry follows
+const MORE_ENTRIES_BIT: i8 = -0x80;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EquipmentSlot {
+    MainHand,
+    OffHand,
+    Boots,
+    Leggings,
+    Chestplate,
+    Helmet,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equipment<const VERSION: u32> {
+    pub slot: EquipmentSlot,
+    pub item: SlotField<VERSION>,
+}
+
+/// Equipment entries of an entity, sent as a top bit set terminated array since 1.16. Must hold at
+/// least one entry to be written
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EquipmentField<const VERSION: u32>(Vec<Equipment<VERSION>>);
+
+impl TryFrom<i8> for EquipmentSlot {
+    type Error = PacketError;
+
+    fn try_from(id: i8) -> Result<Self, Self::Error> {
+        use EquipmentSlot::*;
+        Ok(match id {
+            0 => MainHand,
+            1 => OffHand,
+            2 => Boots,
+            3 => Leggings,
+            4 => Chestplate,
+            5 => Helmet,
+            _ => return Err(PacketError::BadEquipmentSlot(id)),
+        })
+    }
+}
+
+impl Display for EquipmentSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use EquipmentSlot::*;
+        f.write_str(match self {
+            MainHand => "main_hand",
+            OffHand => "off_hand",
+            Boots => "boots",
+            Leggings => "leggings",
+            Chestplate => "chestplate",
+            Helmet => "helmet",
+        })
+    }
+}
+
+impl<const VERSION: u32> EquipmentField<VERSION> {
+    pub fn new(entries: Vec<Equipment<VERSION>>) -> Self {
+        Self(entries)
+    }
+
+    pub fn entries(&self) -> &[Equipment<VERSION>] {
+        &self.0
+    }
+
+    pub fn get(&self, slot: EquipmentSlot) -> Option<&SlotField<VERSION>> {
+        self.0.iter().find(|e| e.slot == slot).map(|e| &e.item)
+    }
+
+    /// Replaces the item already in `slot`, if any
+    pub fn set(&mut self, slot: EquipmentSlot, item: SlotField<VERSION>) {
+        match self.0.iter_mut().find(|e| e.slot == slot) {
+            Some(entry) => entry.item = item,
+            None => self.0.push(Equipment { slot, item }),
+        }
+    }
+
+    pub fn take(self) -> Vec<Equipment<VERSION>> {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> Field for EquipmentField<VERSION> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        self.0.iter().map(|e| 1 + e.item.size()).sum()
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut entries = Vec::new();
+        loop {
+            let byte = *ByteField::read_field(r).await?.value();
+            let slot = EquipmentSlot::try_from(byte & !MORE_ENTRIES_BIT)?;
+            let item = SlotField::read_field(r).await?;
+            entries.push(Equipment { slot, item });
+
+            if byte & MORE_ENTRIES_BIT == 0 {
+                break Ok(Self(entries));
+            }
+        }
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        if self.0.is_empty() {
+            return Err(PacketError::EmptyEquipment);
+        }
+
+        let last = self.0.len() - 1;
+        for (i, entry) in self.0.iter().enumerate() {
+            let mut byte = entry.slot as i8;
+            if i != last {
+                byte |= MORE_ENTRIES_BIT;
+            }
+
+            ByteField::new(byte).write_field(w).await?;
+            entry.item.write_field(w).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const VERSION: u32> Display for EquipmentField<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, entry) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", entry.slot, entry.item)?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ItemStack;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    #[async_test]
+    async fn equipment_round_trip() -> PacketResult<()> {
+        let mut field = EquipmentField::<736>::default();
+        field.set(EquipmentSlot::MainHand, ItemStack::new(300, 1).into());
+        field.set(EquipmentSlot::Helmet, SlotField::empty());
+        field.set(EquipmentSlot::MainHand, ItemStack::new(300, 2).into());
+
+        let mut cursor = Cursor::new(Vec::new());
+        field.write_field(&mut cursor).await?;
+        assert_eq!(
+            cursor.get_ref().as_slice(),
+            b"\x80\x01\xac\x02\x02\x00\x05\x00"
+        );
+        assert_eq!(field.size(), 8);
+
+        cursor.set_position(0);
+        let read = EquipmentField::<736>::read_field(&mut cursor).await?;
+        assert_eq!(read, field);
+        assert_eq!(read.get(EquipmentSlot::Helmet), Some(&SlotField::empty()));
+        assert_eq!(read.get(EquipmentSlot::Boots), None);
+        assert_eq!(read.to_string(), "[main_hand: 2x 300, helmet: empty]");
+        Ok(())
+    }
+
+    #[async_test]
+    async fn equipment_errors() {
+        let mut cursor = Cursor::new(Vec::new());
+        assert!(matches!(
+            EquipmentField::<736>::default()
+                .write_field(&mut cursor)
+                .await,
+            Err(PacketError::EmptyEquipment)
+        ));
+
+        let mut cursor = Cursor::new(vec![0x86u8, 0x00]);
+        assert!(matches!(
+            EquipmentField::<736>::read_field(&mut cursor).await,
+            Err(PacketError::BadEquipmentSlot(6))
+        ));
+    }
+}
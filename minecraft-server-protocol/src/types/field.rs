@@ -13,3 +13,27 @@ pub trait Field: Sized {
     async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self>;
     async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()>;
 }
+
+/// A field that can only be read given the value of an earlier field in the same packet, which
+/// packets name with `#[depends_on = "field"]`
+#[async_trait]
+pub trait DependentField: Field {
+    type Dependency: ?Sized + Sync;
+
+    async fn read_dependent<R: Read + Unpin + Send>(
+        r: &mut R,
+        dependency: &Self::Dependency,
+    ) -> PacketResult<Self>;
+
+    /// Writes given the same earlier field, for fields that don't keep their own copy of it
+    async fn write_dependent<W: Write + Unpin + Send>(
+        &self,
+        w: &mut W,
+        _dependency: &Self::Dependency,
+    ) -> PacketResult<()>
+    where
+        Self: Sync,
+    {
+        self.write_field(w).await
+    }
+}
@@ -0,0 +1,822 @@
+use crate::types::field::Field;
+use crate::types::{
+    BoolField, ByteField, FloatField, ItemStack, NbtField, PacketError, PacketResult, Particle,
+    ParticleData, PositionField, SlotField, StringField, UByteField, UuidField, VarIntField,
+};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// >= added optional chat and particles, shifting the type IDs (1.13)
+const V1_13_TYPES_CUTOFF: u32 = 393;
+
+/// >= added villager data, optional varints and poses (1.14)
+const V1_14_TYPES_CUTOFF: u32 = 477;
+
+/// >= added long jumping before dying, shifting its pose ID (1.17)
+const V1_17_POSES_CUTOFF: u32 = 755;
+
+/// Index that marks the end of the metadata
+const END_INDEX: u8 = 0xff;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MetadataType {
+    Byte,
+    VarInt,
+    Float,
+    String,
+    Chat,
+    OptChat,
+    Slot,
+    Bool,
+    Rotation,
+    Position,
+    OptPosition,
+    Direction,
+    OptUuid,
+    BlockId,
+    Nbt,
+    Particle,
+    VillagerData,
+    OptVarInt,
+    Pose,
+}
+
+/// Type IDs are indices into these
+const TYPES_1_12: &[MetadataType] = {
+    use MetadataType::*;
+    &[
+        Byte,
+        VarInt,
+        Float,
+        String,
+        Chat,
+        Slot,
+        Bool,
+        Rotation,
+        Position,
+        OptPosition,
+        Direction,
+        OptUuid,
+        BlockId,
+        Nbt,
+    ]
+};
+
+const TYPES_1_13: &[MetadataType] = {
+    use MetadataType::*;
+    &[
+        Byte,
+        VarInt,
+        Float,
+        String,
+        Chat,
+        OptChat,
+        Slot,
+        Bool,
+        Rotation,
+        Position,
+        OptPosition,
+        Direction,
+        OptUuid,
+        BlockId,
+        Nbt,
+        Particle,
+    ]
+};
+
+const TYPES_1_14: &[MetadataType] = {
+    use MetadataType::*;
+    &[
+        Byte,
+        VarInt,
+        Float,
+        String,
+        Chat,
+        OptChat,
+        Slot,
+        Bool,
+        Rotation,
+        Position,
+        OptPosition,
+        Direction,
+        OptUuid,
+        BlockId,
+        Nbt,
+        Particle,
+        VillagerData,
+        OptVarInt,
+        Pose,
+    ]
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Pose {
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Sneaking,
+    /// 1.17+
+    LongJumping,
+    Dying,
+}
+
+/// Pose IDs are indices into these
+const POSES_1_14: &[Pose] = {
+    use Pose::*;
+    &[
+        Standing, FallFlying, Sleeping, Swimming, SpinAttack, Sneaking, Dying,
+    ]
+};
+
+const POSES_1_17: &[Pose] = {
+    use Pose::*;
+    &[
+        Standing,
+        FallFlying,
+        Sleeping,
+        Swimming,
+        SpinAttack,
+        Sneaking,
+        LongJumping,
+        Dying,
+    ]
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VillagerData {
+    pub ty: i32,
+    pub profession: i32,
+    pub level: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    /// JSON chat component
+    Chat(String),
+    OptChat(Option<String>),
+    Slot(Option<ItemStack>),
+    Bool(bool),
+    /// Pitch, yaw and roll in degrees
+    Rotation([f32; 3]),
+    Position((i32, i32, i32)),
+    OptPosition(Option<(i32, i32, i32)>),
+    Direction(Direction),
+    OptUuid(Option<UuidField>),
+    /// Block state ID, 0 (air) means absent
+    BlockId(i32),
+    Nbt(NbtField),
+    Particle(Particle),
+    VillagerData(VillagerData),
+    OptVarInt(Option<i32>),
+    Pose(Pose),
+}
+
+/// Entity metadata entries by index, in the order they are sent
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EntityMetadataField<const VERSION: u32> {
+    entries: Vec<(u8, MetadataValue)>,
+}
+
+fn poses(version: u32) -> &'static [Pose] {
+    if version >= V1_17_POSES_CUTOFF {
+        POSES_1_17
+    } else {
+        POSES_1_14
+    }
+}
+
+fn types(version: u32) -> &'static [MetadataType] {
+    if version >= V1_14_TYPES_CUTOFF {
+        TYPES_1_14
+    } else if version >= V1_13_TYPES_CUTOFF {
+        TYPES_1_13
+    } else {
+        TYPES_1_12
+    }
+}
+
+macro_rules! enum_ids {
+    ($($ty:ident { $($variant:ident = $id:expr,)* })*) => {
+        $(
+            impl $ty {
+                fn from_id(id: i32) -> PacketResult<Self> {
+                    match id {
+                        $($id => Ok($ty::$variant),)*
+                        _ => Err(PacketError::BadMetadataEnum {
+                            kind: stringify!($ty),
+                            value: id,
+                        }),
+                    }
+                }
+
+                fn id(self) -> i32 {
+                    self as i32
+                }
+            }
+        )*
+    };
+}
+
+enum_ids! {
+    Direction {
+        Down = 0,
+        Up = 1,
+        North = 2,
+        South = 3,
+        West = 4,
+        East = 5,
+    }
+}
+
+impl Pose {
+    fn from_id(version: u32, id: i32) -> PacketResult<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|idx| poses(version).get(idx).copied())
+            .ok_or(PacketError::BadMetadataEnum {
+                kind: "Pose",
+                value: id,
+            })
+    }
+
+    fn id(self, version: u32) -> PacketResult<i32> {
+        poses(version)
+            .iter()
+            .position(|pose| *pose == self)
+            .map(|idx| idx as i32)
+            .ok_or(PacketError::UnsupportedPose(self))
+    }
+}
+
+macro_rules! copy_accessors {
+    ($($variant:ident $get:ident $set:ident $ty:ty;)*) => {
+        impl<const VERSION: u32> EntityMetadataField<VERSION> {
+            $(
+                pub fn $get(&self, index: u8) -> Option<$ty> {
+                    match self.get(index) {
+                        Some(MetadataValue::$variant(val)) => Some(*val),
+                        _ => None,
+                    }
+                }
+
+                pub fn $set(&mut self, index: u8, value: $ty) {
+                    self.set(index, MetadataValue::$variant(value))
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! ref_accessors {
+    ($($variant:ident $get:ident $set:ident $ty:ty;)*) => {
+        impl<const VERSION: u32> EntityMetadataField<VERSION> {
+            $(
+                pub fn $get(&self, index: u8) -> Option<&$ty> {
+                    match self.get(index) {
+                        Some(MetadataValue::$variant(val)) => Some(val),
+                        _ => None,
+                    }
+                }
+
+                pub fn $set(&mut self, index: u8, value: $ty) {
+                    self.set(index, MetadataValue::$variant(value))
+                }
+            )*
+        }
+    };
+}
+
+copy_accessors! {
+    Byte get_byte set_byte i8;
+    VarInt get_varint set_varint i32;
+    Float get_float set_float f32;
+    Bool get_bool set_bool bool;
+    Rotation get_rotation set_rotation [f32; 3];
+    Position get_position set_position (i32, i32, i32);
+    OptPosition get_opt_position set_opt_position Option<(i32, i32, i32)>;
+    Direction get_direction set_direction Direction;
+    OptUuid get_opt_uuid set_opt_uuid Option<UuidField>;
+    BlockId get_block_id set_block_id i32;
+    VillagerData get_villager_data set_villager_data VillagerData;
+    OptVarInt get_opt_varint set_opt_varint Option<i32>;
+    Pose get_pose set_pose Pose;
+}
+
+ref_accessors! {
+    String get_string set_string String;
+    Chat get_chat set_chat String;
+    OptChat get_opt_chat set_opt_chat Option<String>;
+    Slot get_slot set_slot Option<ItemStack>;
+    Nbt get_nbt set_nbt NbtField;
+    Particle get_particle set_particle Particle;
+}
+
+impl<const VERSION: u32> EntityMetadataField<VERSION> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: u8) -> Option<&MetadataValue> {
+        self.entries
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, value)| value)
+    }
+
+    /// Replaces any existing value at the index, otherwise appends. Index 0xff is reserved as the
+    /// terminator
+    pub fn set(&mut self, index: u8, value: MetadataValue) {
+        assert_ne!(index, END_INDEX, "metadata index 0xff is reserved");
+        match self.entries.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((index, value)),
+        }
+    }
+
+    pub fn remove(&mut self, index: u8) -> Option<MetadataValue> {
+        let idx = self.entries.iter().position(|(i, _)| *i == index)?;
+        Some(self.entries.remove(idx).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &MetadataValue)> + '_ {
+        self.entries.iter().map(|(i, value)| (*i, value))
+    }
+
+    fn value_size(value: &MetadataValue) -> usize {
+        fn string_size(s: &str) -> usize {
+            VarIntField::new(s.len() as i32).size() + s.len()
+        }
+
+        match value {
+            MetadataValue::Byte(_) | MetadataValue::Bool(_) => 1,
+            MetadataValue::VarInt(v) | MetadataValue::BlockId(v) => VarIntField::new(*v).size(),
+            MetadataValue::Float(_) => 4,
+            MetadataValue::String(s) | MetadataValue::Chat(s) => string_size(s),
+            MetadataValue::OptChat(s) => 1 + s.as_deref().map_or(0, string_size),
+            MetadataValue::Slot(item) => SlotField::<VERSION>::new(item.clone()).size(),
+            MetadataValue::Rotation(_) => 12,
+            MetadataValue::Position(_) => 8,
+            MetadataValue::OptPosition(pos) => 1 + pos.map_or(0, |_| 8),
+            MetadataValue::Direction(dir) => VarIntField::new(dir.id()).size(),
+            MetadataValue::OptUuid(uuid) => 1 + uuid.map_or(0, |_| 16),
+            MetadataValue::Nbt(nbt) => nbt.size(),
+            MetadataValue::Particle(particle) => {
+                VarIntField::new(particle.id).size() + particle.data.size::<VERSION>()
+            }
+            MetadataValue::VillagerData(data) => [data.ty, data.profession, data.level]
+                .iter()
+                .map(|v| VarIntField::new(*v).size())
+                .sum(),
+            MetadataValue::OptVarInt(v) => VarIntField::new(v.map_or(0, |v| v + 1)).size(),
+            // poses missing from this version fail when written
+            MetadataValue::Pose(pose) => VarIntField::new(pose.id(VERSION).unwrap_or(0)).size(),
+        }
+    }
+
+    async fn read_value<R: Read + Unpin + Send>(
+        ty: MetadataType,
+        r: &mut R,
+    ) -> PacketResult<MetadataValue> {
+        async fn varint<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<i32> {
+            Ok(VarIntField::read_field(r).await?.value())
+        }
+
+        async fn present<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<bool> {
+            Ok(*BoolField::read_field(r).await?.value())
+        }
+
+        async fn string<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<String> {
+            Ok(StringField::read_field(r).await?.take())
+        }
+
+        async fn position<R: Read + Unpin + Send, const VERSION: u32>(
+            r: &mut R,
+        ) -> PacketResult<(i32, i32, i32)> {
+            Ok(PositionField::<VERSION>::read_field(r).await?.coords())
+        }
+
+        async fn float<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<f32> {
+            Ok(*FloatField::read_field(r).await?.value())
+        }
+
+        Ok(match ty {
+            MetadataType::Byte => MetadataValue::Byte(*ByteField::read_field(r).await?.value()),
+            MetadataType::VarInt => MetadataValue::VarInt(varint(r).await?),
+            MetadataType::Float => MetadataValue::Float(float(r).await?),
+            MetadataType::String => MetadataValue::String(string(r).await?),
+            MetadataType::Chat => MetadataValue::Chat(string(r).await?),
+            MetadataType::OptChat => MetadataValue::OptChat(if present(r).await? {
+                Some(string(r).await?)
+            } else {
+                None
+            }),
+            MetadataType::Slot => {
+                MetadataValue::Slot(SlotField::<VERSION>::read_field(r).await?.take())
+            }
+            MetadataType::Bool => MetadataValue::Bool(present(r).await?),
+            MetadataType::Rotation => {
+                MetadataValue::Rotation([float(r).await?, float(r).await?, float(r).await?])
+            }
+            MetadataType::Position => MetadataValue::Position(position::<_, VERSION>(r).await?),
+            MetadataType::OptPosition => MetadataValue::OptPosition(if present(r).await? {
+                Some(position::<_, VERSION>(r).await?)
+            } else {
+                None
+            }),
+            MetadataType::Direction => {
+                MetadataValue::Direction(Direction::from_id(varint(r).await?)?)
+            }
+            MetadataType::OptUuid => MetadataValue::OptUuid(if present(r).await? {
+                Some(UuidField::read_field(r).await?)
+            } else {
+                None
+            }),
+            MetadataType::BlockId => MetadataValue::BlockId(varint(r).await?),
+            MetadataType::Nbt => MetadataValue::Nbt(NbtField::read_field(r).await?),
+            MetadataType::Particle => {
+                let id = varint(r).await?;
+                let data = ParticleData::read::<VERSION, _>(id, r).await?;
+                MetadataValue::Particle(Particle { id, data })
+            }
+            MetadataType::VillagerData => MetadataValue::VillagerData(VillagerData {
+                ty: varint(r).await?,
+                profession: varint(r).await?,
+                level: varint(r).await?,
+            }),
+            MetadataType::OptVarInt => MetadataValue::OptVarInt(match varint(r).await? {
+                0 => None,
+                v => Some(v - 1),
+            }),
+            MetadataType::Pose => MetadataValue::Pose(Pose::from_id(VERSION, varint(r).await?)?),
+        })
+    }
+
+    async fn write_value<W: Write + Unpin + Send>(
+        value: &MetadataValue,
+        w: &mut W,
+    ) -> PacketResult<()> {
+        async fn varint<W: Write + Unpin + Send>(w: &mut W, v: i32) -> PacketResult<()> {
+            VarIntField::new(v).write_field(w).await
+        }
+
+        async fn present<W: Write + Unpin + Send>(w: &mut W, present: bool) -> PacketResult<()> {
+            BoolField::new(present).write_field(w).await
+        }
+
+        async fn string<W: Write + Unpin + Send>(w: &mut W, s: &str) -> PacketResult<()> {
+            StringField::new(s.to_owned()).write_field(w).await
+        }
+
+        async fn position<W: Write + Unpin + Send, const VERSION: u32>(
+            w: &mut W,
+            pos: (i32, i32, i32),
+        ) -> PacketResult<()> {
+            PositionField::<VERSION>::new(pos)
+                .ok_or(PacketError::PositionOutOfRange(pos.0, pos.1, pos.2))?
+                .write_field(w)
+                .await
+        }
+
+        match value {
+            MetadataValue::Byte(v) => ByteField::new(*v).write_field(w).await,
+            MetadataValue::VarInt(v) | MetadataValue::BlockId(v) => varint(w, *v).await,
+            MetadataValue::Float(v) => FloatField::new(*v).write_field(w).await,
+            MetadataValue::String(s) | MetadataValue::Chat(s) => string(w, s).await,
+            MetadataValue::OptChat(s) => {
+                present(w, s.is_some()).await?;
+                match s {
+                    Some(s) => string(w, s).await,
+                    None => Ok(()),
+                }
+            }
+            MetadataValue::Slot(item) => {
+                SlotField::<VERSION>::new(item.clone()).write_field(w).await
+            }
+            MetadataValue::Bool(v) => present(w, *v).await,
+            MetadataValue::Rotation(rotation) => {
+                for v in rotation {
+                    FloatField::new(*v).write_field(w).await?;
+                }
+                Ok(())
+            }
+            MetadataValue::Position(pos) => position::<_, VERSION>(w, *pos).await,
+            MetadataValue::OptPosition(pos) => {
+                present(w, pos.is_some()).await?;
+                match pos {
+                    Some(pos) => position::<_, VERSION>(w, *pos).await,
+                    None => Ok(()),
+                }
+            }
+            MetadataValue::Direction(dir) => varint(w, dir.id()).await,
+            MetadataValue::OptUuid(uuid) => {
+                present(w, uuid.is_some()).await?;
+                match uuid {
+                    Some(uuid) => uuid.write_field(w).await,
+                    None => Ok(()),
+                }
+            }
+            MetadataValue::Nbt(nbt) => nbt.write_field(w).await,
+            MetadataValue::Particle(particle) => {
+                varint(w, particle.id).await?;
+                particle.data.write::<VERSION, _>(particle.id, w).await
+            }
+            MetadataValue::VillagerData(data) => {
+                varint(w, data.ty).await?;
+                varint(w, data.profession).await?;
+                varint(w, data.level).await
+            }
+            MetadataValue::OptVarInt(v) => varint(w, v.map_or(0, |v| v + 1)).await,
+            MetadataValue::Pose(pose) => varint(w, pose.id(VERSION)?).await,
+        }
+    }
+}
+
+impl MetadataValue {
+    pub fn ty(&self) -> MetadataType {
+        match self {
+            MetadataValue::Byte(_) => MetadataType::Byte,
+            MetadataValue::VarInt(_) => MetadataType::VarInt,
+            MetadataValue::Float(_) => MetadataType::Float,
+            MetadataValue::String(_) => MetadataType::String,
+            MetadataValue::Chat(_) => MetadataType::Chat,
+            MetadataValue::OptChat(_) => MetadataType::OptChat,
+            MetadataValue::Slot(_) => MetadataType::Slot,
+            MetadataValue::Bool(_) => MetadataType::Bool,
+            MetadataValue::Rotation(_) => MetadataType::Rotation,
+            MetadataValue::Position(_) => MetadataType::Position,
+            MetadataValue::OptPosition(_) => MetadataType::OptPosition,
+            MetadataValue::Direction(_) => MetadataType::Direction,
+            MetadataValue::OptUuid(_) => MetadataType::OptUuid,
+            MetadataValue::BlockId(_) => MetadataType::BlockId,
+            MetadataValue::Nbt(_) => MetadataType::Nbt,
+            MetadataValue::Particle(_) => MetadataType::Particle,
+            MetadataValue::VillagerData(_) => MetadataType::VillagerData,
+            MetadataValue::OptVarInt(_) => MetadataType::OptVarInt,
+            MetadataValue::Pose(_) => MetadataType::Pose,
+        }
+    }
+}
+
+impl Display for MetadataValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataValue::Byte(v) => write!(f, "{}", v),
+            MetadataValue::VarInt(v) | MetadataValue::BlockId(v) => write!(f, "{}", v),
+            MetadataValue::Float(v) => write!(f, "{}", v),
+            MetadataValue::String(s) | MetadataValue::Chat(s) => write!(f, "{:?}", s),
+            MetadataValue::Bool(v) => write!(f, "{}", v),
+            MetadataValue::Nbt(nbt) => write!(f, "{}", nbt),
+            MetadataValue::Particle(particle) => write!(f, "{}", particle),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> Field for EntityMetadataField<VERSION> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        let entries: usize = self
+            .entries
+            .iter()
+            .map(|(_, value)| {
+                let ty = type_id(VERSION, value.ty()).unwrap_or(0);
+                1 + VarIntField::new(ty).size() + Self::value_size(value)
+            })
+            .sum();
+
+        entries + 1
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut entries = Vec::new();
+        loop {
+            let index = *UByteField::read_field(r).await?.value();
+            if index == END_INDEX {
+                break;
+            }
+
+            let type_id = VarIntField::read_field(r).await?.value();
+            let ty = usize::try_from(type_id)
+                .ok()
+                .and_then(|idx| types(VERSION).get(idx))
+                .ok_or(PacketError::BadMetadataType(type_id))?;
+
+            let value = Self::read_value(*ty, r).await?;
+            entries.push((index, value));
+        }
+
+        Ok(Self { entries })
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        for (index, value) in &self.entries {
+            let ty = type_id(VERSION, value.ty())
+                .ok_or(PacketError::UnsupportedMetadataType(value.ty()))?;
+
+            UByteField::new(*index).write_field(w).await?;
+            VarIntField::new(ty).write_field(w).await?;
+            Self::write_value(value, w).await?;
+        }
+
+        UByteField::new(END_INDEX).write_field(w).await
+    }
+}
+
+fn type_id(version: u32, ty: MetadataType) -> Option<i32> {
+    types(version)
+        .iter()
+        .position(|t| *t == ty)
+        .map(|idx| idx as i32)
+}
+
+impl<const VERSION: u32> Display for EntityMetadataField<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("{")?;
+        for (i, (index, value)) in self.entries.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", index, value)?;
+        }
+        f.write_str("}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    async fn round_trip<const VERSION: u32>(
+        metadata: &EntityMetadataField<VERSION>,
+    ) -> PacketResult<Vec<u8>> {
+        let mut cursor = Cursor::new(Vec::new());
+        metadata.write_field(&mut cursor).await?;
+        assert_eq!(cursor.get_ref().len(), metadata.size());
+
+        cursor.set_position(0);
+        let read = EntityMetadataField::<VERSION>::read_field(&mut cursor).await?;
+        assert_eq!(&read, metadata);
+        Ok(cursor.into_inner())
+    }
+
+    #[async_test]
+    async fn metadata_type_ids() -> PacketResult<()> {
+        let mut metadata = EntityMetadataField::<340>::new();
+        metadata.set_byte(0, 0x20);
+        metadata.set_bool(5, true);
+        assert_eq!(
+            round_trip(&metadata).await?,
+            b"\x00\x00\x20\x05\x06\x01\xff"
+        );
+
+        let mut metadata = EntityMetadataField::<404>::new();
+        metadata.set_byte(0, 0x20);
+        metadata.set_bool(5, true);
+        metadata.set_opt_chat(2, Some("{\"text\":\"Bob\"}".to_owned()));
+        assert_eq!(
+            &round_trip(&metadata).await?[..7],
+            b"\x00\x00\x20\x05\x07\x01\x02"
+        );
+
+        let mut metadata = EntityMetadataField::<578>::new();
+        metadata.set_pose(6, Pose::Sneaking);
+        metadata.set_opt_varint(7, None);
+        metadata.set_opt_varint(8, Some(4));
+        assert_eq!(
+            round_trip(&metadata).await?,
+            b"\x06\x12\x05\x07\x11\x00\x08\x11\x05\xff"
+        );
+
+        let mut metadata = EntityMetadataField::<755>::new();
+        metadata.set_pose(6, Pose::LongJumping);
+        metadata.set_pose(7, Pose::Dying);
+        assert_eq!(
+            round_trip(&metadata).await?,
+            b"\x06\x12\x06\x07\x12\x07\xff"
+        );
+        Ok(())
+    }
+
+    #[async_test]
+    async fn metadata_values() -> PacketResult<()> {
+        let mut metadata = EntityMetadataField::<578>::new();
+        metadata.set_varint(1, 300);
+        metadata.set_float(2, 1.5);
+        metadata.set_string(3, "name".to_owned());
+        metadata.set_chat(4, "{\"text\":\"hi\"}".to_owned());
+        metadata.set_slot(5, Some(ItemStack::new(1, 64)));
+        metadata.set_rotation(6, [0.0, 90.0, 180.0]);
+        metadata.set_position(7, (1, 2, 3));
+        metadata.set_opt_position(8, None);
+        metadata.set_direction(9, Direction::West);
+        metadata.set_opt_uuid(10, Some(UuidField::new(42)));
+        metadata.set_block_id(11, 9);
+        metadata.set_nbt(12, NbtField::from_snbt("{a:1b}")?);
+        metadata.set_particle(
+            13,
+            Particle {
+                id: 3,
+                data: ParticleData::BlockState(1),
+            },
+        );
+        metadata.set_villager_data(
+            14,
+            VillagerData {
+                ty: 1,
+                profession: 2,
+                level: 3,
+            },
+        );
+        round_trip(&metadata).await?;
+
+        assert_eq!(metadata.len(), 14);
+        assert_eq!(metadata.get_varint(1), Some(300));
+        assert_eq!(metadata.get_float(1), None);
+        assert_eq!(metadata.get_string(3).map(String::as_str), Some("name"));
+        assert_eq!(metadata.get_direction(9), Some(Direction::West));
+        assert_eq!(metadata.get_opt_uuid(10), Some(Some(UuidField::new(42))));
+
+        metadata.set_varint(1, 5);
+        assert_eq!(metadata.iter().next(), Some((1, &MetadataValue::VarInt(5))));
+        assert_eq!(metadata.remove(1), Some(MetadataValue::VarInt(5)));
+        assert_eq!(metadata.get(1), None);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn metadata_invalid() {
+        let mut cursor = Cursor::new(b"\x00\x0e\x00\xff".to_vec());
+        assert!(matches!(
+            EntityMetadataField::<340>::read_field(&mut cursor).await,
+            Err(PacketError::BadMetadataType(14))
+        ));
+
+        let mut cursor = Cursor::new(b"\x00\x0b\x07\xff".to_vec());
+        assert!(matches!(
+            EntityMetadataField::<578>::read_field(&mut cursor).await,
+            Err(PacketError::BadMetadataEnum {
+                kind: "Direction",
+                value: 7
+            })
+        ));
+
+        let mut metadata = EntityMetadataField::<340>::new();
+        metadata.set_pose(6, Pose::Standing);
+        let mut cursor = Cursor::new(Vec::new());
+        assert!(matches!(
+            metadata.write_field(&mut cursor).await,
+            Err(PacketError::UnsupportedMetadataType(MetadataType::Pose))
+        ));
+
+        let mut metadata = EntityMetadataField::<578>::new();
+        metadata.set_pose(6, Pose::LongJumping);
+        let mut cursor = Cursor::new(Vec::new());
+        assert!(matches!(
+            metadata.write_field(&mut cursor).await,
+            Err(PacketError::UnsupportedPose(Pose::LongJumping))
+        ));
+
+        let mut cursor = Cursor::new(b"\x06\x12\x07\xff".to_vec());
+        assert!(matches!(
+            EntityMetadataField::<578>::read_field(&mut cursor).await,
+            Err(PacketError::BadMetadataEnum {
+                kind: "Pose",
+                value: 7
+            })
+        ));
+    }
+}
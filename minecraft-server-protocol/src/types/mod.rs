@@ -1,24 +1,53 @@
-pub use array::{PrefixedArrayField, RestOfPacketByteArrayField, VarIntThenByteArrayField};
-pub use field::Field;
-pub use nbt::{NbtBuilder, NbtField};
+pub use array::{
+    ArrayLength, FixedArrayField, PrefixedArrayField, RestOfPacketByteArrayField,
+    VarIntThenByteArrayField,
+};
+pub use bitfield::BitfieldField;
+pub use chat::{
+    ChatField, ClickEvent, Color, Content, HoverEntity, HoverEvent, HoverItem, NamedColor, Score,
+    StatusResponse, Style, TextComponent, SECTION_SIGN,
+};
+pub use equipment::{Equipment, EquipmentField, EquipmentSlot};
+pub use field::{DependentField, Field};
+pub use metadata::{
+    Direction, EntityMetadataField, MetadataType, MetadataValue, Pose, VillagerData,
+};
+pub use nbt::{
+    NbtBuilder, NbtByteArray, NbtCompound, NbtCompression, NbtField, NbtIntArray, NbtList,
+    NbtLongArray, NbtTag, NbtValue, OptionalNbtField,
+};
+pub use option::OptionField;
 pub use packet::*;
+pub use particle::{Particle, ParticleData, ParticleDataField, VibrationDestination};
 pub use position::PositionField;
 pub use primitive::{
     BoolField, ByteField, DoubleField, FloatField, IntField, LongField, ShortField, UByteField,
     UShortField,
 };
+pub use slot::{ItemStack, SlotField};
 pub use string::{IdentifierField, StringField};
+pub use uuid::UuidField;
 pub use varint::VarIntField;
+pub use varlong::VarLongField;
 
 mod field;
 mod packet;
 
 mod array;
+mod bitfield;
+mod chat;
+mod equipment;
+mod metadata;
 mod nbt;
+mod option;
+mod particle;
 mod position;
 mod primitive;
+mod slot;
 mod string;
+mod uuid;
 mod varint;
+mod varlong;
 
 #[cfg(test)]
 mod tests {
@@ -46,4 +75,179 @@ mod tests {
             assert_eq!(cursor.position() as usize, expected_len);
         });
     }
+
+    #[test]
+    fn derived_container() {
+        use async_std::io::prelude::*;
+        use minecraft_server_protocol_derive::Field;
+        use std::fmt::{Display, Formatter};
+
+        #[derive(Field)]
+        struct Entry {
+            name: StringField,
+            value: OptionField<VarIntField>,
+        }
+
+        async_std::task::block_on(async {
+            let mut cursor = Cursor::new(b"\x03abc\x01\x2a\x01x\x00".to_vec());
+            let entries = vec![
+                Entry::read_field(&mut cursor).await.unwrap(),
+                Entry::read_field(&mut cursor).await.unwrap(),
+            ];
+            assert_eq!(cursor.position(), 9);
+            assert_eq!(entries[0].to_string(), "Entry(name=abc, value=42)");
+            assert_eq!(entries[1].to_string(), "Entry(name=x, value=none)");
+            assert_eq!(entries[0].size() + entries[1].size(), 9);
+
+            let mut out = Cursor::new(Vec::new());
+            for entry in &entries {
+                entry.write_field(&mut out).await.unwrap();
+            }
+            assert_eq!(out.get_ref(), cursor.get_ref());
+        });
+    }
+
+    #[test]
+    fn derived_parent_dependency() {
+        use async_std::io::prelude::*;
+        use async_trait::async_trait;
+        use minecraft_server_protocol_derive::Field;
+        use std::fmt::{Display, Formatter};
+
+        // as generated for a switch
+        enum Value {
+            Number(VarIntField),
+            Text(StringField),
+        }
+
+        impl Display for Value {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Number(value) => write!(f, "Number({})", value.value()),
+                    Self::Text(value) => write!(f, "Text({})", value.value()),
+                }
+            }
+        }
+
+        #[async_trait]
+        impl Field for Value {
+            type Displayable = Self;
+
+            fn value(&self) -> &Self::Displayable {
+                self
+            }
+
+            fn size(&self) -> usize {
+                match self {
+                    Self::Number(value) => value.size(),
+                    Self::Text(value) => value.size(),
+                }
+            }
+
+            async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+                Err(PacketError::MissingDependency("Value"))
+            }
+
+            async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+                match self {
+                    Self::Number(value) => value.write_field(w).await,
+                    Self::Text(value) => value.write_field(w).await,
+                }
+            }
+        }
+
+        #[async_trait]
+        impl DependentField for Value {
+            type Dependency = i32;
+
+            async fn read_dependent<R: Read + Unpin + Send>(
+                r: &mut R,
+                dependency: &i32,
+            ) -> PacketResult<Self> {
+                Ok(match *dependency {
+                    0 => Self::Number(Field::read_field(r).await?),
+                    _ => Self::Text(Field::read_field(r).await?),
+                })
+            }
+
+            async fn write_dependent<W: Write + Unpin + Send>(
+                &self,
+                w: &mut W,
+                dependency: &i32,
+            ) -> PacketResult<()> {
+                let selected = match *dependency {
+                    0 => "Number",
+                    _ => "Text",
+                };
+                let variant = match self {
+                    Self::Number(_) => "Number",
+                    Self::Text(_) => "Text",
+                };
+                if variant != selected {
+                    return Err(PacketError::SwitchMismatch {
+                        field: "Value",
+                        variant,
+                    });
+                }
+
+                match self {
+                    Self::Number(value) => value.write_field(w).await,
+                    Self::Text(value) => value.write_field(w).await,
+                }
+            }
+        }
+
+        #[derive(Field)]
+        #[parent_dependency = "i32"]
+        struct Entry {
+            id: UByteField,
+            #[depends_on = ".."]
+            value: Value,
+        }
+
+        #[derive(Field)]
+        struct Update {
+            action: VarIntField,
+            #[depends_on = "action"]
+            entries: PrefixedArrayField<VarIntField, Entry>,
+        }
+
+        async_std::task::block_on(async {
+            let mut cursor = Cursor::new(b"\x01\x02\x07\x02hi\x08\x00".to_vec());
+            let update = Update::read_field(&mut cursor).await.unwrap();
+            assert_eq!(
+                update.to_string(),
+                "Update(action=1, entries=[Entry(id=7, value=Text(hi)), Entry(id=8, value=Text())])"
+            );
+            assert_eq!(update.size(), 8);
+
+            let mut cursor = Cursor::new(b"\x00\x01\x07\x2a".to_vec());
+            let update = Update::read_field(&mut cursor).await.unwrap();
+            assert!(matches!(update.entries[0].value, Value::Number(ref n) if n.value() == 42));
+
+            let mut written = Cursor::new(Vec::new());
+            update.write_field(&mut written).await.unwrap();
+            assert_eq!(written.get_ref().as_slice(), b"\x00\x01\x07\x2a");
+
+            let mismatched = Update {
+                action: VarIntField::new(1),
+                entries: PrefixedArrayField::new(vec![Entry {
+                    id: UByteField::new(7),
+                    value: Value::Number(VarIntField::new(42)),
+                }]),
+            };
+            assert!(matches!(
+                mismatched.write_field(&mut written).await,
+                Err(PacketError::SwitchMismatch {
+                    field: "Value",
+                    variant: "Number"
+                })
+            ));
+
+            assert!(matches!(
+                Entry::read_field(&mut cursor).await,
+                Err(PacketError::MissingDependency("Entry"))
+            ));
+        });
+    }
 }
@@ -0,0 +1,477 @@
+//! serde data format reading from an [NbtValue] tree, the inverse of [ser](super::ser).
+//!
+//! Arrays and lists can both be deserialized as sequences, and bytes as bools.
+
+use crate::types::nbt::{NbtCompound, NbtValue};
+use crate::types::{NbtField, PacketError, PacketResult};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::fmt::Display;
+
+impl de::Error for PacketError {
+    fn custom<T: Display>(msg: T) -> Self {
+        PacketError::NbtSerde(msg.to_string())
+    }
+}
+
+impl NbtValue {
+    pub fn deserialize_into<T: DeserializeOwned>(self) -> PacketResult<T> {
+        T::deserialize(self)
+    }
+}
+
+impl NbtField {
+    /// Deserializes the root tag, ignoring its name
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> PacketResult<T> {
+        self.to_value()?.1.deserialize_into()
+    }
+}
+
+struct SeqDeserializer<I>(I);
+
+struct CompoundDeserializer {
+    entries: std::vec::IntoIter<(String, NbtValue)>,
+    next_value: Option<NbtValue>,
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Option<NbtValue>,
+}
+
+struct VariantDeserializer(Option<NbtValue>);
+
+fn visit_seq<'de, V: Visitor<'de>, T: Into<NbtValue>>(
+    visitor: V,
+    values: Vec<T>,
+) -> PacketResult<V::Value> {
+    visitor.visit_seq(SeqDeserializer(values.into_iter().map(Into::into)))
+}
+
+fn visit_compound<'de, V: Visitor<'de>>(
+    visitor: V,
+    compound: NbtCompound,
+) -> PacketResult<V::Value> {
+    visitor.visit_map(CompoundDeserializer {
+        entries: compound.into_iter().collect::<Vec<_>>().into_iter(),
+        next_value: None,
+    })
+}
+
+macro_rules! deserialize_unsigned {
+    ($($name:ident $variant:ident $visit:ident $ty:ty;)*) => {
+        $(
+            fn $name<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+                match self {
+                    NbtValue::$variant(v) => visitor.$visit(v as $ty),
+                    other => other.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NbtValue {
+    type Error = PacketError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        match self {
+            NbtValue::Byte(v) => visitor.visit_i8(v),
+            NbtValue::Short(v) => visitor.visit_i16(v),
+            NbtValue::Int(v) => visitor.visit_i32(v),
+            NbtValue::Long(v) => visitor.visit_i64(v),
+            NbtValue::Float(v) => visitor.visit_f32(v),
+            NbtValue::Double(v) => visitor.visit_f64(v),
+            NbtValue::ByteArray(v) => visit_seq(visitor, v),
+            NbtValue::String(v) => visitor.visit_string(v),
+            NbtValue::List(v) => visit_seq(visitor, v.into_iter().collect()),
+            NbtValue::Compound(v) => visit_compound(visitor, v),
+            NbtValue::IntArray(v) => visit_seq(visitor, v),
+            NbtValue::LongArray(v) => visit_seq(visitor, v),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        match self {
+            NbtValue::Byte(b) => visitor.visit_bool(b != 0),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_unsigned! {
+        deserialize_u8 Byte visit_u8 u8;
+        deserialize_u16 Short visit_u16 u16;
+        deserialize_u32 Int visit_u32 u32;
+        deserialize_u64 Long visit_u64 u64;
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        match self {
+            NbtValue::ByteArray(v) => {
+                visitor.visit_byte_buf(v.into_iter().map(|b| b as u8).collect())
+            }
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        // missing values never make it this far
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> PacketResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> PacketResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> PacketResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> PacketResult<V::Value> {
+        let (variant, value) = match self {
+            NbtValue::String(variant) => (variant, None),
+            NbtValue::Compound(compound) if compound.len() == 1 => {
+                let (variant, value) = compound.into_iter().next().unwrap();
+                (variant, Some(value))
+            }
+            other => {
+                return Err(PacketError::NbtSerde(format!(
+                    "expected a string or single entry compound for an enum, got {:?}",
+                    other.tag()
+                )))
+            }
+        };
+
+        visitor.visit_enum(EnumDeserializer { variant, value })
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u128 f32 f64 char str string
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, I: Iterator<Item = NbtValue>> SeqAccess<'de> for SeqDeserializer<I> {
+    type Error = PacketError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> PacketResult<Option<T::Value>> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.0.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(upper),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for CompoundDeserializer {
+    type Error = PacketError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> PacketResult<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.next_value = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> PacketResult<V::Value> {
+        let value = self
+            .next_value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = PacketError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> PacketResult<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(IntoDeserializer::<PacketError>::into_deserializer(
+            self.variant,
+        ))?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+impl VariantDeserializer {
+    fn value(self) -> PacketResult<NbtValue> {
+        self.0.ok_or_else(|| {
+            PacketError::NbtSerde("expected an enum variant with a value".to_owned())
+        })
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = PacketError;
+
+    fn unit_variant(self) -> PacketResult<()> {
+        match self.0 {
+            None => Ok(()),
+            Some(_) => Err(PacketError::NbtSerde(
+                "expected a unit enum variant".to_owned(),
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> PacketResult<T::Value> {
+        seed.deserialize(self.value()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> PacketResult<V::Value> {
+        de::Deserializer::deserialize_seq(self.value()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> PacketResult<V::Value> {
+        de::Deserializer::deserialize_map(self.value()?, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Field, NbtByteArray, NbtIntArray, NbtList, NbtLongArray, NbtTag};
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct DimensionCodec {
+        #[serde(rename = "minecraft:dimension_type")]
+        dimension_type: Registry<DimensionType>,
+        heightmap: NbtLongArray,
+        sections: NbtIntArray,
+        light: NbtByteArray,
+        tags: BTreeMap<String, Vec<String>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Registry<T> {
+        #[serde(rename = "type")]
+        ty: String,
+        value: Vec<RegistryEntry<T>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct RegistryEntry<T> {
+        name: String,
+        id: i32,
+        element: T,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct DimensionType {
+        piglin_safe: bool,
+        ambient_light: f32,
+        coordinate_scale: f64,
+        fixed_time: Option<i64>,
+        logical_height: u16,
+        effects: Effects,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Effects {
+        Overworld,
+        TheNether,
+        Custom { sky: i32 },
+    }
+
+    fn codec() -> DimensionCodec {
+        let overworld = DimensionType {
+            piglin_safe: false,
+            ambient_light: 0.0,
+            coordinate_scale: 1.0,
+            fixed_time: None,
+            logical_height: 256,
+            effects: Effects::Overworld,
+        };
+        let nether = DimensionType {
+            piglin_safe: true,
+            ambient_light: 0.1,
+            coordinate_scale: 8.0,
+            fixed_time: Some(18000),
+            logical_height: 128,
+            effects: Effects::Custom { sky: 3 },
+        };
+
+        let mut tags = BTreeMap::new();
+        tags.insert("minecraft:logs".to_owned(), vec!["oak_log".to_owned()]);
+        tags.insert("minecraft:empty".to_owned(), vec![]);
+
+        DimensionCodec {
+            dimension_type: Registry {
+                ty: "minecraft:dimension_type".to_owned(),
+                value: vec![
+                    RegistryEntry {
+                        name: "minecraft:overworld".to_owned(),
+                        id: 0,
+                        element: overworld,
+                    },
+                    RegistryEntry {
+                        name: "minecraft:the_nether".to_owned(),
+                        id: 1,
+                        element: nether,
+                    },
+                ],
+            },
+            heightmap: NbtLongArray(vec![1, 2, i64::MIN]),
+            sections: NbtIntArray(vec![-1, 0]),
+            light: NbtByteArray(vec![15, 0]),
+            tags,
+        }
+    }
+
+    #[async_test]
+    async fn serde_round_trip() -> PacketResult<()> {
+        let codec = codec();
+        let field = NbtField::serialize_from(&codec)?;
+
+        let mut cursor = Cursor::new(Vec::new());
+        field.write_field(&mut cursor).await?;
+        cursor.set_position(0);
+        let read = NbtField::read_field(&mut cursor).await?;
+
+        assert_eq!(read.deserialize_into::<DimensionCodec>()?, codec);
+        Ok(())
+    }
+
+    #[test]
+    fn serde_tags() -> PacketResult<()> {
+        let value = NbtValue::serialize_from(&codec())?;
+        let root = value.as_compound().unwrap();
+
+        assert_eq!(
+            root.get_long_array("heightmap").map(Vec::as_slice),
+            Some(&[1, 2, i64::MIN][..])
+        );
+        assert_eq!(
+            root.get_int_array("sections").map(Vec::as_slice),
+            Some(&[-1, 0][..])
+        );
+        assert_eq!(
+            root.get_byte_array("light").map(Vec::as_slice),
+            Some(&[15, 0][..])
+        );
+        assert_eq!(
+            root.get_path("tags.minecraft:empty")
+                .and_then(NbtValue::as_list)
+                .map(NbtList::tag),
+            Some(NbtTag::End)
+        );
+
+        let overworld = root
+            .get_path("minecraft:dimension_type.value.0.element")
+            .and_then(NbtValue::as_compound)
+            .unwrap();
+        assert_eq!(overworld.get_byte("piglin_safe"), Some(0));
+        assert_eq!(overworld.get_short("logical_height"), Some(256));
+        assert_eq!(overworld.get_str("effects"), Some("overworld"));
+        assert!(!overworld.contains_key("fixed_time"));
+
+        let nether = root
+            .get_path("minecraft:dimension_type.value.1.element")
+            .and_then(NbtValue::as_compound)
+            .unwrap();
+        assert_eq!(nether.get_long("fixed_time"), Some(18000));
+        assert_eq!(
+            nether
+                .get_path("effects.custom.sky")
+                .and_then(NbtValue::as_int),
+            Some(3)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serde_arrays() -> PacketResult<()> {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Arrays {
+            ints: Vec<i32>,
+            empty: Vec<i64>,
+            empty_array: NbtIntArray,
+            bytes: NbtByteArray,
+        }
+
+        let arrays = Arrays {
+            ints: vec![1, 2],
+            empty: vec![],
+            empty_array: NbtIntArray::default(),
+            bytes: NbtByteArray(vec![-1]),
+        };
+        let value = NbtValue::serialize_from(&arrays)?;
+        let root = value.as_compound().unwrap();
+
+        let ints = root.get_list("ints").unwrap();
+        assert_eq!(ints.tag(), NbtTag::Int);
+        assert_eq!(ints.len(), 2);
+        assert_eq!(root.get_list("empty").map(NbtList::tag), Some(NbtTag::End));
+        assert_eq!(root.get_int_array("empty_array"), Some(&vec![]));
+        assert_eq!(root.get_byte_array("bytes"), Some(&vec![-1]));
+
+        assert_eq!(value.deserialize_into::<Arrays>()?, arrays);
+        Ok(())
+    }
+
+    #[test]
+    fn serde_errors() {
+        assert!(NbtValue::serialize_from(&None::<i32>).is_err());
+        assert!(NbtValue::serialize_from(&vec![Some(1), None]).is_err());
+        assert!(NbtValue::serialize_from(&(1, "mixed")).is_err());
+        assert!(NbtValue::Int(5).deserialize_into::<String>().is_err());
+
+        #[derive(Serialize)]
+        struct NbtIntArray(Vec<i64>);
+        assert!(NbtValue::serialize_from(&NbtIntArray(vec![1])).is_err());
+    }
+}
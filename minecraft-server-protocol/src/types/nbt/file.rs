@@ -0,0 +1,177 @@
+//! Reading and writing NBT files like `level.dat` and `playerdata/*.dat`, which are usually
+//! compressed.
+
+use crate::types::{Field, NbtField, PacketError, PacketResult};
+use async_std::io::Cursor;
+use async_std::path::Path;
+use async_std::task;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use std::io::{Read, Write};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NbtCompression {
+    None,
+    /// Used by `level.dat` and player data
+    Gzip,
+    /// Used by region file chunks
+    Zlib,
+}
+
+impl NbtCompression {
+    /// Guesses from the magic bytes, anything unrecognised is assumed uncompressed
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0x1f, 0x8b, ..] => NbtCompression::Gzip,
+            // CMF for deflate with a 32K window, and the FCHECK bits make it a multiple of 31
+            [0x78, flg, ..] if (0x7800 | *flg as u16).is_multiple_of(31) => NbtCompression::Zlib,
+            _ => NbtCompression::None,
+        }
+    }
+}
+
+impl NbtField {
+    /// Decompresses with the detected compression and reads the root tag, which must be all there
+    /// is
+    pub async fn from_compressed(bytes: &[u8]) -> PacketResult<Self> {
+        let decompressed;
+        let raw = match NbtCompression::detect(bytes) {
+            NbtCompression::None => bytes,
+            NbtCompression::Gzip => {
+                decompressed = decompress(bytes, GzDecoder::new).await?;
+                &decompressed
+            }
+            NbtCompression::Zlib => {
+                decompressed = decompress(bytes, ZlibDecoder::new).await?;
+                &decompressed
+            }
+        };
+
+        let mut cursor = Cursor::new(raw);
+        let field = NbtField::read_field(&mut cursor).await?;
+        match raw.len() - cursor.position() as usize {
+            0 => Ok(field),
+            trailing => Err(PacketError::TrailingNbtBytes(trailing)),
+        }
+    }
+
+    pub fn to_compressed(&self, compression: NbtCompression) -> PacketResult<Vec<u8>> {
+        let bytes = match compression {
+            NbtCompression::None => self.as_bytes().to_vec(),
+            NbtCompression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(self.as_bytes())?;
+                encoder.finish()?
+            }
+            NbtCompression::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(self.as_bytes())?;
+                encoder.finish()?
+            }
+        };
+
+        Ok(bytes)
+    }
+
+    pub async fn read_file(path: impl AsRef<Path>) -> PacketResult<Self> {
+        let bytes = async_std::fs::read(path).await?;
+        Self::from_compressed(&bytes).await
+    }
+
+    pub async fn write_file(
+        &self,
+        path: impl AsRef<Path>,
+        compression: NbtCompression,
+    ) -> PacketResult<()> {
+        let bytes = self.to_compressed(compression)?;
+        async_std::fs::write(path, bytes).await?;
+        Ok(())
+    }
+}
+
+/// flate2 blocks, so this runs on the blocking thread pool
+async fn decompress<D: Read + 'static>(
+    bytes: &[u8],
+    decoder: fn(std::io::Cursor<Vec<u8>>) -> D,
+) -> PacketResult<Vec<u8>> {
+    let bytes = std::io::Cursor::new(bytes.to_vec());
+    task::spawn_blocking(move || {
+        let mut out = Vec::new();
+        decoder(bytes).read_to_end(&mut out)?;
+        Ok(out)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::nbt::NbtCompound;
+    use futures_await_test::async_test;
+    use std::convert::TryFrom;
+
+    fn level() -> NbtField {
+        "{Data:{LevelName:\"world\",RandomSeed:-42L,Version:{Id:2586}}}"
+            .parse::<NbtCompound>()
+            .unwrap()
+            .into()
+    }
+
+    #[async_test]
+    async fn nbt_compression_round_trip() -> PacketResult<()> {
+        let level = level();
+        for compression in &[
+            NbtCompression::None,
+            NbtCompression::Gzip,
+            NbtCompression::Zlib,
+        ] {
+            let bytes = level.to_compressed(*compression)?;
+            assert_eq!(NbtCompression::detect(&bytes), *compression);
+
+            let read = NbtField::from_compressed(&bytes).await?;
+            assert_eq!(read.as_bytes(), level.as_bytes());
+        }
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_file_round_trip() -> PacketResult<()> {
+        let path = std::env::temp_dir().join(format!("nbt-test-{}.dat", std::process::id()));
+        let level = level();
+
+        level.write_file(&path, NbtCompression::Gzip).await?;
+        let read = NbtField::read_file(&path).await;
+        async_std::fs::remove_file(&path).await?;
+
+        assert_eq!(
+            NbtCompound::try_from(&read?)?.get_path("Data.RandomSeed"),
+            Some(&(-42i64).into())
+        );
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_compression_corrupt() {
+        let mut bytes = level().to_compressed(NbtCompression::Gzip).unwrap();
+        bytes.truncate(bytes.len() / 2);
+        assert!(matches!(
+            NbtField::from_compressed(&bytes).await,
+            Err(PacketError::Io(_))
+        ));
+    }
+
+    #[async_test]
+    async fn nbt_trailing_bytes() {
+        let mut bytes = level().as_bytes().to_vec();
+        bytes.push(0);
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&bytes).unwrap();
+
+        for bytes in &[bytes.clone(), encoder.finish().unwrap()] {
+            assert!(matches!(
+                NbtField::from_compressed(bytes).await,
+                Err(PacketError::TrailingNbtBytes(1))
+            ));
+        }
+    }
+}
@@ -0,0 +1,903 @@
+use crate::types::{Field, PacketError, PacketResult};
+use async_std::io::prelude::*;
+use async_std::io::Cursor;
+use async_trait::async_trait;
+use mutf8::mstr;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+pub use file::NbtCompression;
+pub use ser::{NbtByteArray, NbtIntArray, NbtLongArray};
+pub use value::{NbtCompound, NbtList, NbtValue};
+
+mod de;
+mod file;
+mod ser;
+mod snbt;
+mod value;
+
+/// Max depth of nested compounds and lists, same as vanilla
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NbtField {
+    bytes: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct NbtBuilder(Cursor<Vec<u8>>);
+/// Returns to its parent `P` when ended
+pub struct NbtCompoundBuilder<P = NbtBuilder>(P);
+pub struct NbtTagBuilder<'a, P = NbtBuilder>(NbtCompoundBuilder<P>, Option<&'a str>);
+
+/// Unnamed tags that must all be the same type. The element type and count are written when
+/// the list is ended
+pub struct NbtListBuilder<P> {
+    parent: P,
+    /// Offset of the element type and count
+    header: usize,
+    tag: NbtTag,
+    count: i32,
+}
+
+/// A builder that nested compounds and lists can write through
+pub trait NbtParent: Send {
+    fn builder(&mut self) -> &mut NbtBuilder;
+}
+
+/// Reads a single NBT tag from a stream, keeping a copy of every byte read
+struct NbtReader<'r, R> {
+    r: &'r mut R,
+    bytes: Vec<u8>,
+}
+
+enum NbtFrame {
+    Compound,
+    List { tag: NbtTag, remaining: i32 },
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NbtTag {
+    End = 0,
+    Byte = 1,
+    Short = 2,
+    Int = 3,
+    Long = 4,
+    Float = 5,
+    Double = 6,
+    ByteArray = 7,
+    String = 8,
+    List = 9,
+    Compound = 10,
+    IntArray = 11,
+    LongArray = 12,
+}
+
+impl TryFrom<u8> for NbtTag {
+    type Error = PacketError;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        Ok(match b {
+            0 => NbtTag::End,
+            1 => NbtTag::Byte,
+            2 => NbtTag::Short,
+            3 => NbtTag::Int,
+            4 => NbtTag::Long,
+            5 => NbtTag::Float,
+            6 => NbtTag::Double,
+            7 => NbtTag::ByteArray,
+            8 => NbtTag::String,
+            9 => NbtTag::List,
+            10 => NbtTag::Compound,
+            11 => NbtTag::IntArray,
+            12 => NbtTag::LongArray,
+            _ => return Err(PacketError::BadNbtTag(b)),
+        })
+    }
+}
+
+macro_rules! write_primitives {
+    ($($int:ty)*) => {
+        paste::paste! {
+            $(
+                /// No tag
+                async fn [< write_ $int >](&mut self, int: $int) -> PacketResult<()> {
+                    let bytes = int.to_be_bytes();
+                    self.0.write_all(&bytes).await?;
+                    Ok(())
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! nbt_primitive {
+    ($tag:expr, $name:ident, $int:ty) => {
+        paste::paste! {
+            pub async fn $name(mut self, val: $int) -> PacketResult<NbtCompoundBuilder<P>> {
+                self.write_tag($tag).await?;
+                self.0.builder().[< write_ $int >](val).await?;
+                Ok(self.0)
+            }
+        }
+    };
+}
+
+macro_rules! nbt_array {
+    ($tag:expr, $name:ident, $int:ty) => {
+        pub async fn $name(mut self, val: &[$int]) -> PacketResult<NbtCompoundBuilder<P>> {
+            self.write_tag($tag).await?;
+            self.0
+                .builder()
+                .write_array(val, <$int>::to_be_bytes)
+                .await?;
+            Ok(self.0)
+        }
+    };
+}
+
+macro_rules! list_primitive {
+    ($tag:expr, $name:ident, $int:ty) => {
+        paste::paste! {
+            pub async fn $name(mut self, val: $int) -> PacketResult<Self> {
+                self.add_element($tag)?;
+                self.parent.builder().[< write_ $int >](val).await?;
+                Ok(self)
+            }
+        }
+    };
+}
+
+macro_rules! list_array {
+    ($tag:expr, $name:ident, $int:ty) => {
+        pub async fn $name(mut self, val: &[$int]) -> PacketResult<Self> {
+            self.add_element($tag)?;
+            self.parent
+                .builder()
+                .write_array(val, <$int>::to_be_bytes)
+                .await?;
+            Ok(self)
+        }
+    };
+}
+
+impl NbtField {
+    pub fn builder() -> NbtBuilder {
+        NbtBuilder::default()
+    }
+
+    /// The encoded root tag, including its type and name
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl NbtBuilder {
+    pub fn new(storage: Vec<u8>) -> Self {
+        Self(Cursor::new(storage))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.get_ref()
+    }
+
+    pub fn into_field(self) -> NbtField {
+        NbtField {
+            bytes: self.0.into_inner(),
+        }
+    }
+
+    async fn write_tag_raw(&mut self, tag: NbtTag) -> PacketResult<()> {
+        let b = tag as u8;
+        self.0.write_all(&[b]).await?;
+        Ok(())
+    }
+
+    /// No tag
+    async fn write_string_raw(&mut self, str: &str) -> PacketResult<()> {
+        let mut bytes = Vec::with_capacity(str.len() + 2);
+        value::write_string(&mut bytes, str);
+        self.0.write_all(&bytes).await?;
+        Ok(())
+    }
+
+    /// No tag, length prefixed
+    async fn write_array<T: Copy, const N: usize>(
+        &mut self,
+        vals: &[T],
+        to_bytes: impl Fn(T) -> [u8; N],
+    ) -> PacketResult<()> {
+        let len = i32::try_from(vals.len()).expect("array is too long");
+        self.write_i32(len).await?;
+
+        for val in vals {
+            self.0.write_all(&to_bytes(*val)).await?;
+        }
+
+        Ok(())
+    }
+
+    write_primitives!(i64 i32 i16 i8 f32 f64);
+
+    pub async fn begin_named_compound(mut self, name: &str) -> PacketResult<NbtCompoundBuilder> {
+        self.write_tag_raw(NbtTag::Compound).await?;
+        self.write_string_raw(name).await?;
+        Ok(NbtCompoundBuilder(self))
+    }
+
+    pub async fn begin_unnamed_compound(mut self) -> PacketResult<NbtCompoundBuilder> {
+        self.write_tag_raw(NbtTag::Compound).await?;
+        Ok(NbtCompoundBuilder(self))
+    }
+}
+
+impl NbtParent for NbtBuilder {
+    fn builder(&mut self) -> &mut NbtBuilder {
+        self
+    }
+}
+
+impl<P: NbtParent> NbtParent for NbtCompoundBuilder<P> {
+    fn builder(&mut self) -> &mut NbtBuilder {
+        self.0.builder()
+    }
+}
+
+impl<P: NbtParent> NbtParent for NbtListBuilder<P> {
+    fn builder(&mut self) -> &mut NbtBuilder {
+        self.parent.builder()
+    }
+}
+
+impl<P: NbtParent> NbtCompoundBuilder<P> {
+    pub async fn begin_named_tag(self, name: &str) -> PacketResult<NbtTagBuilder<'_, P>> {
+        Ok(NbtTagBuilder(self, Some(name)))
+    }
+
+    pub async fn begin_unnamed_tag<'a>(self) -> PacketResult<NbtTagBuilder<'a, P>> {
+        Ok(NbtTagBuilder(self, None))
+    }
+
+    pub async fn end(mut self) -> PacketResult<P> {
+        self.builder().write_tag_raw(NbtTag::End).await?;
+        Ok(self.0)
+    }
+}
+
+impl<P: NbtParent> NbtTagBuilder<'_, P> {
+    async fn write_tag(&mut self, tag: NbtTag) -> PacketResult<()> {
+        let name = self.1;
+        let builder = self.0.builder();
+        builder.write_tag_raw(tag).await?;
+        if let Some(name) = name {
+            builder.write_string_raw(name).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn string(mut self, val: &str) -> PacketResult<NbtCompoundBuilder<P>> {
+        self.write_tag(NbtTag::String).await?;
+        self.0.builder().write_string_raw(val).await?;
+        Ok(self.0)
+    }
+
+    /// Writes an already built tree
+    pub async fn value(mut self, val: &NbtValue) -> PacketResult<NbtCompoundBuilder<P>> {
+        self.write_tag(val.tag()).await?;
+
+        let mut bytes = Vec::new();
+        value::write_payload(&mut bytes, val);
+        self.0.builder().0.write_all(&bytes).await?;
+        Ok(self.0)
+    }
+
+    /// Must be ended to return to this compound
+    pub async fn begin_compound(
+        mut self,
+    ) -> PacketResult<NbtCompoundBuilder<NbtCompoundBuilder<P>>> {
+        self.write_tag(NbtTag::Compound).await?;
+        Ok(NbtCompoundBuilder(self.0))
+    }
+
+    /// Must be ended to return to this compound
+    pub async fn begin_list(mut self) -> PacketResult<NbtListBuilder<NbtCompoundBuilder<P>>> {
+        self.write_tag(NbtTag::List).await?;
+        NbtListBuilder::begin(self.0).await
+    }
+
+    nbt_primitive!(NbtTag::Long, long, i64);
+    nbt_primitive!(NbtTag::Int, int, i32);
+    nbt_primitive!(NbtTag::Short, short, i16);
+    nbt_primitive!(NbtTag::Byte, byte, i8);
+    nbt_primitive!(NbtTag::Double, double, f64);
+    nbt_primitive!(NbtTag::Float, float, f32);
+
+    nbt_array!(NbtTag::ByteArray, byte_array, i8);
+    nbt_array!(NbtTag::IntArray, int_array, i32);
+    nbt_array!(NbtTag::LongArray, long_array, i64);
+}
+
+impl<P: NbtParent> NbtListBuilder<P> {
+    async fn begin(mut parent: P) -> PacketResult<Self> {
+        let builder = parent.builder();
+        let header = builder.0.position() as usize;
+
+        // placeholders until the list is ended
+        builder.write_tag_raw(NbtTag::End).await?;
+        builder.write_i32(0).await?;
+
+        Ok(Self {
+            parent,
+            header,
+            tag: NbtTag::End,
+            count: 0,
+        })
+    }
+
+    fn add_element(&mut self, tag: NbtTag) -> PacketResult<()> {
+        if self.tag == NbtTag::End {
+            self.tag = tag;
+        } else if self.tag != tag {
+            return Err(PacketError::NbtListType {
+                expected: self.tag,
+                actual: tag,
+            });
+        }
+
+        self.count = self.count.checked_add(1).expect("list is too long");
+        Ok(())
+    }
+
+    pub async fn string(mut self, val: &str) -> PacketResult<Self> {
+        self.add_element(NbtTag::String)?;
+        self.parent.builder().write_string_raw(val).await?;
+        Ok(self)
+    }
+
+    /// Writes an already built tree
+    pub async fn value(mut self, val: &NbtValue) -> PacketResult<Self> {
+        self.add_element(val.tag())?;
+
+        let mut bytes = Vec::new();
+        value::write_payload(&mut bytes, val);
+        self.parent.builder().0.write_all(&bytes).await?;
+        Ok(self)
+    }
+
+    /// Must be ended to return to this list
+    pub async fn begin_compound(mut self) -> PacketResult<NbtCompoundBuilder<Self>> {
+        self.add_element(NbtTag::Compound)?;
+        Ok(NbtCompoundBuilder(self))
+    }
+
+    /// Must be ended to return to this list
+    pub async fn begin_list(mut self) -> PacketResult<NbtListBuilder<Self>> {
+        self.add_element(NbtTag::List)?;
+        NbtListBuilder::begin(self).await
+    }
+
+    pub async fn end(mut self) -> PacketResult<P> {
+        let header = self.header;
+        let mut bytes = [0u8; 5];
+        bytes[0] = self.tag as u8;
+        bytes[1..].copy_from_slice(&self.count.to_be_bytes());
+
+        let storage = self.parent.builder().0.get_mut();
+        storage[header..header + bytes.len()].copy_from_slice(&bytes);
+        Ok(self.parent)
+    }
+
+    list_primitive!(NbtTag::Long, long, i64);
+    list_primitive!(NbtTag::Int, int, i32);
+    list_primitive!(NbtTag::Short, short, i16);
+    list_primitive!(NbtTag::Byte, byte, i8);
+    list_primitive!(NbtTag::Double, double, f64);
+    list_primitive!(NbtTag::Float, float, f32);
+
+    list_array!(NbtTag::ByteArray, byte_array, i8);
+    list_array!(NbtTag::IntArray, int_array, i32);
+    list_array!(NbtTag::LongArray, long_array, i64);
+}
+
+impl<'r, R: Read + Unpin + Send> NbtReader<'r, R> {
+    fn new(r: &'r mut R) -> Self {
+        Self {
+            r,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Reads a full root tag: type, name and payload. TAG_End is not a valid root
+    async fn read_root(self) -> PacketResult<Vec<u8>> {
+        self.read_optional_root()
+            .await?
+            .ok_or(PacketError::UnexpectedNbtEnd)
+    }
+
+    /// Reads a full root tag, or None for a lone TAG_End
+    async fn read_optional_root(mut self) -> PacketResult<Option<Vec<u8>>> {
+        let tag = self.read_tag().await?;
+        if tag == NbtTag::End {
+            return Ok(None);
+        }
+
+        self.read_string().await?;
+        self.read_payload(tag).await?;
+        Ok(Some(self.bytes))
+    }
+
+    async fn read_exact<const N: usize>(&mut self) -> PacketResult<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.r.read_exact(&mut buf).await?;
+        self.bytes.extend_from_slice(&buf);
+        Ok(buf)
+    }
+
+    /// Reads n bytes without allocating them all up front, as n comes from the stream
+    async fn read_n(&mut self, n: usize) -> PacketResult<&[u8]> {
+        let start = self.bytes.len();
+        let read = (&mut *self.r)
+            .take(n as u64)
+            .read_to_end(&mut self.bytes)
+            .await?;
+
+        if read != n {
+            return Err(PacketError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        Ok(&self.bytes[start..])
+    }
+
+    async fn read_tag(&mut self) -> PacketResult<NbtTag> {
+        let [b] = self.read_exact().await?;
+        NbtTag::try_from(b)
+    }
+
+    async fn read_length(&mut self) -> PacketResult<usize> {
+        let len = i32::from_be_bytes(self.read_exact().await?);
+        usize::try_from(len).map_err(|_| PacketError::BadNbtLength(len))
+    }
+
+    async fn read_string(&mut self) -> PacketResult<()> {
+        let len = u16::from_be_bytes(self.read_exact().await?);
+        let bytes = self.read_n(len as usize).await?;
+        mstr::from_mutf8(bytes)
+            .to_utf8()
+            .map_err(PacketError::BadNbtString)?;
+        Ok(())
+    }
+
+    /// Nested compounds and lists are tracked on an explicit stack rather than by recursion
+    async fn read_payload(&mut self, tag: NbtTag) -> PacketResult<()> {
+        let mut stack = Vec::new();
+        let mut next = Some(tag);
+
+        loop {
+            if let Some(tag) = next.take() {
+                match tag {
+                    NbtTag::End => unreachable!("TAG_End has no payload"),
+                    NbtTag::Byte => {
+                        self.read_exact::<1>().await?;
+                    }
+                    NbtTag::Short => {
+                        self.read_exact::<2>().await?;
+                    }
+                    NbtTag::Int | NbtTag::Float => {
+                        self.read_exact::<4>().await?;
+                    }
+                    NbtTag::Long | NbtTag::Double => {
+                        self.read_exact::<8>().await?;
+                    }
+                    NbtTag::ByteArray => {
+                        let len = self.read_length().await?;
+                        self.read_n(len).await?;
+                    }
+                    NbtTag::IntArray => {
+                        let len = self.read_length().await?;
+                        self.read_n(len.saturating_mul(4)).await?;
+                    }
+                    NbtTag::LongArray => {
+                        let len = self.read_length().await?;
+                        self.read_n(len.saturating_mul(8)).await?;
+                    }
+                    NbtTag::String => self.read_string().await?,
+                    NbtTag::List => {
+                        let tag = self.read_tag().await?;
+                        let len = self.read_length().await?;
+                        if tag == NbtTag::End && len != 0 {
+                            return Err(PacketError::BadNbtTag(tag as u8));
+                        }
+
+                        stack.push(NbtFrame::List {
+                            tag,
+                            remaining: len as i32,
+                        });
+                    }
+                    NbtTag::Compound => stack.push(NbtFrame::Compound),
+                }
+
+                if stack.len() > MAX_DEPTH {
+                    return Err(PacketError::NbtTooDeep(MAX_DEPTH));
+                }
+            }
+
+            match stack.last_mut() {
+                None => return Ok(()),
+                Some(NbtFrame::Compound) => {
+                    let tag = self.read_tag().await?;
+                    if tag == NbtTag::End {
+                        stack.pop();
+                    } else {
+                        self.read_string().await?;
+                        next = Some(tag);
+                    }
+                }
+                Some(NbtFrame::List { remaining: 0, .. }) => {
+                    stack.pop();
+                }
+                Some(NbtFrame::List { tag, remaining }) => {
+                    *remaining -= 1;
+                    next = Some(*tag);
+                }
+            }
+        }
+    }
+}
+
+impl Display for NbtField {
+    /// SNBT of the root value, or a hex dump if it can't be decoded
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.to_value() {
+            Ok((_, value)) => write!(f, "{}", value),
+            Err(_) => write!(f, "{:x?}", self.bytes),
+        }
+    }
+}
+
+#[async_trait]
+impl Field for NbtField {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let bytes = NbtReader::new(r).read_root().await?;
+        Ok(Self { bytes })
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        w.write_all(&self.bytes).await?;
+        Ok(())
+    }
+}
+
+/// NBT that may be absent, sent as a lone TAG_End
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OptionalNbtField(Option<NbtField>);
+
+impl OptionalNbtField {
+    pub fn new(nbt: Option<NbtField>) -> Self {
+        Self(nbt)
+    }
+
+    pub fn get(&self) -> Option<&NbtField> {
+        self.0.as_ref()
+    }
+
+    pub fn take(self) -> Option<NbtField> {
+        self.0
+    }
+}
+
+impl From<Option<NbtField>> for OptionalNbtField {
+    fn from(nbt: Option<NbtField>) -> Self {
+        Self(nbt)
+    }
+}
+
+impl From<NbtField> for OptionalNbtField {
+    fn from(nbt: NbtField) -> Self {
+        Self(Some(nbt))
+    }
+}
+
+impl Display for OptionalNbtField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(nbt) => Display::fmt(nbt, f),
+            None => f.write_str("none"),
+        }
+    }
+}
+
+#[async_trait]
+impl Field for OptionalNbtField {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        self.0.as_ref().map_or(1, NbtField::size)
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let bytes = NbtReader::new(r).read_optional_root().await?;
+        Ok(Self(bytes.map(|bytes| NbtField { bytes })))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        match &self.0 {
+            Some(nbt) => nbt.write_field(w).await,
+            None => {
+                w.write_all(&[NbtTag::End as u8]).await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_await_test::async_test;
+    use std::iter::FromIterator;
+
+    #[async_test]
+    async fn nbt_hello_world() -> PacketResult<()> {
+        let nbt = NbtBuilder::default()
+            .begin_named_compound("hello world")
+            .await?
+            .begin_named_tag("name")
+            .await?
+            .string("Bananrama")
+            .await?
+            .end()
+            .await?;
+
+        assert_eq!(
+            nbt.as_bytes(),
+            b"\x0a\x00\x0b\x68\x65\x6c\x6c\x6f\x20\x77\x6f\x72\x6c\x64\x08\x00\x04\x6e\x61\x6d\x65\
+            \x00\x09\x42\x61\x6e\x61\x6e\x72\x61\x6d\x61\x00"
+        );
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_read_round_trip() -> PacketResult<()> {
+        let nbt = NbtBuilder::default()
+            .begin_named_compound("hello world")
+            .await?
+            .begin_named_tag("name")
+            .await?
+            .string("Bananrama")
+            .await?
+            .begin_named_tag("heightmap")
+            .await?
+            .long_array(&[1, -2, i64::MAX])
+            .await?
+            .end()
+            .await?
+            .into_field();
+
+        // trailing byte should be left unread
+        let mut bytes = nbt.bytes.clone();
+        bytes.push(0xff);
+        let mut cursor = Cursor::new(bytes);
+
+        let read = NbtField::read_field(&mut cursor).await?;
+        assert_eq!(read.bytes, nbt.bytes);
+        assert_eq!(cursor.position() as usize, nbt.size());
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_read_nested() -> PacketResult<()> {
+        // compound "" { list "l" of 2 compounds { int "i" }, { byte array "b" [1, 2] } }
+        let bytes: &[u8] = b"\x0a\x00\x00\
+            \x09\x00\x01l\x0a\x00\x00\x00\x02\
+                \x03\x00\x01i\x00\x00\x00\x05\x00\
+                \x07\x00\x01b\x00\x00\x00\x02\x01\x02\x00\
+            \x0b\x00\x02ia\x00\x00\x00\x01\x00\x00\x00\x03\
+            \x09\x00\x01e\x00\x00\x00\x00\x00\
+            \x00";
+
+        let mut cursor = Cursor::new(bytes.to_vec());
+        let read = NbtField::read_field(&mut cursor).await?;
+        assert_eq!(read.bytes, bytes);
+
+        let mut out = Cursor::new(Vec::new());
+        read.write_field(&mut out).await?;
+        assert_eq!(out.into_inner(), bytes);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_read_invalid() {
+        async fn read(bytes: &[u8]) -> PacketResult<NbtField> {
+            NbtField::read_field(&mut Cursor::new(bytes.to_vec())).await
+        }
+
+        assert!(matches!(
+            read(b"\x00").await,
+            Err(PacketError::UnexpectedNbtEnd)
+        ));
+        assert!(matches!(
+            read(b"\x0d\x00\x00").await,
+            Err(PacketError::BadNbtTag(13))
+        ));
+        assert!(matches!(
+            read(b"\x07\x00\x00\xff\xff\xff\xff").await,
+            Err(PacketError::BadNbtLength(-1))
+        ));
+        assert!(matches!(
+            read(b"\x09\x00\x00\x00\x00\x00\x00\x01").await,
+            Err(PacketError::BadNbtTag(0))
+        ));
+        assert!(matches!(
+            read(b"\x0a\x00\x00\x01\x00\x01").await,
+            Err(PacketError::Io(_))
+        ));
+
+        // lists of lists nested past the limit
+        let mut deep = b"\x09\x00\x00".to_vec();
+        for _ in 0..=MAX_DEPTH {
+            deep.extend_from_slice(b"\x09\x00\x00\x00\x01");
+        }
+        assert!(matches!(
+            read(&deep).await,
+            Err(PacketError::NbtTooDeep(MAX_DEPTH))
+        ));
+    }
+
+    #[async_test]
+    async fn optional_nbt() -> PacketResult<()> {
+        let mut cursor = Cursor::new(Vec::new());
+        let absent = OptionalNbtField::default();
+        absent.write_field(&mut cursor).await?;
+        assert_eq!(cursor.get_ref().as_slice(), b"\x00");
+        assert_eq!(absent.size(), 1);
+
+        let present = OptionalNbtField::from(NbtField::from_snbt("{Text1:'\"hi\"'}")?);
+        present.write_field(&mut cursor).await?;
+        assert_eq!(cursor.position() as usize, 1 + present.size());
+
+        cursor.set_position(0);
+        assert!(OptionalNbtField::read_field(&mut cursor)
+            .await?
+            .get()
+            .is_none());
+        let read = OptionalNbtField::read_field(&mut cursor).await?;
+        assert_eq!(read.to_string(), "{Text1:'\"hi\"'}");
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_builder_nested() -> PacketResult<()> {
+        let nbt = NbtBuilder::default()
+            .begin_named_compound("")
+            .await?
+            .begin_named_tag("dimension")
+            .await?
+            .begin_compound()
+            .await?
+            .begin_named_tag("type")
+            .await?
+            .string("minecraft:dimension_type")
+            .await?
+            .begin_named_tag("value")
+            .await?
+            .begin_list()
+            .await?
+            .begin_compound()
+            .await?
+            .begin_named_tag("id")
+            .await?
+            .int(0)
+            .await?
+            .end()
+            .await?
+            .begin_compound()
+            .await?
+            .begin_named_tag("heights")
+            .await?
+            .int_array(&[1, 2])
+            .await?
+            .end()
+            .await?
+            .end()
+            .await?
+            .end()
+            .await?
+            .begin_named_tag("lore")
+            .await?
+            .begin_list()
+            .await?
+            .string("\0 null")
+            .await?
+            .string("é")
+            .await?
+            .end()
+            .await?
+            .begin_named_tag("nested")
+            .await?
+            .begin_list()
+            .await?
+            .begin_list()
+            .await?
+            .byte(1)
+            .await?
+            .end()
+            .await?
+            .begin_list()
+            .await?
+            .end()
+            .await?
+            .end()
+            .await?
+            .begin_named_tag("bytes")
+            .await?
+            .byte_array(&[-1, 2])
+            .await?
+            .end()
+            .await?
+            .into_field();
+
+        let mut dimension = NbtCompound::new();
+        dimension.insert("type", "minecraft:dimension_type");
+        dimension.insert(
+            "value",
+            NbtList::try_from(vec![
+                NbtCompound::from_iter(vec![("id", 0)]),
+                NbtCompound::from_iter(vec![("heights", vec![1i32, 2])]),
+            ])?,
+        );
+
+        let mut expected = NbtCompound::new();
+        expected.insert("dimension", dimension);
+        expected.insert("lore", NbtList::try_from(vec!["\0 null", "é"])?);
+        expected.insert(
+            "nested",
+            NbtList::try_from(vec![NbtList::try_from(vec![1i8])?, NbtList::new()])?,
+        );
+        expected.insert("bytes", vec![-1i8, 2]);
+
+        assert_eq!(nbt.bytes, NbtField::from(&expected).bytes);
+
+        // and it should be readable
+        let read = NbtField::read_field(&mut Cursor::new(nbt.bytes.clone())).await?;
+        assert_eq!(read.bytes, nbt.bytes);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn nbt_builder_list_type() -> PacketResult<()> {
+        let list = NbtBuilder::default()
+            .begin_unnamed_compound()
+            .await?
+            .begin_named_tag("list")
+            .await?
+            .begin_list()
+            .await?
+            .int(5)
+            .await?;
+
+        assert!(matches!(
+            list.long(5).await,
+            Err(PacketError::NbtListType {
+                expected: NbtTag::Int,
+                actual: NbtTag::Long
+            })
+        ));
+        Ok(())
+    }
+}
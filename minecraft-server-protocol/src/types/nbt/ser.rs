@@ -0,0 +1,584 @@
+//! serde data format producing an [NbtValue] tree.
+//!
+//! Sequences become lists, and only [NbtByteArray], [NbtIntArray] and [NbtLongArray] (or bytes)
+//! become arrays. `None` struct fields and map values are left out, as NBT has no null. Unsigned
+//! integers are stored in the signed tag of the same width.
+
+use crate::types::nbt::{NbtCompound, NbtList, NbtTag, NbtValue};
+use crate::types::{NbtField, PacketError, PacketResult};
+use serde::ser;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::Display;
+
+/// Serialized as TAG_Byte_Array rather than a list
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct NbtByteArray(pub Vec<i8>);
+
+/// Serialized as TAG_Int_Array rather than a list
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct NbtIntArray(pub Vec<i32>);
+
+/// Serialized as TAG_Long_Array rather than a list
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct NbtLongArray(pub Vec<i64>);
+
+impl ser::Error for PacketError {
+    fn custom<T: Display>(msg: T) -> Self {
+        PacketError::NbtSerde(msg.to_string())
+    }
+}
+
+impl NbtValue {
+    pub fn serialize_from<T: Serialize + ?Sized>(value: &T) -> PacketResult<Self> {
+        value
+            .serialize(Serializer)?
+            .ok_or_else(|| PacketError::NbtSerde("root value is missing".to_owned()))
+    }
+}
+
+impl NbtField {
+    /// Serializes as a root tag with an empty name
+    pub fn serialize_from<T: Serialize + ?Sized>(value: &T) -> PacketResult<Self> {
+        NbtValue::serialize_from(value).map(|value| NbtField::from_value("", &value))
+    }
+}
+
+/// Produces `None` for values that should be left out of the parent
+struct Serializer;
+
+struct SerializeSeq {
+    values: Vec<NbtValue>,
+    /// Wraps the list in a compound keyed by variant name
+    variant: Option<&'static str>,
+}
+
+struct SerializeCompound {
+    compound: NbtCompound,
+    next_key: Option<String>,
+    /// Wraps the compound in another keyed by variant name
+    variant: Option<&'static str>,
+}
+
+struct KeySerializer;
+
+fn present(value: impl Into<NbtValue>) -> PacketResult<Option<NbtValue>> {
+    Ok(Some(value.into()))
+}
+
+fn wrap_variant(variant: Option<&'static str>, value: NbtValue) -> NbtValue {
+    match variant {
+        Some(variant) => NbtValue::Compound(std::iter::once((variant, value)).collect()),
+        None => value,
+    }
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeSeq;
+    type SerializeTupleStruct = SerializeSeq;
+    type SerializeTupleVariant = SerializeSeq;
+    type SerializeMap = SerializeCompound;
+    type SerializeStruct = SerializeCompound;
+    type SerializeStructVariant = SerializeCompound;
+
+    fn serialize_bool(self, v: bool) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> PacketResult<Self::Ok> {
+        present(v as i8)
+    }
+
+    fn serialize_u16(self, v: u16) -> PacketResult<Self::Ok> {
+        present(v as i16)
+    }
+
+    fn serialize_u32(self, v: u32) -> PacketResult<Self::Ok> {
+        present(v as i32)
+    }
+
+    fn serialize_u64(self, v: u64) -> PacketResult<Self::Ok> {
+        present(v as i64)
+    }
+
+    fn serialize_f32(self, v: f32) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_char(self, v: char) -> PacketResult<Self::Ok> {
+        present(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> PacketResult<Self::Ok> {
+        present(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> PacketResult<Self::Ok> {
+        present(v.iter().map(|b| *b as i8).collect::<Vec<_>>())
+    }
+
+    fn serialize_none(self) -> PacketResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> PacketResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> PacketResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> PacketResult<Self::Ok> {
+        present(NbtCompound::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> PacketResult<Self::Ok> {
+        present(variant)
+    }
+
+    /// The array newtypes are recognised by name
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> PacketResult<Self::Ok> {
+        let array = match name {
+            "NbtByteArray" => NbtTag::ByteArray,
+            "NbtIntArray" => NbtTag::IntArray,
+            "NbtLongArray" => NbtTag::LongArray,
+            _ => return value.serialize(self),
+        };
+
+        match value.serialize(self)? {
+            Some(value) => into_array(array, value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> PacketResult<Self::Ok> {
+        Ok(value
+            .serialize(self)?
+            .map(|value| wrap_variant(Some(variant), value)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> PacketResult<Self::SerializeSeq> {
+        Ok(SerializeSeq {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> PacketResult<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> PacketResult<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> PacketResult<Self::SerializeTupleVariant> {
+        Ok(SerializeSeq {
+            values: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> PacketResult<Self::SerializeMap> {
+        Ok(SerializeCompound {
+            compound: NbtCompound::new(),
+            next_key: None,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> PacketResult<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> PacketResult<Self::SerializeStructVariant> {
+        Ok(SerializeCompound {
+            compound: NbtCompound::new(),
+            next_key: None,
+            variant: Some(variant),
+        })
+    }
+}
+
+impl SerializeSeq {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        let value = value.serialize(Serializer)?.ok_or_else(|| {
+            PacketError::NbtSerde("NBT lists can't hold missing values".to_owned())
+        })?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> PacketResult<Option<NbtValue>> {
+        let list = NbtList::try_from(self.values)?;
+        Ok(Some(wrap_variant(self.variant, NbtValue::List(list))))
+    }
+}
+
+/// Converts a serialized sequence to the given array type
+fn into_array(array: NbtTag, value: NbtValue) -> PacketResult<NbtValue> {
+    fn collect<T>(value: &NbtValue, f: impl Fn(&NbtValue) -> Option<T>) -> Option<Vec<T>> {
+        value.as_list()?.iter().map(f).collect()
+    }
+
+    // already an array if serialized as bytes
+    if value.tag() == array {
+        return Ok(value);
+    }
+
+    let converted = match array {
+        NbtTag::ByteArray => collect(&value, NbtValue::as_byte).map(NbtValue::ByteArray),
+        NbtTag::IntArray => collect(&value, NbtValue::as_int).map(NbtValue::IntArray),
+        NbtTag::LongArray => collect(&value, NbtValue::as_long).map(NbtValue::LongArray),
+        _ => None,
+    };
+
+    converted.ok_or_else(|| PacketError::NbtSerde(format!("{:?} can't hold {}", array, value)))
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeSeq {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeSeq {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl SerializeCompound {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> PacketResult<()> {
+        if let Some(value) = value.serialize(Serializer)? {
+            self.compound.insert(key, value);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> PacketResult<Option<NbtValue>> {
+        Ok(Some(wrap_variant(
+            self.variant,
+            NbtValue::Compound(self.compound),
+        )))
+    }
+}
+
+impl ser::SerializeMap for SerializeCompound {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> PacketResult<()> {
+        self.next_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> PacketResult<()> {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.insert(key, value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeCompound {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> PacketResult<()> {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeCompound {
+    type Ok = Option<NbtValue>;
+    type Error = PacketError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> PacketResult<()> {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> PacketResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+/// Compound keys must be strings, but integer keys are stringified like in JSON
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = PacketError;
+    type SerializeSeq = ser::Impossible<String, PacketError>;
+    type SerializeTuple = ser::Impossible<String, PacketError>;
+    type SerializeTupleStruct = ser::Impossible<String, PacketError>;
+    type SerializeTupleVariant = ser::Impossible<String, PacketError>;
+    type SerializeMap = ser::Impossible<String, PacketError>;
+    type SerializeStruct = ser::Impossible<String, PacketError>;
+    type SerializeStructVariant = ser::Impossible<String, PacketError>;
+
+    fn serialize_bool(self, _v: bool) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_i8(self, v: i8) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_char(self, v: char) -> PacketResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> PacketResult<String> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> PacketResult<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> PacketResult<String> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> PacketResult<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> PacketResult<String> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> PacketResult<Self::SerializeSeq> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> PacketResult<Self::SerializeTuple> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> PacketResult<Self::SerializeTupleStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> PacketResult<Self::SerializeTupleVariant> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> PacketResult<Self::SerializeMap> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> PacketResult<Self::SerializeStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> PacketResult<Self::SerializeStructVariant> {
+        Err(key_error())
+    }
+}
+
+fn key_error() -> PacketError {
+    PacketError::NbtSerde("NBT compound keys must be strings".to_owned())
+}
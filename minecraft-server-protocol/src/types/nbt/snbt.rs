@@ -0,0 +1,563 @@
+//! Stringified NBT, as used in commands like `/give @p stone{display:{Name:'"x"'}} 3`.
+//!
+//! Printing follows vanilla: no whitespace, strings always quoted, and numbers suffixed with their
+//! type apart from ints. Parsing is as lenient as vanilla, so unquoted words that aren't numbers or
+//! `true`/`false` are strings, and out of range numbers are strings too. Non-finite floats print
+//! as `NaNf`, `inff` and `-inff` (or with `d`), which are read back as floats.
+
+use crate::types::nbt::{NbtCompound, NbtList, NbtTag, NbtValue, MAX_DEPTH};
+use crate::types::{NbtField, PacketError, PacketResult};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+impl NbtField {
+    /// Parses SNBT as a root tag with an empty name
+    pub fn from_snbt(snbt: &str) -> PacketResult<Self> {
+        let value = snbt.parse::<NbtValue>()?;
+        Ok(NbtField::from_value("", &value))
+    }
+}
+
+impl FromStr for NbtValue {
+    type Err = PacketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = SnbtParser { s, pos: 0 };
+        let value = parser.read_value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != s.len() {
+            return Err(parser.error("trailing data"));
+        }
+
+        Ok(value)
+    }
+}
+
+impl FromStr for NbtCompound {
+    type Err = PacketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            NbtValue::Compound(compound) => Ok(compound),
+            other => Err(PacketError::UnexpectedNbtTag {
+                expected: NbtTag::Compound,
+                actual: other.tag(),
+            }),
+        }
+    }
+}
+
+impl Display for NbtValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NbtValue::Byte(v) => write!(f, "{}b", v),
+            NbtValue::Short(v) => write!(f, "{}s", v),
+            NbtValue::Int(v) => write!(f, "{}", v),
+            NbtValue::Long(v) => write!(f, "{}L", v),
+            NbtValue::Float(v) => write!(f, "{}f", v),
+            NbtValue::Double(v) => write!(f, "{}d", v),
+            NbtValue::ByteArray(v) => write_array(f, "B", v, "b"),
+            NbtValue::String(v) => write_quoted(f, v),
+            NbtValue::List(v) => v.fmt(f),
+            NbtValue::Compound(v) => v.fmt(f),
+            NbtValue::IntArray(v) => write_array(f, "I", v, ""),
+            NbtValue::LongArray(v) => write_array(f, "L", v, "L"),
+        }
+    }
+}
+
+impl Display for NbtCompound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char('{')?;
+        for (i, (name, value)) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_char(',')?;
+            }
+
+            if !name.is_empty() && name.chars().all(is_unquoted_char) {
+                f.write_str(name)?;
+            } else {
+                write_quoted(f, name)?;
+            }
+            write!(f, ":{}", value)?;
+        }
+        f.write_char('}')
+    }
+}
+
+impl Display for NbtList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char('[')?;
+        for (i, value) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_char(',')?;
+            }
+            write!(f, "{}", value)?;
+        }
+        f.write_char(']')
+    }
+}
+
+fn write_array<T: Display>(
+    f: &mut Formatter<'_>,
+    prefix: &str,
+    values: &[T],
+    suffix: &str,
+) -> std::fmt::Result {
+    write!(f, "[{};", prefix)?;
+    for (i, value) in values.iter().enumerate() {
+        if i != 0 {
+            f.write_char(',')?;
+        }
+        write!(f, "{}{}", value, suffix)?;
+    }
+    f.write_char(']')
+}
+
+/// Prefers double quotes unless the string contains them and no single quotes
+fn write_quoted(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    let quote = if s.contains('"') && !s.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+
+    f.write_char(quote)?;
+    for c in s.chars() {
+        if c == quote || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char(quote)
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+struct SnbtParser<'a> {
+    s: &'a str,
+    /// Byte offset into `s`
+    pos: usize,
+}
+
+impl<'a> SnbtParser<'a> {
+    fn error(&self, reason: &'static str) -> PacketError {
+        PacketError::BadSnbt {
+            pos: self.pos,
+            reason,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.s[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace then consumes `c` if it's next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char, reason: &'static str) -> PacketResult<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(reason))
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> PacketResult<NbtValue> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.read_compound(depth + 1).map(NbtValue::Compound),
+            Some('[') => self.read_list_or_array(depth + 1),
+            Some('"') | Some('\'') => self.read_quoted().map(NbtValue::String),
+            Some(_) => {
+                let start = self.pos;
+                let word = self.read_unquoted();
+                if word.is_empty() {
+                    self.pos = start;
+                    Err(self.error("expected a value"))
+                } else {
+                    Ok(parse_unquoted(word))
+                }
+            }
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn read_compound(&mut self, depth: usize) -> PacketResult<NbtCompound> {
+        if depth > MAX_DEPTH {
+            return Err(PacketError::NbtTooDeep(MAX_DEPTH));
+        }
+
+        self.expect('{', "expected '{'")?;
+        let mut compound = NbtCompound::new();
+        if self.eat('}') {
+            return Ok(compound);
+        }
+
+        loop {
+            let name = self.read_key()?;
+            self.expect(':', "expected ':' after key")?;
+            let value = self.read_value(depth)?;
+            compound.insert(name, value);
+
+            if !self.eat(',') {
+                self.expect('}', "expected ',' or '}'")?;
+                return Ok(compound);
+            }
+        }
+    }
+
+    fn read_list_or_array(&mut self, depth: usize) -> PacketResult<NbtValue> {
+        if depth > MAX_DEPTH {
+            return Err(PacketError::NbtTooDeep(MAX_DEPTH));
+        }
+
+        self.expect('[', "expected '['")?;
+        let rest = &self.s[self.pos..];
+        let array_tag = match rest.get(..2) {
+            Some("B;") => Some(NbtTag::Byte),
+            Some("I;") => Some(NbtTag::Int),
+            Some("L;") => Some(NbtTag::Long),
+            _ => None,
+        };
+
+        let mut values = Vec::new();
+        if let Some(tag) = array_tag {
+            self.pos += 2;
+            if !self.eat(']') {
+                loop {
+                    let start = self.pos;
+                    let value = self.read_value(depth)?;
+                    if value.tag() != tag {
+                        self.pos = start;
+                        return Err(self.error("array element has the wrong type"));
+                    }
+                    values.push(value);
+
+                    if !self.eat(',') {
+                        self.expect(']', "expected ',' or ']'")?;
+                        break;
+                    }
+                }
+            }
+
+            let values = values.into_iter();
+            return Ok(match tag {
+                NbtTag::Byte => NbtValue::ByteArray(values.filter_map(|v| v.as_byte()).collect()),
+                NbtTag::Int => NbtValue::IntArray(values.filter_map(|v| v.as_int()).collect()),
+                _ => NbtValue::LongArray(values.filter_map(|v| v.as_long()).collect()),
+            });
+        }
+
+        if !self.eat(']') {
+            loop {
+                let start = self.pos;
+                let value = self.read_value(depth)?;
+                if values.first().is_some_and(|v| v.tag() != value.tag()) {
+                    self.pos = start;
+                    return Err(self.error("list elements must all have the same type"));
+                }
+                values.push(value);
+
+                if !self.eat(',') {
+                    self.expect(']', "expected ',' or ']'")?;
+                    break;
+                }
+            }
+        }
+
+        // types are already checked
+        NbtList::try_from(values).map(NbtValue::List)
+    }
+
+    fn read_key(&mut self) -> PacketResult<String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') | Some('\'') => self.read_quoted(),
+            _ => {
+                let key = self.read_unquoted();
+                if key.is_empty() {
+                    Err(self.error("expected a key"))
+                } else {
+                    Ok(key.to_owned())
+                }
+            }
+        }
+    }
+
+    fn read_unquoted(&mut self) -> &'a str {
+        let rest = &self.s[self.pos..];
+        let len = rest.find(|c| !is_unquoted_char(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn read_quoted(&mut self) -> PacketResult<String> {
+        let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+        self.pos += 1;
+
+        let mut out = String::new();
+        let mut chars = self.s[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, escaped)) if escaped == quote || escaped == '\\' => out.push(escaped),
+                    _ => {
+                        self.pos += i;
+                        return Err(self.error("invalid escape sequence"));
+                    }
+                },
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                c => out.push(c),
+            }
+        }
+
+        self.pos = self.s.len();
+        Err(self.error("unterminated string"))
+    }
+}
+
+/// Numbers by their suffix, booleans as bytes, anything else is a string
+fn parse_unquoted(word: &str) -> NbtValue {
+    match word {
+        "true" => return NbtValue::Byte(1),
+        "false" => return NbtValue::Byte(0),
+        _ => {}
+    }
+
+    let (body, suffix) = match word.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&word[..i], Some(c.to_ascii_lowercase())),
+        _ => (word, None),
+    };
+
+    let number = match suffix {
+        Some('b') if is_integer(body) => body.parse().ok().map(NbtValue::Byte),
+        Some('s') if is_integer(body) => body.parse().ok().map(NbtValue::Short),
+        Some('l') if is_integer(body) => body.parse().ok().map(NbtValue::Long),
+        Some('f') if is_decimal(body, false) || is_non_finite(body) => {
+            body.parse().ok().map(NbtValue::Float)
+        }
+        Some('d') if is_decimal(body, false) || is_non_finite(body) => {
+            body.parse().ok().map(NbtValue::Double)
+        }
+        None if is_integer(body) => body.parse().ok().map(NbtValue::Int),
+        None if is_decimal(body, true) => body.parse().ok().map(NbtValue::Double),
+        _ => None,
+    };
+
+    number.unwrap_or_else(|| NbtValue::String(word.to_owned()))
+}
+
+fn strip_sign(s: &str) -> &str {
+    s.strip_prefix(['-', '+']).unwrap_or(s)
+}
+
+/// As printed for non-finite floats
+fn is_non_finite(s: &str) -> bool {
+    matches!(s, "NaN" | "inf" | "-inf")
+}
+
+/// `[-+]?(0|[1-9][0-9]*)`
+fn is_integer(s: &str) -> bool {
+    let digits = strip_sign(s);
+    match digits.as_bytes() {
+        [b'0'] => true,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    }
+}
+
+/// `[-+]?([0-9]+[.]?|[0-9]*[.][0-9]+)(e[-+]?[0-9]+)?`, with the dot required for unsuffixed doubles
+fn is_decimal(s: &str, needs_dot: bool) -> bool {
+    let s = strip_sign(s);
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(idx) => (&s[..idx], Some(strip_sign(&s[idx + 1..]))),
+        None => (s, None),
+    };
+
+    let (whole, fraction) = match mantissa.find('.') {
+        Some(idx) => (&mantissa[..idx], Some(&mantissa[idx + 1..])),
+        None => (mantissa, None),
+    };
+
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let mantissa_ok = match fraction {
+        Some(fraction) => {
+            digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty())
+        }
+        None => !needs_dot && !whole.is_empty() && digits(whole),
+    };
+    let exponent_ok = exponent.is_none_or(|e| !e.is_empty() && digits(e));
+
+    mantissa_ok && exponent_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Field;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    #[test]
+    fn snbt_print() {
+        let display: NbtCompound = vec![("Name", NbtValue::from("\"x\""))]
+            .into_iter()
+            .collect();
+        let mut lore = NbtList::new();
+        lore.push("it's a \"stick\"").unwrap();
+
+        let mut compound = NbtCompound::new();
+        compound.insert("display", display);
+        compound.insert("Count", 3i8);
+        compound.insert("Lore", lore);
+        compound.insert("minecraft:id", 5i16);
+        compound.insert("Time", 10i64);
+        compound.insert("Scale", 0.5f32);
+        compound.insert("Pos", 1f64);
+        compound.insert("Ints", vec![1, -2]);
+        compound.insert("Longs", vec![3i64]);
+        compound.insert("Bytes", Vec::<i8>::new());
+
+        assert_eq!(
+            NbtValue::Compound(compound).to_string(),
+            r#"{display:{Name:'"x"'},Count:3b,Lore:["it's a \"stick\""],"minecraft:id":5s,Time:10L,Scale:0.5f,Pos:1d,Ints:[I;1,-2],Longs:[L;3L],Bytes:[B;]}"#
+        );
+        assert_eq!(
+            vec![("", 1), ("a b", 2)]
+                .into_iter()
+                .collect::<NbtCompound>()
+                .to_string(),
+            r#"{"":1,"a b":2}"#
+        );
+    }
+
+    #[test]
+    fn snbt_parse() -> PacketResult<()> {
+        let compound: NbtCompound =
+            r#" { display : { Name : '"x"' } , Count:3b, flag: true, word: stone, 'q"k': "a\\b",
+            list: [1.5, -2.d, .5e3], ints: [I; 1, 2], empty: [], id: 1234567890123s }"#
+                .parse()?;
+
+        assert_eq!(
+            compound.get_path("display.Name").and_then(NbtValue::as_str),
+            Some("\"x\"")
+        );
+        assert_eq!(compound.get_byte("Count"), Some(3));
+        assert_eq!(compound.get_byte("flag"), Some(1));
+        assert_eq!(compound.get_str("word"), Some("stone"));
+        assert_eq!(compound.get_str("q\"k"), Some("a\\b"));
+        assert_eq!(
+            compound
+                .get_list("list")
+                .map(|l| l.iter().cloned().collect()),
+            Some(vec![
+                NbtValue::Double(1.5),
+                NbtValue::Double(-2.0),
+                NbtValue::Double(500.0)
+            ])
+        );
+        assert_eq!(compound.get_int_array("ints"), Some(&vec![1, 2]));
+        assert_eq!(
+            compound.get_list("empty").map(NbtList::tag),
+            Some(NbtTag::End)
+        );
+        // out of range for a short
+        assert_eq!(compound.get_str("id"), Some("1234567890123s"));
+
+        for (snbt, value) in &[
+            ("1", NbtValue::Int(1)),
+            ("-0", NbtValue::Int(0)),
+            ("01", NbtValue::from("01")),
+            ("1.", NbtValue::Double(1.0)),
+            ("1f", NbtValue::Float(1.0)),
+            ("1e2D", NbtValue::Double(100.0)),
+            ("+7L", NbtValue::Long(7)),
+            ("1e2", NbtValue::from("1e2")),
+        ] {
+            assert_eq!(&snbt.parse::<NbtValue>()?, value, "{}", snbt);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn snbt_invalid() {
+        for snbt in &[
+            "", "{", "{a:1,}", "{a 1}", "{:1}", "[1,2b]", "[B;1,2]", "\"abc", "'\\n'", "{} {}",
+        ] {
+            assert!(snbt.parse::<NbtValue>().is_err(), "{}", snbt);
+        }
+
+        assert!(matches!(
+            "[1,2b]".parse::<NbtValue>(),
+            Err(PacketError::BadSnbt { pos: 3, .. })
+        ));
+        assert!(matches!(
+            "[".repeat(MAX_DEPTH + 1).parse::<NbtValue>(),
+            Err(PacketError::NbtTooDeep(_))
+        ));
+        assert!(matches!(
+            "1".parse::<NbtCompound>(),
+            Err(PacketError::UnexpectedNbtTag { .. })
+        ));
+    }
+
+    #[test]
+    fn snbt_round_trip() -> PacketResult<()> {
+        let snbt = r#"{a:[{b:[B;-1b,2b]},{}],"c d":[[1s],[]],e:'"',f:-1.25E-5f,g:[L;-9223372036854775808L]}"#;
+        let value: NbtValue = snbt.parse()?;
+        assert_eq!(value.to_string().parse::<NbtValue>()?, value);
+        assert_eq!(
+            value.to_string(),
+            r#"{a:[{b:[B;-1b,2b]},{}],"c d":[[1s],[]],e:'"',f:-0.0000125f,g:[L;-9223372036854775808L]}"#
+        );
+
+        let non_finite = NbtList::try_from(vec![f64::INFINITY, f64::NEG_INFINITY])?;
+        assert_eq!(non_finite.to_string(), "[infd,-infd]");
+        assert_eq!(
+            NbtValue::from(non_finite.clone())
+                .to_string()
+                .parse::<NbtValue>()?,
+            NbtValue::List(non_finite)
+        );
+        let nan = NbtValue::Float(f32::NAN).to_string().parse::<NbtValue>()?;
+        assert!(nan.as_float().is_some_and(f32::is_nan));
+        assert_eq!(
+            "inf".parse::<NbtValue>()?,
+            NbtValue::String("inf".to_owned())
+        );
+        Ok(())
+    }
+
+    #[async_test]
+    async fn snbt_field_display() -> PacketResult<()> {
+        let field = NbtField::from_snbt("{Count:3b,tag:{Damage:5}}")?;
+
+        let mut cursor = Cursor::new(Vec::new());
+        field.write_field(&mut cursor).await?;
+        cursor.set_position(0);
+        let read = NbtField::read_field(&mut cursor).await?;
+
+        assert_eq!(read.to_string(), "{Count:3b,tag:{Damage:5}}");
+        Ok(())
+    }
+}
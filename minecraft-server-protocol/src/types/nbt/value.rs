@@ -0,0 +1,753 @@
+use crate::types::nbt::{NbtTag, MAX_DEPTH};
+use crate::types::{NbtField, PacketError, PacketResult};
+use mutf8::mstr;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+/// An owned NBT tag payload
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtValue {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(NbtList),
+    Compound(NbtCompound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+/// Named tags in insertion order, so a decoded compound is encoded back to the same bytes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NbtCompound(Vec<(String, NbtValue)>);
+
+/// Values that all share the same tag type
+#[derive(Debug, Clone, PartialEq)]
+pub struct NbtList {
+    /// End if nothing has been pushed yet
+    tag: NbtTag,
+    values: Vec<NbtValue>,
+}
+
+macro_rules! value_accessors {
+    ($($variant:ident $as:ident $get:ident $ty:ty;)*) => {
+        impl NbtValue {
+            $(
+                pub fn $as(&self) -> Option<$ty> {
+                    match self {
+                        NbtValue::$variant(val) => Some(*val),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+
+        impl NbtCompound {
+            $(
+                pub fn $get(&self, name: &str) -> Option<$ty> {
+                    self.get(name).and_then(NbtValue::$as)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! ref_accessors {
+    ($($variant:ident $as:ident $as_mut:ident $get:ident $get_mut:ident $ty:ty;)*) => {
+        impl NbtValue {
+            $(
+                pub fn $as(&self) -> Option<&$ty> {
+                    match self {
+                        NbtValue::$variant(val) => Some(val),
+                        _ => None,
+                    }
+                }
+
+                pub fn $as_mut(&mut self) -> Option<&mut $ty> {
+                    match self {
+                        NbtValue::$variant(val) => Some(val),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+
+        impl NbtCompound {
+            $(
+                pub fn $get(&self, name: &str) -> Option<&$ty> {
+                    self.get(name).and_then(NbtValue::$as)
+                }
+
+                pub fn $get_mut(&mut self, name: &str) -> Option<&mut $ty> {
+                    self.get_mut(name).and_then(NbtValue::$as_mut)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! value_from {
+    ($($ty:ty => $variant:ident;)*) => {
+        $(
+            impl From<$ty> for NbtValue {
+                fn from(val: $ty) -> Self {
+                    NbtValue::$variant(val)
+                }
+            }
+        )*
+    };
+}
+
+value_accessors! {
+    Byte as_byte get_byte i8;
+    Short as_short get_short i16;
+    Int as_int get_int i32;
+    Long as_long get_long i64;
+    Float as_float get_float f32;
+    Double as_double get_double f64;
+}
+
+ref_accessors! {
+    ByteArray as_byte_array as_byte_array_mut get_byte_array get_byte_array_mut Vec<i8>;
+    String as_string as_string_mut get_string get_string_mut String;
+    List as_list as_list_mut get_list get_list_mut NbtList;
+    Compound as_compound as_compound_mut get_compound get_compound_mut NbtCompound;
+    IntArray as_int_array as_int_array_mut get_int_array get_int_array_mut Vec<i32>;
+    LongArray as_long_array as_long_array_mut get_long_array get_long_array_mut Vec<i64>;
+}
+
+value_from! {
+    i8 => Byte;
+    i16 => Short;
+    i32 => Int;
+    i64 => Long;
+    f32 => Float;
+    f64 => Double;
+    Vec<i8> => ByteArray;
+    String => String;
+    NbtList => List;
+    NbtCompound => Compound;
+    Vec<i32> => IntArray;
+    Vec<i64> => LongArray;
+}
+
+impl From<&str> for NbtValue {
+    fn from(s: &str) -> Self {
+        NbtValue::String(s.to_owned())
+    }
+}
+
+impl From<bool> for NbtValue {
+    fn from(b: bool) -> Self {
+        NbtValue::Byte(b as i8)
+    }
+}
+
+impl NbtValue {
+    pub fn tag(&self) -> NbtTag {
+        match self {
+            NbtValue::Byte(_) => NbtTag::Byte,
+            NbtValue::Short(_) => NbtTag::Short,
+            NbtValue::Int(_) => NbtTag::Int,
+            NbtValue::Long(_) => NbtTag::Long,
+            NbtValue::Float(_) => NbtTag::Float,
+            NbtValue::Double(_) => NbtTag::Double,
+            NbtValue::ByteArray(_) => NbtTag::ByteArray,
+            NbtValue::String(_) => NbtTag::String,
+            NbtValue::List(_) => NbtTag::List,
+            NbtValue::Compound(_) => NbtTag::Compound,
+            NbtValue::IntArray(_) => NbtTag::IntArray,
+            NbtValue::LongArray(_) => NbtTag::LongArray,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_string().map(String::as_str)
+    }
+
+    /// Looks up a nested value by a dot-separated path of compound keys and list indices, e.g.
+    /// `display.Lore.0`
+    pub fn get_path(&self, path: &str) -> Option<&NbtValue> {
+        path.split('.').try_fold(self, |value, key| match value {
+            NbtValue::Compound(compound) => compound.get(key),
+            NbtValue::List(list) => key.parse().ok().and_then(|idx| list.get(idx)),
+            _ => None,
+        })
+    }
+
+    /// Looks up a nested compound by path, see [get_path](Self::get_path)
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut NbtCompound> {
+        path.split('.')
+            .try_fold(self, |value, key| match value {
+                NbtValue::Compound(compound) => compound.get_mut(key),
+                NbtValue::List(list) => key
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |idx| list.values.get_mut(idx))
+                    // only nested containers can be reached, so the list stays homogeneous
+                    .filter(|val| matches!(val, NbtValue::Compound(_) | NbtValue::List(_))),
+                _ => None,
+            })
+            .and_then(NbtValue::as_compound_mut)
+    }
+}
+
+impl NbtCompound {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NbtValue> {
+        self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NbtValue> {
+        self.0.iter_mut().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(NbtValue::as_str)
+    }
+
+    /// See [NbtValue::get_path]
+    pub fn get_path(&self, path: &str) -> Option<&NbtValue> {
+        let (key, rest) = split_path(path);
+        let value = self.get(key)?;
+        match rest {
+            Some(rest) => value.get_path(rest),
+            None => Some(value),
+        }
+    }
+
+    /// See [NbtValue::get_path_mut]
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut NbtCompound> {
+        let (key, rest) = split_path(path);
+        let value = self.get_mut(key)?;
+        match rest {
+            Some(rest) => value.get_path_mut(rest),
+            None => value.as_compound_mut(),
+        }
+    }
+
+    /// Replaces the value in place if the name already exists, otherwise appends it
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<NbtValue>,
+    ) -> Option<NbtValue> {
+        let name = name.into();
+        let value = value.into();
+        match self.get_mut(&name) {
+            Some(existing) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.0.push((name, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<NbtValue> {
+        let idx = self.0.iter().position(|(k, _)| k == name)?;
+        Some(self.0.remove(idx).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &NbtValue)> + '_ {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut NbtValue)> + '_ {
+        self.0.iter_mut().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl NbtList {
+    pub fn new() -> Self {
+        Self {
+            tag: NbtTag::End,
+            values: Vec::new(),
+        }
+    }
+
+    /// An empty list that can only hold values of the given type
+    pub fn with_tag(tag: NbtTag) -> Self {
+        Self {
+            tag,
+            values: Vec::new(),
+        }
+    }
+
+    /// Element type, End if empty and untyped
+    pub fn tag(&self) -> NbtTag {
+        self.tag
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&NbtValue> {
+        self.values.get(idx)
+    }
+
+    pub fn get_compound_mut(&mut self, idx: usize) -> Option<&mut NbtCompound> {
+        self.values.get_mut(idx).and_then(NbtValue::as_compound_mut)
+    }
+
+    pub fn get_list_mut(&mut self, idx: usize) -> Option<&mut NbtList> {
+        self.values.get_mut(idx).and_then(NbtValue::as_list_mut)
+    }
+
+    /// Element type of the list once it holds `value`
+    fn tag_with(&self, value: &NbtValue) -> PacketResult<NbtTag> {
+        let tag = value.tag();
+        if self.tag == NbtTag::End || self.tag == tag {
+            Ok(tag)
+        } else {
+            Err(PacketError::NbtListType {
+                expected: self.tag,
+                actual: tag,
+            })
+        }
+    }
+
+    pub fn push(&mut self, value: impl Into<NbtValue>) -> PacketResult<()> {
+        let value = value.into();
+        self.tag = self.tag_with(&value)?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Replaces the value at the given index with another of the same type
+    pub fn set(&mut self, idx: usize, value: impl Into<NbtValue>) -> PacketResult<NbtValue> {
+        let value = value.into();
+        if idx >= self.values.len() {
+            return Err(PacketError::NbtListIndex(idx));
+        }
+
+        self.tag = self.tag_with(&value)?;
+        Ok(std::mem::replace(&mut self.values[idx], value))
+    }
+
+    pub fn remove(&mut self, idx: usize) -> Option<NbtValue> {
+        if idx < self.values.len() {
+            Some(self.values.remove(idx))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NbtValue> + '_ {
+        self.values.iter()
+    }
+}
+
+impl Default for NbtList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntoIterator for NbtCompound {
+    type Item = (String, NbtValue);
+    type IntoIter = std::vec::IntoIter<(String, NbtValue)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl IntoIterator for NbtList {
+    type Item = NbtValue;
+    type IntoIter = std::vec::IntoIter<NbtValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<V: Into<NbtValue>> TryFrom<Vec<V>> for NbtList {
+    type Error = PacketError;
+
+    fn try_from(values: Vec<V>) -> Result<Self, Self::Error> {
+        let mut list = NbtList::new();
+        values.into_iter().try_for_each(|val| list.push(val))?;
+        Ok(list)
+    }
+}
+
+impl<K: Into<String>, V: Into<NbtValue>> FromIterator<(K, V)> for NbtCompound {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut compound = NbtCompound::new();
+        for (k, v) in iter {
+            compound.insert(k, v);
+        }
+        compound
+    }
+}
+
+fn split_path(path: &str) -> (&str, Option<&str>) {
+    match path.find('.') {
+        Some(idx) => (&path[..idx], Some(&path[idx + 1..])),
+        None => (path, None),
+    }
+}
+
+impl NbtField {
+    /// Encodes a root tag with the given name, usually empty for network NBT
+    pub fn from_value(name: &str, value: &NbtValue) -> Self {
+        let mut bytes = Vec::new();
+        bytes.push(value.tag() as u8);
+        write_string(&mut bytes, name);
+        write_payload(&mut bytes, value);
+        NbtField { bytes }
+    }
+
+    /// Decodes the root tag into its name and value
+    pub fn to_value(&self) -> PacketResult<(String, NbtValue)> {
+        let mut reader = ValueReader(&self.bytes);
+        let tag = reader.read_tag()?;
+        if tag == NbtTag::End {
+            return Err(PacketError::UnexpectedNbtEnd);
+        }
+
+        let name = reader.read_string()?;
+        let value = reader.read_payload(tag, 0)?;
+        Ok((name, value))
+    }
+}
+
+impl From<&NbtCompound> for NbtField {
+    fn from(compound: &NbtCompound) -> Self {
+        let mut bytes = vec![NbtTag::Compound as u8];
+        write_string(&mut bytes, "");
+        write_compound(&mut bytes, compound);
+        NbtField { bytes }
+    }
+}
+
+impl From<NbtCompound> for NbtField {
+    fn from(compound: NbtCompound) -> Self {
+        Self::from(&compound)
+    }
+}
+
+impl TryFrom<&NbtField> for NbtCompound {
+    type Error = PacketError;
+
+    fn try_from(field: &NbtField) -> Result<Self, Self::Error> {
+        match field.to_value()?.1 {
+            NbtValue::Compound(compound) => Ok(compound),
+            other => Err(PacketError::UnexpectedNbtTag {
+                expected: NbtTag::Compound,
+                actual: other.tag(),
+            }),
+        }
+    }
+}
+
+pub(crate) fn write_string(out: &mut Vec<u8>, s: &str) {
+    let mstring = mstr::from_utf8(s.as_bytes()).unwrap(); // str is utf8 always
+    let len = u16::try_from(mstring.len()).expect("string is too long");
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(mstring.as_bytes());
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    let len = i32::try_from(len).expect("array is too long");
+    out.extend_from_slice(&len.to_be_bytes());
+}
+
+fn write_compound(out: &mut Vec<u8>, compound: &NbtCompound) {
+    for (name, value) in compound.iter() {
+        out.push(value.tag() as u8);
+        write_string(out, name);
+        write_payload(out, value);
+    }
+    out.push(NbtTag::End as u8);
+}
+
+pub(crate) fn write_payload(out: &mut Vec<u8>, value: &NbtValue) {
+    match value {
+        NbtValue::Byte(b) => out.push(*b as u8),
+        NbtValue::Short(s) => out.extend_from_slice(&s.to_be_bytes()),
+        NbtValue::Int(i) => out.extend_from_slice(&i.to_be_bytes()),
+        NbtValue::Long(l) => out.extend_from_slice(&l.to_be_bytes()),
+        NbtValue::Float(f) => out.extend_from_slice(&f.to_be_bytes()),
+        NbtValue::Double(d) => out.extend_from_slice(&d.to_be_bytes()),
+        NbtValue::ByteArray(arr) => {
+            write_len(out, arr.len());
+            out.extend(arr.iter().map(|b| *b as u8));
+        }
+        NbtValue::String(s) => write_string(out, s),
+        NbtValue::List(list) => {
+            out.push(list.tag as u8);
+            write_len(out, list.len());
+            list.iter().for_each(|val| write_payload(out, val));
+        }
+        NbtValue::Compound(compound) => write_compound(out, compound),
+        NbtValue::IntArray(arr) => {
+            write_len(out, arr.len());
+            arr.iter()
+                .for_each(|i| out.extend_from_slice(&i.to_be_bytes()));
+        }
+        NbtValue::LongArray(arr) => {
+            write_len(out, arr.len());
+            arr.iter()
+                .for_each(|l| out.extend_from_slice(&l.to_be_bytes()));
+        }
+    }
+}
+
+/// Decodes from bytes already validated by [NbtReader](super::NbtReader), but still checks
+/// everything in case the field was constructed from arbitrary bytes
+struct ValueReader<'a>(&'a [u8]);
+
+impl<'a> ValueReader<'a> {
+    fn take(&mut self, n: usize) -> PacketResult<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(PacketError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> PacketResult<[u8; N]> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.take(N)?);
+        Ok(buf)
+    }
+
+    fn read_tag(&mut self) -> PacketResult<NbtTag> {
+        let [b] = self.take_array()?;
+        NbtTag::try_from(b)
+    }
+
+    fn read_string(&mut self) -> PacketResult<String> {
+        let len = u16::from_be_bytes(self.take_array()?);
+        let bytes = self.take(len as usize)?;
+        mstr::from_mutf8(bytes)
+            .to_utf8()
+            .map(|s| s.into_owned())
+            .map_err(PacketError::BadNbtString)
+    }
+
+    fn read_length(&mut self, elem_size: usize) -> PacketResult<usize> {
+        let len = i32::from_be_bytes(self.take_array()?);
+        let len = usize::try_from(len).map_err(|_| PacketError::BadNbtLength(len))?;
+        if len.saturating_mul(elem_size) > self.0.len() {
+            return Err(PacketError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        Ok(len)
+    }
+
+    fn read_payload(&mut self, tag: NbtTag, depth: usize) -> PacketResult<NbtValue> {
+        Ok(match tag {
+            NbtTag::End => return Err(PacketError::UnexpectedNbtEnd),
+            NbtTag::Byte => NbtValue::Byte(i8::from_be_bytes(self.take_array()?)),
+            NbtTag::Short => NbtValue::Short(i16::from_be_bytes(self.take_array()?)),
+            NbtTag::Int => NbtValue::Int(i32::from_be_bytes(self.take_array()?)),
+            NbtTag::Long => NbtValue::Long(i64::from_be_bytes(self.take_array()?)),
+            NbtTag::Float => NbtValue::Float(f32::from_be_bytes(self.take_array()?)),
+            NbtTag::Double => NbtValue::Double(f64::from_be_bytes(self.take_array()?)),
+            NbtTag::ByteArray => {
+                let len = self.read_length(1)?;
+                NbtValue::ByteArray(self.take(len)?.iter().map(|b| *b as i8).collect())
+            }
+            NbtTag::String => NbtValue::String(self.read_string()?),
+            NbtTag::List => {
+                if depth >= MAX_DEPTH {
+                    return Err(PacketError::NbtTooDeep(MAX_DEPTH));
+                }
+
+                let tag = self.read_tag()?;
+                let len = self.read_length(1)?;
+                if tag == NbtTag::End && len != 0 {
+                    return Err(PacketError::BadNbtTag(tag as u8));
+                }
+
+                let values = (0..len)
+                    .map(|_| self.read_payload(tag, depth + 1))
+                    .collect::<PacketResult<_>>()?;
+                NbtValue::List(NbtList { tag, values })
+            }
+            NbtTag::Compound => {
+                if depth >= MAX_DEPTH {
+                    return Err(PacketError::NbtTooDeep(MAX_DEPTH));
+                }
+
+                let mut compound = NbtCompound::new();
+                loop {
+                    let tag = self.read_tag()?;
+                    if tag == NbtTag::End {
+                        break;
+                    }
+
+                    let name = self.read_string()?;
+                    let value = self.read_payload(tag, depth + 1)?;
+                    compound.0.push((name, value));
+                }
+                NbtValue::Compound(compound)
+            }
+            NbtTag::IntArray => {
+                let len = self.read_length(4)?;
+                let values = (0..len)
+                    .map(|_| self.take_array().map(i32::from_be_bytes))
+                    .collect::<PacketResult<_>>()?;
+                NbtValue::IntArray(values)
+            }
+            NbtTag::LongArray => {
+                let len = self.read_length(8)?;
+                let values = (0..len)
+                    .map(|_| self.take_array().map(i64::from_be_bytes))
+                    .collect::<PacketResult<_>>()?;
+                NbtValue::LongArray(values)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NbtBuilder;
+    use futures_await_test::async_test;
+
+    fn item() -> NbtCompound {
+        let mut display = NbtCompound::new();
+        display.insert("Name", r#"{"text":"Excalibur"}"#);
+        display.insert("Lore", NbtList::try_from(vec!["shiny", "sharp"]).unwrap());
+
+        let mut item = NbtCompound::new();
+        item.insert("Damage", 12);
+        item.insert("display", display);
+        item.insert("Unbreakable", true);
+        item
+    }
+
+    #[test]
+    fn accessors() {
+        let mut item = item();
+        assert_eq!(item.get_int("Damage"), Some(12));
+        assert_eq!(item.get_short("Damage"), None);
+        assert_eq!(item.get_byte("Unbreakable"), Some(1));
+        assert_eq!(item.get_int("missing"), None);
+
+        let display = item.get_compound("display").unwrap();
+        assert_eq!(display.get_str("Name"), Some(r#"{"text":"Excalibur"}"#));
+        assert_eq!(display.get_list("Lore").map(NbtList::len), Some(2));
+
+        assert_eq!(
+            item.get_path("display.Lore.1").and_then(NbtValue::as_str),
+            Some("sharp")
+        );
+        assert!(item.get_path("display.Lore.2").is_none());
+        assert!(item.get_path("Damage.nope").is_none());
+
+        item.get_path_mut("display")
+            .unwrap()
+            .insert("Name", r#"{"text":"Butter knife"}"#);
+        assert_eq!(item.len(), 3);
+        assert_eq!(
+            item.get_path("display.Name").and_then(NbtValue::as_str),
+            Some(r#"{"text":"Butter knife"}"#)
+        );
+
+        assert_eq!(item.remove("Damage"), Some(NbtValue::Int(12)));
+        assert!(!item.contains_key("Damage"));
+    }
+
+    #[test]
+    fn list_types() {
+        let mut list = NbtList::new();
+        assert_eq!(list.tag(), NbtTag::End);
+
+        list.push(5i32).unwrap();
+        assert_eq!(list.tag(), NbtTag::Int);
+        assert!(matches!(
+            list.push("nope"),
+            Err(PacketError::NbtListType {
+                expected: NbtTag::Int,
+                actual: NbtTag::String
+            })
+        ));
+        assert!(list.set(0, 1i64).is_err());
+        assert_eq!(list.set(0, 6i32).unwrap(), NbtValue::Int(5));
+        assert!(list.set(1, 6i32).is_err());
+        assert_eq!(list.len(), 1);
+
+        // a failed set leaves an empty list untyped
+        let mut empty = NbtList::new();
+        assert!(matches!(
+            empty.set(0, "nope"),
+            Err(PacketError::NbtListIndex(0))
+        ));
+        assert_eq!(empty.tag(), NbtTag::End);
+        empty.push(1i8).unwrap();
+        assert_eq!(empty.tag(), NbtTag::Byte);
+    }
+
+    #[test]
+    fn field_round_trip() {
+        let item = item();
+        let field = NbtField::from(&item);
+        assert_eq!(NbtCompound::try_from(&field).unwrap(), item);
+
+        let value = NbtValue::List(NbtList::with_tag(NbtTag::Compound));
+        let field = NbtField::from_value("empty", &value);
+        assert_eq!(field.to_value().unwrap(), ("empty".to_owned(), value));
+        assert!(NbtCompound::try_from(&field).is_err());
+    }
+
+    #[async_test]
+    async fn matches_builder() -> PacketResult<()> {
+        let built = NbtBuilder::default()
+            .begin_named_compound("hello world")
+            .await?
+            .begin_named_tag("name")
+            .await?
+            .string("Bananrama")
+            .await?
+            .begin_named_tag("heights")
+            .await?
+            .long_array(&[1, 2, 3])
+            .await?
+            .end()
+            .await?
+            .into_field();
+
+        let (name, value) = built.to_value()?;
+        assert_eq!(name, "hello world");
+
+        let compound = value.as_compound().unwrap();
+        assert_eq!(compound.get_str("name"), Some("Bananrama"));
+        assert_eq!(
+            compound.get_long_array("heights").map(Vec::as_slice),
+            Some(&[1i64, 2, 3][..])
+        );
+
+        assert_eq!(NbtField::from_value(&name, &value).bytes, built.bytes);
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::types::field::Field;
+use crate::types::{BoolField, PacketResult};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+
+/// Optional value, prefixed by a bool indicating if it is present
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OptionField<T>(Option<T>);
+
+impl<T> OptionField<T> {
+    pub fn new(value: Option<T>) -> Self {
+        Self(value)
+    }
+
+    pub fn some(value: T) -> Self {
+        Self(Some(value))
+    }
+
+    pub fn none() -> Self {
+        Self(None)
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.0.as_mut()
+    }
+
+    pub fn take(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> From<Option<T>> for OptionField<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+#[async_trait]
+impl<T: Field + Send + Sync> Field for OptionField<T> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        1 + self.0.as_ref().map_or(0, T::size)
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        if *BoolField::read_field(r).await?.value() {
+            T::read_field(r).await.map(Self::some)
+        } else {
+            Ok(Self::none())
+        }
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        BoolField::new(self.0.is_some()).write_field(w).await?;
+        match &self.0 {
+            Some(value) => value.write_field(w).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Field> Display for OptionField<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(value) => value.value().fmt(f),
+            None => f.write_str("none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{StringField, VarIntField};
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    #[async_test]
+    async fn option_round_trip() -> PacketResult<()> {
+        let mut cursor = Cursor::new(Vec::new());
+        let some = OptionField::some(VarIntField::new(300));
+        let none = OptionField::<VarIntField>::none();
+        some.write_field(&mut cursor).await?;
+        none.write_field(&mut cursor).await?;
+        assert_eq!(cursor.get_ref().as_slice(), b"\x01\xac\x02\x00");
+        assert_eq!(some.size() + none.size(), 4);
+
+        cursor.set_position(0);
+        let read = OptionField::<VarIntField>::read_field(&mut cursor).await?;
+        assert_eq!(read.get().map(VarIntField::value), Some(300));
+        assert_eq!(read.to_string(), "300");
+
+        let read = OptionField::<VarIntField>::read_field(&mut cursor).await?;
+        assert!(read.get().is_none());
+        assert_eq!(read.to_string(), "none");
+        Ok(())
+    }
+
+    #[async_test]
+    async fn option_missing_value() {
+        let mut cursor = Cursor::new(vec![1u8]);
+        assert!(OptionField::<StringField>::read_field(&mut cursor)
+            .await
+            .is_err());
+    }
+}
@@ -29,11 +29,138 @@ pub enum PacketError {
     /// Invalid unicode string: {0}
     BadString(#[from] FromUtf8Error),
 
+    /// Invalid chat component: {0}
+    BadChat(serde_json::Error),
+
     /// Varint is longer than the max of 5 bytes (got {0} bytes)
     BadVarInt(usize),
 
+    /// Varlong is longer than the max of 10 bytes (got {0} bytes)
+    BadVarLong(usize),
+
     /// Bad bool value, must be 0 or 1 (got {0})
     BadBool(u8),
+
+    /// Invalid NBT tag type {0}
+    BadNbtTag(u8),
+
+    /// Invalid NBT length {0}
+    BadNbtLength(i32),
+
+    /// Invalid modified UTF-8 string in NBT: {0}
+    BadNbtString(mutf8::error::Error),
+
+    /// NBT is nested deeper than the max of {0} levels
+    NbtTooDeep(usize),
+
+    /// Expected an NBT tag but got TAG_End
+    UnexpectedNbtEnd,
+
+    /// Expected NBT tag {expected:?} but got {actual:?}
+    UnexpectedNbtTag { expected: NbtTag, actual: NbtTag },
+
+    /// NBT list of {expected:?} can't hold {actual:?}
+    NbtListType { expected: NbtTag, actual: NbtTag },
+
+    /// NBT list index {0} is out of bounds
+    NbtListIndex(usize),
+
+    /// {0} bytes left over after the NBT root tag
+    TrailingNbtBytes(usize),
+
+    /// NBT serde error: {0}
+    NbtSerde(String),
+
+    /// Invalid SNBT at position {pos}: {reason}
+    BadSnbt { pos: usize, reason: &'static str },
+
+    /// Invalid UUID string {0:?}
+    BadUuid(String),
+
+    /// Item ID {0} can't be sent in this version's slot format
+    BadItemId(i32),
+
+    /// Position ({0}, {1}, {2}) is out of range
+    PositionOutOfRange(i32, i32, i32),
+
+    /// {0} depends on an earlier field so can't be read on its own
+    MissingDependency(&'static str),
+
+    /// {field} is {variant} but the field it depends on selects another variant
+    SwitchMismatch {
+        field: &'static str,
+        variant: &'static str,
+    },
+
+    /// Particle {0} takes a different kind of data
+    ParticleDataMismatch(i32),
+
+    /// Unknown vibration destination type {0:?}
+    BadVibrationDestination(String),
+
+    /// Unknown entity metadata type {0}
+    BadMetadataType(i32),
+
+    /// Invalid {kind} {value} in entity metadata
+    BadMetadataEnum { kind: &'static str, value: i32 },
+
+    /// Entity metadata type {0:?} isn't supported in this version
+    UnsupportedMetadataType(MetadataType),
+
+    /// Pose {0:?} isn't supported in this version
+    UnsupportedPose(Pose),
+
+    /// Invalid equipment slot {0}
+    BadEquipmentSlot(i8),
+
+    /// Entity equipment must have at least one entry
+    EmptyEquipment,
+
+    /// Invalid array length {0}
+    BadArrayLength(i64),
+
+    /// Array of {0} elements is too long for its length prefix
+    ArrayTooLong(usize),
+
+    /// Invalid frame length {0}
+    BadFrameLength(i32),
+
+    /// Frame of {length} bytes is longer than the max of {max}
+    FrameTooLong { length: usize, max: usize },
+
+    /// Packet wrote {written} bytes but its length prefix says {length}
+    PacketLengthMismatch { length: usize, written: usize },
+
+    /// Invalid uncompressed length {0} of a compressed packet
+    BadDataLength(i32),
+
+    /// Compressed packet of {length} bytes is below the threshold of {threshold}
+    CompressedBelowThreshold { length: usize, threshold: usize },
+
+    /// Compressed packet declares {declared} bytes but inflates to {actual}
+    DataLengthMismatch { declared: usize, actual: usize },
+
+    /// RSA error: {0}
+    #[cfg(feature = "auth")]
+    Rsa(#[from] rsa::Error),
+
+    /// Shared secret must be 16 bytes (got {0} bytes)
+    BadSharedSecret(usize),
+
+    /// Verify token doesn't match the one sent
+    BadVerifyToken,
+
+    /// Session server request failed: {0}
+    SessionServer(String),
+
+    /// Invalid profile from the session server: {0}
+    BadProfile(serde_json::Error),
+
+    /// {0} hasn't joined through the session server
+    NotAuthenticated(String),
+
+    /// Invalid username {0:?}
+    BadUsername(String),
 }
 
 pub struct PacketBody {
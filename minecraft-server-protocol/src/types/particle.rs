@@ -0,0 +1,520 @@
+use crate::types::field::{DependentField, Field};
+use crate::types::{
+    FloatField, ItemStack, PacketError, PacketResult, PositionField, SlotField, StringField,
+    VarIntField,
+};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+
+/// >= has particle data keyed by particle ID, < is generated from plain switches (1.13)
+const PARTICLE_DATA_CUTOFF: u32 = 393;
+
+/// Snapshot protocol versions have this bit set, so don't compare in order with releases
+const SNAPSHOT_BIT: u32 = 1 << 30;
+
+/// A particle type and its extra data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub id: i32,
+    pub data: ParticleData,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParticleData {
+    None,
+    /// `block` and `falling_dust`
+    BlockState(i32),
+    Dust {
+        red: f32,
+        green: f32,
+        blue: f32,
+        scale: f32,
+    },
+    /// 1.17+
+    DustColorTransition {
+        from: [f32; 3],
+        scale: f32,
+        to: [f32; 3],
+    },
+    Item(Option<ItemStack>),
+    /// 1.17+
+    Vibration {
+        origin: (i32, i32, i32),
+        destination: VibrationDestination,
+        ticks: i32,
+    },
+}
+
+/// Particle data for the particle ID in an earlier field, which is also used to write it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleDataField<const VERSION: u32>(ParticleData);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum VibrationDestination {
+    Block((i32, i32, i32)),
+    Entity(i32),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum DataKind {
+    None,
+    BlockState,
+    Dust,
+    DustColorTransition,
+    Item,
+    Vibration,
+}
+
+/// IDs of the particles that take data, which shift around as particles are added
+struct ParticleIds {
+    block: i32,
+    dust: i32,
+    dust_color_transition: Option<i32>,
+    falling_dust: i32,
+    item: i32,
+    vibration: Option<i32>,
+}
+
+const PARTICLES_1_13: ParticleIds = ParticleIds {
+    block: 3,
+    dust: 11,
+    dust_color_transition: None,
+    falling_dust: 20,
+    item: 27,
+    vibration: None,
+};
+
+const PARTICLES_1_14: ParticleIds = ParticleIds {
+    block: 3,
+    dust: 14,
+    dust_color_transition: None,
+    falling_dust: 23,
+    item: 32,
+    vibration: None,
+};
+
+const PARTICLES_1_16: ParticleIds = ParticleIds {
+    block: 3,
+    dust: 14,
+    dust_color_transition: None,
+    falling_dust: 23,
+    item: 34,
+    vibration: None,
+};
+
+const PARTICLES_1_17: ParticleIds = ParticleIds {
+    block: 4,
+    dust: 15,
+    dust_color_transition: Some(16),
+    falling_dust: 25,
+    item: 36,
+    vibration: Some(37),
+};
+
+fn particle_ids(version: u32) -> Option<&'static ParticleIds> {
+    Some(match version {
+        // 21w07a is the only snapshot here, and still has the 1.16 particles
+        v if v & SNAPSHOT_BIT != 0 => &PARTICLES_1_16,
+        755.. => &PARTICLES_1_17,
+        709.. => &PARTICLES_1_16,
+        477.. => &PARTICLES_1_14,
+        PARTICLE_DATA_CUTOFF.. => &PARTICLES_1_13,
+        _ => return None,
+    })
+}
+
+fn data_kind(version: u32, id: i32) -> DataKind {
+    let ids = match particle_ids(version) {
+        Some(ids) => ids,
+        None => return DataKind::None,
+    };
+
+    if id == ids.block || id == ids.falling_dust {
+        DataKind::BlockState
+    } else if id == ids.dust {
+        DataKind::Dust
+    } else if Some(id) == ids.dust_color_transition {
+        DataKind::DustColorTransition
+    } else if id == ids.item {
+        DataKind::Item
+    } else if Some(id) == ids.vibration {
+        DataKind::Vibration
+    } else {
+        DataKind::None
+    }
+}
+
+impl ParticleData {
+    fn kind(&self) -> DataKind {
+        match self {
+            ParticleData::None => DataKind::None,
+            ParticleData::BlockState(_) => DataKind::BlockState,
+            ParticleData::Dust { .. } => DataKind::Dust,
+            ParticleData::DustColorTransition { .. } => DataKind::DustColorTransition,
+            ParticleData::Item(_) => DataKind::Item,
+            ParticleData::Vibration { .. } => DataKind::Vibration,
+        }
+    }
+
+    /// Reads the data for the given particle ID
+    pub(crate) async fn read<const VERSION: u32, R: Read + Unpin + Send>(
+        id: i32,
+        r: &mut R,
+    ) -> PacketResult<Self> {
+        Ok(match data_kind(VERSION, id) {
+            DataKind::None => ParticleData::None,
+            DataKind::BlockState => ParticleData::BlockState(read_varint(r).await?),
+            DataKind::Dust => {
+                let [red, green, blue, scale] = read_floats(r).await?;
+                ParticleData::Dust {
+                    red,
+                    green,
+                    blue,
+                    scale,
+                }
+            }
+            DataKind::DustColorTransition => {
+                let [r1, g1, b1, scale, r2, g2, b2] = read_floats(r).await?;
+                ParticleData::DustColorTransition {
+                    from: [r1, g1, b1],
+                    scale,
+                    to: [r2, g2, b2],
+                }
+            }
+            DataKind::Item => ParticleData::Item(SlotField::<VERSION>::read_field(r).await?.take()),
+            DataKind::Vibration => {
+                let origin = PositionField::<VERSION>::read_field(r).await?.coords();
+                let destination = match StringField::read_field(r).await?.value().as_str() {
+                    "minecraft:block" => VibrationDestination::Block(
+                        PositionField::<VERSION>::read_field(r).await?.coords(),
+                    ),
+                    "minecraft:entity" => VibrationDestination::Entity(read_varint(r).await?),
+                    other => return Err(PacketError::BadVibrationDestination(other.to_owned())),
+                };
+                let ticks = read_varint(r).await?;
+                ParticleData::Vibration {
+                    origin,
+                    destination,
+                    ticks,
+                }
+            }
+        })
+    }
+
+    /// Writes the data, which must be the kind the given particle ID takes
+    pub(crate) async fn write<const VERSION: u32, W: Write + Unpin + Send>(
+        &self,
+        id: i32,
+        w: &mut W,
+    ) -> PacketResult<()> {
+        if self.kind() != data_kind(VERSION, id) {
+            return Err(PacketError::ParticleDataMismatch(id));
+        }
+
+        match self {
+            ParticleData::None => Ok(()),
+            ParticleData::BlockState(state) => VarIntField::new(*state).write_field(w).await,
+            ParticleData::Dust {
+                red,
+                green,
+                blue,
+                scale,
+            } => write_floats(w, &[*red, *green, *blue, *scale]).await,
+            ParticleData::DustColorTransition { from, scale, to } => {
+                write_floats(w, from).await?;
+                FloatField::new(*scale).write_field(w).await?;
+                write_floats(w, to).await
+            }
+            ParticleData::Item(item) => {
+                SlotField::<VERSION>::new(item.clone()).write_field(w).await
+            }
+            ParticleData::Vibration {
+                origin,
+                destination,
+                ticks,
+            } => {
+                position::<VERSION>(*origin)?.write_field(w).await?;
+                match destination {
+                    VibrationDestination::Block(pos) => {
+                        StringField::new("minecraft:block".to_owned())
+                            .write_field(w)
+                            .await?;
+                        position::<VERSION>(*pos)?.write_field(w).await?;
+                    }
+                    VibrationDestination::Entity(entity) => {
+                        StringField::new("minecraft:entity".to_owned())
+                            .write_field(w)
+                            .await?;
+                        VarIntField::new(*entity).write_field(w).await?;
+                    }
+                }
+                VarIntField::new(*ticks).write_field(w).await
+            }
+        }
+    }
+
+    pub(crate) fn size<const VERSION: u32>(&self) -> usize {
+        match self {
+            ParticleData::None => 0,
+            ParticleData::BlockState(state) => VarIntField::new(*state).size(),
+            ParticleData::Dust { .. } => 4 * 4,
+            ParticleData::DustColorTransition { .. } => 7 * 4,
+            ParticleData::Item(item) => SlotField::<VERSION>::new(item.clone()).size(),
+            ParticleData::Vibration {
+                destination, ticks, ..
+            } => {
+                let destination = match destination {
+                    VibrationDestination::Block(_) => {
+                        StringField::new("minecraft:block".to_owned()).size() + 8
+                    }
+                    VibrationDestination::Entity(entity) => {
+                        StringField::new("minecraft:entity".to_owned()).size()
+                            + VarIntField::new(*entity).size()
+                    }
+                };
+                8 + destination + VarIntField::new(*ticks).size()
+            }
+        }
+    }
+}
+
+impl Display for Particle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.data {
+            ParticleData::None => write!(f, "{}", self.id),
+            data => write!(f, "{} {:?}", self.id, data),
+        }
+    }
+}
+
+impl Display for ParticleData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl<const VERSION: u32> ParticleDataField<VERSION> {
+    pub fn new(data: ParticleData) -> Self {
+        Self(data)
+    }
+
+    pub fn data(&self) -> &ParticleData {
+        &self.0
+    }
+
+    pub fn take(self) -> ParticleData {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> Field for ParticleDataField<VERSION> {
+    type Displayable = ParticleData;
+
+    fn value(&self) -> &Self::Displayable {
+        &self.0
+    }
+
+    fn size(&self) -> usize {
+        self.0.size::<VERSION>()
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+        Err(PacketError::MissingDependency("ParticleDataField"))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
+        Err(PacketError::MissingDependency("ParticleDataField"))
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> DependentField for ParticleDataField<VERSION> {
+    /// Particle ID
+    type Dependency = i32;
+
+    async fn read_dependent<R: Read + Unpin + Send>(r: &mut R, id: &i32) -> PacketResult<Self> {
+        Ok(Self(ParticleData::read::<VERSION, _>(*id, r).await?))
+    }
+
+    async fn write_dependent<W: Write + Unpin + Send>(
+        &self,
+        w: &mut W,
+        id: &i32,
+    ) -> PacketResult<()> {
+        self.0.write::<VERSION, _>(*id, w).await
+    }
+}
+
+fn position<const VERSION: u32>(pos: (i32, i32, i32)) -> PacketResult<PositionField<VERSION>> {
+    PositionField::new(pos).ok_or(PacketError::PositionOutOfRange(pos.0, pos.1, pos.2))
+}
+
+async fn read_varint<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<i32> {
+    Ok(VarIntField::read_field(r).await?.value())
+}
+
+async fn read_floats<R: Read + Unpin + Send, const N: usize>(r: &mut R) -> PacketResult<[f32; N]> {
+    let mut floats = [0.0; N];
+    for float in floats.iter_mut() {
+        *float = *FloatField::read_field(r).await?.value();
+    }
+    Ok(floats)
+}
+
+async fn write_floats<W: Write + Unpin + Send>(w: &mut W, floats: &[f32]) -> PacketResult<()> {
+    for float in floats {
+        FloatField::new(*float).write_field(w).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    async fn round_trip<const VERSION: u32>(particle: Particle) -> PacketResult<Vec<u8>> {
+        let mut cursor = Cursor::new(Vec::new());
+        particle
+            .data
+            .write::<VERSION, _>(particle.id, &mut cursor)
+            .await?;
+        assert_eq!(cursor.get_ref().len(), particle.data.size::<VERSION>());
+
+        cursor.set_position(0);
+        let read = ParticleData::read::<VERSION, _>(particle.id, &mut cursor).await?;
+        assert_eq!(read, particle.data);
+        Ok(cursor.into_inner())
+    }
+
+    #[async_test]
+    async fn particle_tables() -> PacketResult<()> {
+        let dust = ParticleData::Dust {
+            red: 1.0,
+            green: 0.0,
+            blue: 0.5,
+            scale: 2.0,
+        };
+
+        let bytes = round_trip::<404>(Particle {
+            id: 11,
+            data: dust.clone(),
+        })
+        .await?;
+        assert_eq!(bytes.len(), 16);
+
+        round_trip::<578>(Particle { id: 14, data: dust }).await?;
+        round_trip::<578>(Particle {
+            id: 11,
+            data: ParticleData::None,
+        })
+        .await?;
+        round_trip::<751>(Particle {
+            id: 34,
+            data: ParticleData::Item(Some(ItemStack::new(1, 1))),
+        })
+        .await?;
+        round_trip::<{ SNAPSHOT_BIT | 15 }>(Particle {
+            id: 23,
+            data: ParticleData::BlockState(9),
+        })
+        .await?;
+        round_trip::<755>(Particle {
+            id: 37,
+            data: ParticleData::Vibration {
+                origin: (1, 2, 3),
+                destination: VibrationDestination::Entity(5),
+                ticks: 20,
+            },
+        })
+        .await?;
+        round_trip::<755>(Particle {
+            id: 16,
+            data: ParticleData::DustColorTransition {
+                from: [0.0, 0.5, 1.0],
+                scale: 1.0,
+                to: [1.0, 0.5, 0.0],
+            },
+        })
+        .await?;
+        Ok(())
+    }
+
+    #[async_test]
+    async fn particle_data_field() -> PacketResult<()> {
+        let field = ParticleDataField::<578>::new(ParticleData::Item(None));
+        let mut cursor = Cursor::new(Vec::new());
+        field.write_dependent(&mut cursor, &32).await?;
+        assert_eq!(cursor.get_ref().as_slice(), b"\x00");
+        assert!(matches!(
+            field.write_dependent(&mut cursor, &3).await,
+            Err(PacketError::ParticleDataMismatch(3))
+        ));
+        assert!(matches!(
+            field.write_field(&mut cursor).await,
+            Err(PacketError::MissingDependency(_))
+        ));
+
+        cursor.set_position(0);
+        let read = ParticleDataField::<578>::read_dependent(&mut cursor, &32).await?;
+        assert_eq!(read, field);
+
+        cursor.set_position(0);
+        assert!(matches!(
+            ParticleDataField::<578>::read_field(&mut cursor).await,
+            Err(PacketError::MissingDependency(_))
+        ));
+        Ok(())
+    }
+
+    #[async_test]
+    async fn particle_data_in_packet() -> PacketResult<()> {
+        use crate::types::{ClientBound, IntField, PacketBody, PacketId, ServerBound};
+        use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+
+        #[derive(ServerBoundPacket)]
+        #[packet_id = 0x24]
+        struct Particles {
+            particle_id: IntField,
+            #[depends_on = "particle_id"]
+            data: ParticleDataField<578>,
+        }
+
+        let packet = Particles::read_packet(PacketBody {
+            id: 0x24,
+            body: b"\x00\x00\x00\x03\x09".to_vec(),
+        })
+        .await?;
+        assert_eq!(packet.particle_id.value(), &3);
+        assert_eq!(packet.data.data(), &ParticleData::BlockState(9));
+
+        #[derive(ClientBoundPacket)]
+        #[packet_id = 0x24]
+        struct ParticlesOut {
+            particle_id: IntField,
+            #[depends_on = "particle_id"]
+            data: ParticleDataField<578>,
+        }
+
+        let packet = ParticlesOut {
+            particle_id: IntField::new(3),
+            data: ParticleDataField::new(ParticleData::BlockState(9)),
+        };
+        let mut buf = vec![0; packet.length() + 1];
+        packet.write_packet(&mut Cursor::new(&mut buf[..])).await?;
+        assert_eq!(buf, b"\x06\x24\x00\x00\x00\x03\x09");
+        Ok(())
+    }
+
+    #[async_test]
+    async fn particle_mismatch() {
+        let mut cursor = Cursor::new(Vec::new());
+        let result = ParticleData::BlockState(1)
+            .write::<578, _>(14, &mut cursor)
+            .await;
+        assert!(matches!(result, Err(PacketError::ParticleDataMismatch(14))));
+    }
+}
@@ -7,7 +7,7 @@ use std::fmt::{Display, Formatter};
 /// < is pre-1.14 and uses old format, >= is post-1.14 and uses new format
 const NEW_FORMAT_CUTOFF: u32 = 477;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PositionField<const VERSION: u32> {
     x: i32,
     y: i32,
@@ -103,12 +103,19 @@ impl<const VERSION: u32> PositionField<VERSION> {
         const MAX_Y: i32 = 2i32.pow(11) - 1;
         const MIN_Y: i32 = -(2i32.pow(11));
 
-        if x < MIN_XZ || x > MAX_XZ || z < MIN_XZ || z > MAX_XZ || y < MIN_Y || y > MAX_Y {
+        if !(MIN_XZ..=MAX_XZ).contains(&x)
+            || !(MIN_XZ..=MAX_XZ).contains(&z)
+            || !(MIN_Y..=MAX_Y).contains(&y)
+        {
             None
         } else {
             Some(PositionField { x, y, z })
         }
     }
+
+    pub fn coords(&self) -> (i32, i32, i32) {
+        (self.x, self.y, self.z)
+    }
 }
 
 impl ToSigned for u64 {
@@ -0,0 +1,247 @@
+use crate::types::field::Field;
+use crate::types::{
+    BoolField, ByteField, NbtField, OptionalNbtField, PacketError, PacketResult, ShortField,
+    VarIntField,
+};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// < sends item damage after the count, >= keeps it in the `Damage` NBT tag (17w47a)
+const NO_DAMAGE_CUTOFF: u32 = 346;
+
+/// < uses a short ID of -1 for empty slots, >= uses a present flag and varint ID (1.13.2-pre1)
+const PRESENT_FLAG_CUTOFF: u32 = 402;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    pub id: i32,
+    pub count: i8,
+    /// Only sent before 1.13
+    pub damage: i16,
+    pub nbt: Option<NbtField>,
+}
+
+/// An inventory slot, which may be empty
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SlotField<const VERSION: u32>(Option<ItemStack>);
+
+impl ItemStack {
+    pub fn new(id: i32, count: i8) -> Self {
+        Self {
+            id,
+            count,
+            damage: 0,
+            nbt: None,
+        }
+    }
+
+    pub fn with_damage(mut self, damage: i16) -> Self {
+        self.damage = damage;
+        self
+    }
+
+    pub fn with_nbt(mut self, nbt: NbtField) -> Self {
+        self.nbt = Some(nbt);
+        self
+    }
+}
+
+impl Display for ItemStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x {}", self.count, self.id)?;
+        if self.damage != 0 {
+            write!(f, ":{}", self.damage)?;
+        }
+        if let Some(nbt) = &self.nbt {
+            write!(f, " {}", nbt)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const VERSION: u32> SlotField<VERSION> {
+    pub fn new(item: Option<ItemStack>) -> Self {
+        Self(item)
+    }
+
+    pub fn empty() -> Self {
+        Self(None)
+    }
+
+    pub fn item(&self) -> Option<&ItemStack> {
+        self.0.as_ref()
+    }
+
+    pub fn item_mut(&mut self) -> Option<&mut ItemStack> {
+        self.0.as_mut()
+    }
+
+    pub fn take(self) -> Option<ItemStack> {
+        self.0
+    }
+
+    fn nbt_size(item: &ItemStack) -> usize {
+        item.nbt.as_ref().map_or(1, NbtField::size)
+    }
+}
+
+impl<const VERSION: u32> From<ItemStack> for SlotField<VERSION> {
+    fn from(item: ItemStack) -> Self {
+        Self(Some(item))
+    }
+}
+
+impl<const VERSION: u32> From<Option<ItemStack>> for SlotField<VERSION> {
+    fn from(item: Option<ItemStack>) -> Self {
+        Self(item)
+    }
+}
+
+#[async_trait]
+impl<const VERSION: u32> Field for SlotField<VERSION> {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        match &self.0 {
+            None if VERSION >= PRESENT_FLAG_CUTOFF => 1,
+            None => 2,
+            Some(item) if VERSION >= PRESENT_FLAG_CUTOFF => {
+                1 + VarIntField::new(item.id).size() + 1 + Self::nbt_size(item)
+            }
+            Some(item) if VERSION >= NO_DAMAGE_CUTOFF => 2 + 1 + Self::nbt_size(item),
+            Some(item) => 2 + 1 + 2 + Self::nbt_size(item),
+        }
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let id = if VERSION >= PRESENT_FLAG_CUTOFF {
+            if !*BoolField::read_field(r).await?.value() {
+                return Ok(Self(None));
+            }
+
+            VarIntField::read_field(r).await?.value()
+        } else {
+            match *ShortField::read_field(r).await?.value() {
+                -1 => return Ok(Self(None)),
+                id => id as i32,
+            }
+        };
+
+        let count = *ByteField::read_field(r).await?.value();
+        let damage = if VERSION >= NO_DAMAGE_CUTOFF {
+            0
+        } else {
+            *ShortField::read_field(r).await?.value()
+        };
+        let nbt = OptionalNbtField::read_field(r).await?.take();
+
+        Ok(Self(Some(ItemStack {
+            id,
+            count,
+            damage,
+            nbt,
+        })))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        let item = match &self.0 {
+            Some(item) => item,
+            None if VERSION >= PRESENT_FLAG_CUTOFF => {
+                return BoolField::new(false).write_field(w).await;
+            }
+            None => return ShortField::new(-1).write_field(w).await,
+        };
+
+        if VERSION >= PRESENT_FLAG_CUTOFF {
+            BoolField::new(true).write_field(w).await?;
+            VarIntField::new(item.id).write_field(w).await?;
+        } else {
+            let id = i16::try_from(item.id)
+                .ok()
+                .filter(|id| *id != -1)
+                .ok_or(PacketError::BadItemId(item.id))?;
+            ShortField::new(id).write_field(w).await?;
+        }
+
+        ByteField::new(item.count).write_field(w).await?;
+        if VERSION < NO_DAMAGE_CUTOFF {
+            ShortField::new(item.damage).write_field(w).await?;
+        }
+
+        match &item.nbt {
+            Some(nbt) => nbt.write_field(w).await,
+            None => OptionalNbtField::default().write_field(w).await,
+        }
+    }
+}
+
+impl<const VERSION: u32> Display for SlotField<VERSION> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(item) => item.fmt(f),
+            None => f.write_str("empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    async fn round_trip<const VERSION: u32>(
+        slot: SlotField<VERSION>,
+        expected: &[u8],
+    ) -> PacketResult<()> {
+        let mut cursor = Cursor::new(Vec::new());
+        slot.write_field(&mut cursor).await?;
+        assert_eq!(cursor.get_ref().as_slice(), expected);
+        assert_eq!(slot.size(), expected.len());
+
+        cursor.set_position(0);
+        let read = SlotField::<VERSION>::read_field(&mut cursor).await?;
+        assert_eq!(read, slot);
+        Ok(())
+    }
+
+    #[async_test]
+    async fn slot_versions() -> PacketResult<()> {
+        let nbt = NbtField::from_snbt("{Damage:3}")?;
+        let item = ItemStack::new(300, 2);
+
+        round_trip::<340>(SlotField::empty(), b"\xff\xff").await?;
+        round_trip::<340>(
+            item.clone().with_damage(3).into(),
+            b"\x01\x2c\x02\x00\x03\x00",
+        )
+        .await?;
+
+        round_trip::<401>(SlotField::empty(), b"\xff\xff").await?;
+        round_trip::<401>(
+            item.clone().with_nbt(nbt.clone()).into(),
+            b"\x01\x2c\x02\x0a\x00\x00\x03\x00\x06Damage\x00\x00\x00\x03\x00",
+        )
+        .await?;
+
+        round_trip::<578>(SlotField::empty(), b"\x00").await?;
+        round_trip::<578>(item.into(), b"\x01\xac\x02\x02\x00").await?;
+        Ok(())
+    }
+
+    #[async_test]
+    async fn slot_bad_id() {
+        let slot = SlotField::<340>::from(ItemStack::new(40000, 1));
+        let mut cursor = Cursor::new(Vec::new());
+        assert!(matches!(
+            slot.write_field(&mut cursor).await,
+            Err(PacketError::BadItemId(40000))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::types::field::Field;
+use crate::types::{PacketError, PacketResult};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// 128-bit UUID, sent as 2 big-endian longs
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct UuidField(u128);
+
+impl UuidField {
+    pub fn new(value: u128) -> Self {
+        Self(value)
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+}
+
+#[async_trait]
+impl Field for UuidField {
+    type Displayable = Self;
+
+    fn value(&self) -> &Self::Displayable {
+        self
+    }
+
+    fn size(&self) -> usize {
+        16
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut buf = [0u8; 16];
+        r.read_exact(&mut buf).await.map_err(PacketError::Io)?;
+        Ok(Self(u128::from_be_bytes(buf)))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        w.write_all(&self.to_bytes()).await.map_err(PacketError::Io)
+    }
+}
+
+impl Display for UuidField {
+    /// Hyphenated lowercase hex, e.g. `069a79f4-44e9-4726-a5be-fca90e38aaf5`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let v = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            v >> 96,
+            (v >> 80) & 0xffff,
+            (v >> 64) & 0xffff,
+            (v >> 48) & 0xffff,
+            v & 0xffff_ffff_ffff
+        )
+    }
+}
+
+impl FromStr for UuidField {
+    type Err = PacketError;
+
+    /// Accepts hyphenated or plain hex, as the Mojang API returns the latter
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+        let valid = if s.len() == 32 {
+            true
+        } else {
+            let groups = s.split('-').map(str::len);
+            groups.eq(GROUPS.iter().copied())
+        };
+
+        let hex = s.replace('-', "");
+        if !valid || hex.len() != 32 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(PacketError::BadUuid(s.to_owned()));
+        }
+
+        u128::from_str_radix(&hex, 16)
+            .map(Self)
+            .map_err(|_| PacketError::BadUuid(s.to_owned()))
+    }
+}
+
+impl From<u128> for UuidField {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<UuidField> for u128 {
+    fn from(uuid: UuidField) -> Self {
+        uuid.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::io::Cursor;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn uuid_strings() {
+        let uuid: UuidField = "069a79f4-44e9-4726-a5be-fca90e38aaf5".parse().unwrap();
+        assert_eq!(uuid.as_u128(), 0x069a79f444e94726a5befca90e38aaf5);
+        assert_eq!(uuid.to_string(), "069a79f4-44e9-4726-a5be-fca90e38aaf5");
+
+        let plain: UuidField = "069A79F444E94726A5BEFCA90E38AAF5".parse().unwrap();
+        assert_eq!(plain, uuid);
+
+        assert_eq!(
+            UuidField::new(1).to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+
+        for bad in &[
+            "",
+            "069a79f4-44e9-4726-a5be-fca90e38aaf",
+            "069a79f444e9-4726-a5be-fca90e38aaf5",
+            "069a79f4-44e9-4726-a5be-fca90e38aafg",
+            "+69a79f444e94726a5befca90e38aaf5",
+        ] {
+            assert!(bad.parse::<UuidField>().is_err(), "{}", bad);
+        }
+    }
+
+    #[quickcheck]
+    fn uuid_values(value: u128) {
+        async_std::task::block_on(async {
+            let field = UuidField::new(value);
+            assert_eq!(field.to_string().parse::<UuidField>().unwrap(), field);
+
+            let mut cursor = Cursor::new(Vec::new());
+            field.write_field(&mut cursor).await.unwrap();
+            assert_eq!(cursor.get_ref().as_slice(), &value.to_be_bytes());
+
+            cursor.set_position(0);
+            let read = UuidField::read_field(&mut cursor).await.unwrap();
+            assert_eq!(read, field);
+        });
+    }
+}
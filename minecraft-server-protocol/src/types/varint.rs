@@ -47,7 +47,7 @@ impl Field for VarIntField {
         if n > 5 {
             Err(PacketError::BadVarInt(n))
         } else {
-            let value = unsafe { std::mem::transmute(out) };
+            let value = out as i32;
             Ok(Self {
                 value,
                 bytes,
@@ -0,0 +1,157 @@
+use crate::types::field::Field;
+use crate::types::{PacketError, PacketResult};
+use async_std::io::prelude::*;
+use async_trait::async_trait;
+
+const MAX_BYTES: usize = 10;
+
+#[derive(Debug, Copy, Clone)]
+pub struct VarLongField {
+    value: i64,
+    bytes: [u8; MAX_BYTES],
+    byte_count: u8,
+}
+
+#[async_trait]
+impl Field for VarLongField {
+    type Displayable = i64;
+
+    fn value(&self) -> &Self::Displayable {
+        &self.value
+    }
+
+    fn size(&self) -> usize {
+        self.byte_count as usize
+    }
+
+    async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+        let mut out = 0u64;
+        let mut bytes = [0u8; MAX_BYTES];
+
+        for n in 0..MAX_BYTES {
+            let byte = {
+                let mut buf = [0u8; 1];
+                r.read_exact(&mut buf).await.map_err(PacketError::Io)?;
+                buf[0]
+            };
+            bytes[n] = byte;
+            out |= ((byte & 0x7f) as u64) << (7 * n as u32);
+
+            if byte & 0x80 == 0 {
+                return Ok(Self {
+                    value: out as i64,
+                    bytes,
+                    byte_count: n as u8 + 1,
+                });
+            }
+        }
+
+        Err(PacketError::BadVarLong(MAX_BYTES + 1))
+    }
+
+    async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+        w.write_all(self.bytes()).await.map_err(PacketError::Io)
+    }
+}
+
+impl VarLongField {
+    pub fn new(value: i64) -> Self {
+        let mut n = 0;
+        let mut val = value as u64;
+        let mut bytes = [0u8; MAX_BYTES];
+
+        loop {
+            let mut next: u8 = (val & 0x7f) as u8;
+
+            val >>= 7;
+            if val > 0 {
+                next |= 0x80;
+            }
+
+            bytes[n] = next;
+
+            n += 1;
+            if val == 0 {
+                break;
+            }
+        }
+
+        Self {
+            value,
+            bytes,
+            byte_count: n as u8,
+        }
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.byte_count as usize]
+    }
+}
+
+impl From<i64> for VarLongField {
+    fn from(i: i64) -> Self {
+        Self::new(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_std::io::Cursor;
+    use futures_await_test::async_test;
+
+    use crate::types::field::Field;
+    use crate::types::{PacketError, VarLongField};
+
+    async fn assert_varlong(val: i64, bytes: &[u8]) {
+        let varlong = VarLongField::from(val);
+        assert_eq!(varlong.value(), val);
+        assert_eq!(varlong.bytes(), bytes);
+        assert_eq!(varlong.size(), bytes.len());
+
+        let mut cursor = Cursor::new(Vec::new());
+        varlong.write_field(&mut cursor).await.unwrap();
+        assert_eq!(cursor.get_ref().as_slice(), bytes);
+
+        cursor.set_position(0);
+        let decoded = VarLongField::read_field(&mut cursor).await.unwrap();
+        assert_eq!(decoded.value(), val);
+        assert_eq!(decoded.bytes(), bytes);
+    }
+
+    #[async_test]
+    async fn varlong() {
+        assert_varlong(0, &[0x00]).await;
+        assert_varlong(1, &[0x01]).await;
+        assert_varlong(127, &[0x7f]).await;
+        assert_varlong(128, &[0x80, 0x01]).await;
+        assert_varlong(2_147_483_647, &[0xff, 0xff, 0xff, 0xff, 0x07]).await;
+        assert_varlong(
+            9_223_372_036_854_775_807,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+        )
+        .await;
+        assert_varlong(
+            -1,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+        )
+        .await;
+        assert_varlong(
+            -9_223_372_036_854_775_808,
+            &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01],
+        )
+        .await;
+    }
+
+    #[async_test]
+    async fn varlong_too_long() {
+        let mut cursor = Cursor::new(vec![0xffu8; 11]);
+        assert!(matches!(
+            VarLongField::read_field(&mut cursor).await,
+            Err(PacketError::BadVarLong(11))
+        ));
+    }
+}
@@ -4,18 +4,17 @@
 use crate::types::*;
 use async_std::io::{prelude::*, Cursor};
 use async_trait::async_trait;
-use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+use minecraft_server_protocol_derive::{ClientBoundPacket, Field, ServerBoundPacket};
 use std::fmt::{Display, Formatter};
 
 pub mod client {
     use super::*;
 
-    /* TODO incomplete struct SpawnEntity
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x00]
     pub struct SpawnEntity {
         pub entity_id: VarIntField,
-        // TODO pub object_uuid: Uuid,
+        pub object_uuid: UuidField,
         pub r#type: ByteField,
         pub x: DoubleField,
         pub y: DoubleField,
@@ -26,7 +25,7 @@ pub mod client {
         pub velocity_x: ShortField,
         pub velocity_y: ShortField,
         pub velocity_z: ShortField,
-    }*/
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x01]
@@ -48,12 +47,11 @@ pub mod client {
         pub z: DoubleField,
     }
 
-    /* TODO incomplete struct SpawnEntityLiving
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x03]
     pub struct SpawnEntityLiving {
         pub entity_id: VarIntField,
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub r#type: VarIntField,
         pub x: DoubleField,
         pub y: DoubleField,
@@ -64,33 +62,31 @@ pub mod client {
         pub velocity_x: ShortField,
         pub velocity_y: ShortField,
         pub velocity_z: ShortField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<335>,
+    }
 
-    /* TODO incomplete struct SpawnEntityPainting
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x04]
     pub struct SpawnEntityPainting {
         pub entity_id: VarIntField,
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub title: StringField,
         pub location: PositionField<335>,
         pub direction: UByteField,
-    }*/
+    }
 
-    /* TODO incomplete struct NamedEntitySpawn
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x05]
     pub struct NamedEntitySpawn {
         pub entity_id: VarIntField,
-        // TODO pub player_uuid: Uuid,
+        pub player_uuid: UuidField,
         pub x: DoubleField,
         pub y: DoubleField,
         pub z: DoubleField,
         pub yaw: ByteField,
         pub pitch: ByteField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<335>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x06]
@@ -99,12 +95,17 @@ pub mod client {
         pub animation: UByteField,
     }
 
-    /* TODO incomplete struct Statistics
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x07]
     pub struct Statistics {
-        // TODO pub entries: Array<Container>,
-    }*/
+        pub entries: PrefixedArrayField<VarIntField, StatisticsEntry>,
+    }
+
+    #[derive(Field)]
+    pub struct StatisticsEntry {
+        pub name: StringField,
+        pub value: VarIntField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x08]
@@ -114,14 +115,13 @@ pub mod client {
         pub destroy_stage: ByteField,
     }
 
-    /* TODO incomplete struct TileEntityData
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x09]
     pub struct TileEntityData {
         pub location: PositionField<335>,
         pub action: UByteField,
-        // TODO pub nbt_data: OptionalNbt,
-    }*/
+        pub nbt_data: OptionalNbtField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0a]
@@ -139,30 +139,40 @@ pub mod client {
         pub r#type: VarIntField,
     }
 
-    /* TODO incomplete struct BossBar
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0c]
     pub struct BossBar {
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub title: BossBarTitle,
+        #[depends_on = "action"]
         pub health: BossBarHealth,
+        #[depends_on = "action"]
         pub color: BossBarColor,
+        #[depends_on = "action"]
         pub dividers: BossBarDividers,
+        #[depends_on = "action"]
         pub flags: BossBarFlags,
-    }*/
+    }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarTitle {
         /// action=0
-        One(StringField),
+        Action0(ChatField<335>),
         /// action=3
-        Two(StringField),
+        Action3(ChatField<335>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarTitle {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarTitle") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -175,28 +185,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarTitle"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarTitle {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarTitle",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarHealth {
         /// action=0
-        One(FloatField),
+        Action0(FloatField),
         /// action=2
-        Two(FloatField),
+        Action2(FloatField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarHealth {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarHealth") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -209,28 +279,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarHealth"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarHealth {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarHealth",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarColor {
         /// action=0
-        One(VarIntField),
+        Action0(VarIntField),
         /// action=4
-        Two(VarIntField),
+        Action4(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarColor {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarColor") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -243,28 +373,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarColor"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarColor {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarColor",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarDividers {
         /// action=0
-        One(VarIntField),
+        Action0(VarIntField),
         /// action=4
-        Two(VarIntField),
+        Action4(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarDividers {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarDividers") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -277,28 +467,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarDividers"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarDividers {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarDividers",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarFlags {
         /// action=0
-        One(UByteField),
+        Action0(UByteField),
         /// action=5
-        Two(UByteField),
+        Action5(UByteField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarFlags {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarFlags") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action5(value) => write!(f, "Action5({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -311,17 +561,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action5(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarFlags"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarFlags {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                5 => Self::Action5(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                5 => "Action5",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action5(_) => "Action5",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarFlags",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0d]
     pub struct Difficulty {
@@ -337,18 +641,24 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0f]
     pub struct Chat {
-        pub message: StringField,
+        pub message: ChatField<335>,
         pub position: ByteField,
     }
 
-    /* TODO incomplete struct MultiBlockChange
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x10]
     pub struct MultiBlockChange {
         pub chunk_x: IntField,
         pub chunk_z: IntField,
-        // TODO pub records: Array<Container>,
-    }*/
+        pub records: PrefixedArrayField<VarIntField, MultiBlockChangeRecord>,
+    }
+
+    #[derive(Field)]
+    pub struct MultiBlockChangeRecord {
+        pub horizontal_pos: UByteField,
+        pub y: UByteField,
+        pub block_id: VarIntField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x11]
@@ -369,20 +679,26 @@ pub mod client {
     pub struct OpenWindow {
         pub window_id: UByteField,
         pub inventory_type: StringField,
-        pub window_title: StringField,
+        pub window_title: ChatField<335>,
         pub slot_count: UByteField,
+        #[depends_on = "inventory_type"]
         pub entity_id: OpenWindowEntityId,
     }
 
+    /// The variant must be the one inventoryType selects, or writing the packet fails
     pub enum OpenWindowEntityId {
         /// inventoryType=EntityHorse
-        One(IntField),
+        EntityHorse(IntField),
+        /// Any other inventoryType
         NotPresent,
     }
 
     impl Display for OpenWindowEntityId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("OpenWindowEntityId") // TODO better display for autogenerated types
+            match self {
+                Self::EntityHorse(value) => write!(f, "EntityHorse({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -395,24 +711,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::EntityHorse(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("OpenWindowEntityId"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::EntityHorse(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for OpenWindowEntityId {
+        type Dependency = String;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.as_str() {
+                "EntityHorse" => Self::EntityHorse(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.as_str() {
+                "EntityHorse" => "EntityHorse",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::EntityHorse(_) => "EntityHorse",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "OpenWindowEntityId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::EntityHorse(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    /* TODO incomplete struct WindowItems
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x14]
     pub struct WindowItems {
         pub window_id: UByteField,
-        // TODO pub items: Array<Slot>,
-    }*/
+        pub items: PrefixedArrayField<ShortField, SlotField<335>>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x15]
@@ -422,14 +785,13 @@ pub mod client {
         pub value: ShortField,
     }
 
-    /* TODO incomplete struct SetSlot
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x16]
     pub struct SetSlot {
         pub window_id: ByteField,
         pub slot: ShortField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<335>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x17]
@@ -460,7 +822,7 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1a]
     pub struct KickDisconnect {
-        pub reason: StringField,
+        pub reason: ChatField<335>,
     }
 
     #[derive(ClientBoundPacket)]
@@ -470,7 +832,6 @@ pub mod client {
         pub entity_status: ByteField,
     }
 
-    /* TODO incomplete struct Explosion
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1c]
     pub struct Explosion {
@@ -478,11 +839,18 @@ pub mod client {
         pub y: FloatField,
         pub z: FloatField,
         pub radius: FloatField,
-        // TODO pub affected_block_offsets: Array<Container>,
+        pub affected_block_offsets: PrefixedArrayField<IntField, ExplosionAffectedBlockOffset>,
         pub player_motion_x: FloatField,
         pub player_motion_y: FloatField,
         pub player_motion_z: FloatField,
-    }*/
+    }
+
+    #[derive(Field)]
+    pub struct ExplosionAffectedBlockOffset {
+        pub x: ByteField,
+        pub y: ByteField,
+        pub z: ByteField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1d]
@@ -537,22 +905,30 @@ pub mod client {
         pub offset_z: FloatField,
         pub particle_data: FloatField,
         pub particles: IntField,
+        #[depends_on = "particle_id"]
         pub data: WorldParticlesData,
     }
 
+    /// The variant must be the one particleId selects, or writing the packet fails
     pub enum WorldParticlesData {
         /// particleId=36
-        One([VarIntField; 2]),
+        ParticleId36(FixedArrayField<VarIntField, 2>),
         /// particleId=37
-        Two([VarIntField; 1]),
+        ParticleId37(FixedArrayField<VarIntField, 1>),
         /// particleId=38
-        Three([VarIntField; 1]),
+        ParticleId38(FixedArrayField<VarIntField, 1>),
+        /// Any other particleId
         NotPresent,
     }
 
     impl Display for WorldParticlesData {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldParticlesData") // TODO better display for autogenerated types
+            match self {
+                Self::ParticleId36(value) => write!(f, "ParticleId36({})", value.value()),
+                Self::ParticleId37(value) => write!(f, "ParticleId37({})", value.value()),
+                Self::ParticleId38(value) => write!(f, "ParticleId38({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -565,17 +941,77 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::ParticleId36(value) => value.size(),
+                Self::ParticleId37(value) => value.size(),
+                Self::ParticleId38(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldParticlesData"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::ParticleId36(value) => value.write_field(w).await,
+                Self::ParticleId37(value) => value.write_field(w).await,
+                Self::ParticleId38(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for WorldParticlesData {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                36 => Self::ParticleId36(Field::read_field(r).await?),
+                37 => Self::ParticleId37(Field::read_field(r).await?),
+                38 => Self::ParticleId38(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                36 => "ParticleId36",
+                37 => "ParticleId37",
+                38 => "ParticleId38",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::ParticleId36(_) => "ParticleId36",
+                Self::ParticleId37(_) => "ParticleId37",
+                Self::ParticleId38(_) => "ParticleId38",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldParticlesData",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::ParticleId36(value) => value.write_field(w).await,
+                Self::ParticleId37(value) => value.write_field(w).await,
+                Self::ParticleId38(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x23]
     pub struct Login {
@@ -588,30 +1024,45 @@ pub mod client {
         pub reduced_debug_info: BoolField,
     }
 
-    /* TODO incomplete struct Map
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x24]
     pub struct Map {
         pub item_damage: VarIntField,
         pub scale: ByteField,
         pub tracking_position: BoolField,
-        // TODO pub icons: Array<Container>,
+        pub icons: PrefixedArrayField<VarIntField, MapIcon>,
         pub columns: ByteField,
+        #[depends_on = "columns"]
         pub rows: MapRows,
+        #[depends_on = "columns"]
         pub x: MapX,
+        #[depends_on = "columns"]
         pub y: MapY,
+        #[depends_on = "columns"]
         pub data: MapData,
-    }*/
+    }
 
+    #[derive(Field)]
+    pub struct MapIcon {
+        pub direction_and_type: ByteField,
+        pub x: ByteField,
+        pub y: ByteField,
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapRows {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapRows {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapRows") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -624,26 +1075,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapRows"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapRows {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapRows",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapX {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapX") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -656,26 +1160,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapX {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapY {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapY {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapY") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -688,26 +1245,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapY"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapY {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapY",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapData {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(VarIntThenByteArrayField),
     }
 
     impl Display for MapData {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapData") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -720,17 +1330,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapData"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for MapData {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapData",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x25]
     pub struct Entity {
@@ -796,21 +1454,30 @@ pub mod client {
     #[packet_id = 0x2c]
     pub struct CombatEvent {
         pub event: VarIntField,
+        #[depends_on = "event"]
         pub duration: CombatEventDuration,
+        #[depends_on = "event"]
         pub player_id: CombatEventPlayerId,
+        #[depends_on = "event"]
         pub entity_id: CombatEventEntityId,
+        #[depends_on = "event"]
         pub message: CombatEventMessage,
     }
 
+    /// The variant must be the one event selects, or writing the packet fails
     pub enum CombatEventDuration {
         /// event=1
-        One(VarIntField),
+        Event1(VarIntField),
+        /// Any other event
         NotPresent,
     }
 
     impl Display for CombatEventDuration {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventDuration") // TODO better display for autogenerated types
+            match self {
+                Self::Event1(value) => write!(f, "Event1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -823,26 +1490,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Event1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CombatEventDuration"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for CombatEventDuration {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Event1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Event1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event1(_) => "Event1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventDuration",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
     pub enum CombatEventPlayerId {
         /// event=2
-        One(VarIntField),
+        Event2(VarIntField),
+        /// Any other event
         NotPresent,
     }
 
     impl Display for CombatEventPlayerId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventPlayerId") // TODO better display for autogenerated types
+            match self {
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -855,28 +1575,82 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CombatEventPlayerId"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for CombatEventPlayerId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventPlayerId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
     pub enum CombatEventEntityId {
         /// event=1
-        One(IntField),
+        Event1(IntField),
         /// event=2
-        Two(IntField),
+        Event2(IntField),
+        /// Any other event
         NotPresent,
     }
 
     impl Display for CombatEventEntityId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventEntityId") // TODO better display for autogenerated types
+            match self {
+                Self::Event1(value) => write!(f, "Event1({})", value.value()),
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -889,26 +1663,85 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Event1(value) => value.size(),
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CombatEventEntityId"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for CombatEventEntityId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Event1(Field::read_field(r).await?),
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Event1",
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event1(_) => "Event1",
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventEntityId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
     pub enum CombatEventMessage {
         /// event=2
-        One(StringField),
+        Event2(ChatField<335>),
+        /// Any other event
         NotPresent,
     }
 
     impl Display for CombatEventMessage {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventMessage") // TODO better display for autogenerated types
+            match self {
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -921,24 +1754,553 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CombatEventMessage"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for CombatEventMessage {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventMessage",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    /* TODO incomplete struct PlayerInfo
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x2d]
     pub struct PlayerInfo {
         pub action: VarIntField,
-        // TODO pub data: Array<Container>,
-    }*/
+        #[depends_on = "action"]
+        pub data: PrefixedArrayField<VarIntField, PlayerInfoDataEntry>,
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryName {
+        /// action=0
+        Action0(StringField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryName {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryName {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("PlayerInfoDataEntryName"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryName {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct PlayerInfoDataEntryPropertiesAction0Entry {
+        pub name: StringField,
+        pub value: StringField,
+        pub signature: OptionField<StringField>,
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryProperties {
+        /// action=0
+        Action0(PrefixedArrayField<VarIntField, PlayerInfoDataEntryPropertiesAction0Entry>),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryProperties {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryProperties {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryProperties",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryProperties {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryProperties",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryGamemode {
+        /// action=0
+        Action0(VarIntField),
+        /// action=1
+        Action1(VarIntField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryGamemode {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryGamemode {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryGamemode",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryGamemode {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1(_) => "Action1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryGamemode",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryPing {
+        /// action=0
+        Action0(VarIntField),
+        /// action=2
+        Action2(VarIntField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryPing {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryPing {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("PlayerInfoDataEntryPing"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryPing {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryPing",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryDisplayName {
+        /// action=0
+        Action0(OptionField<ChatField<335>>),
+        /// action=3
+        Action3(OptionField<ChatField<335>>),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryDisplayName {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryDisplayName {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryDisplayName",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryDisplayName {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryDisplayName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    #[parent_dependency = "i32"]
+    pub struct PlayerInfoDataEntry {
+        pub uuid: UuidField,
+        #[depends_on = ".."]
+        pub name: PlayerInfoDataEntryName,
+        #[depends_on = ".."]
+        pub properties: PlayerInfoDataEntryProperties,
+        #[depends_on = ".."]
+        pub gamemode: PlayerInfoDataEntryGamemode,
+        #[depends_on = ".."]
+        pub ping: PlayerInfoDataEntryPing,
+        #[depends_on = ".."]
+        pub display_name: PlayerInfoDataEntryDisplayName,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x2e]
@@ -966,18 +2328,24 @@ pub mod client {
         pub crafting_book_open: BoolField,
         pub filtering_craftable: BoolField,
         pub recipes_1: PrefixedArrayField<VarIntField, VarIntField>,
+        #[depends_on = "action"]
         pub recipes_2: UnlockRecipesRecipes2,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum UnlockRecipesRecipes2 {
         /// action=0
-        One(PrefixedArrayField<VarIntField, VarIntField>),
+        Action0(PrefixedArrayField<VarIntField, VarIntField>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for UnlockRecipesRecipes2 {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UnlockRecipesRecipes2") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -990,17 +2358,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UnlockRecipesRecipes2"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for UnlockRecipesRecipes2 {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UnlockRecipesRecipes2",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x31]
     pub struct EntityDestroy {
@@ -1037,37 +2453,50 @@ pub mod client {
         pub head_yaw: ByteField,
     }
 
-    /* TODO incomplete struct SelectAdvancementTab
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x36]
     pub struct SelectAdvancementTab {
-        // TODO pub id: Option,
-    }*/
+        pub id: OptionField<StringField>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x37]
     pub struct WorldBorder {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub radius: WorldBorderRadius,
+        #[depends_on = "action"]
         pub x: WorldBorderX,
+        #[depends_on = "action"]
         pub z: WorldBorderZ,
+        #[depends_on = "action"]
         pub old_radius: WorldBorderOldRadius,
+        #[depends_on = "action"]
         pub new_radius: WorldBorderNewRadius,
+        #[depends_on = "action"]
         pub speed: WorldBorderSpeed,
+        #[depends_on = "action"]
         pub portal_boundary: WorldBorderPortalBoundary,
+        #[depends_on = "action"]
         pub warning_time: WorldBorderWarningTime,
+        #[depends_on = "action"]
         pub warning_blocks: WorldBorderWarningBlocks,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderRadius {
         /// action=0
-        One(DoubleField),
+        Action0(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1080,28 +2509,82 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderX {
         /// action=2
-        One(DoubleField),
+        Action2(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderX") // TODO better display for autogenerated types
+            match self {
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1114,28 +2597,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action2(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderX {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Action2(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Action2",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action2(_) => "Action2",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderZ {
         /// action=2
-        One(DoubleField),
+        Action2(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderZ {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderZ") // TODO better display for autogenerated types
+            match self {
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1148,28 +2691,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action2(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderZ"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderZ {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Action2(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Action2",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action2(_) => "Action2",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderZ",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderOldRadius {
         /// action=1
-        One(DoubleField),
+        Action1(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderOldRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderOldRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1182,28 +2785,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderOldRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderOldRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderOldRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderNewRadius {
         /// action=1
-        One(DoubleField),
+        Action1(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderNewRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderNewRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1216,28 +2879,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderNewRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderNewRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderNewRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderSpeed {
         /// action=1
-        One(VarIntField),
+        Action1(VarIntField),
         /// action=3
-        Two(VarIntField),
+        Action3(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderSpeed {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderSpeed") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1250,26 +2973,85 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderSpeed"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderSpeed {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderSpeed",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderPortalBoundary {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderPortalBoundary {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderPortalBoundary") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1282,28 +3064,82 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderPortalBoundary"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderPortalBoundary {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderPortalBoundary",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderWarningTime {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
         /// action=4
-        Two(VarIntField),
+        Action4(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderWarningTime {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderWarningTime") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1316,28 +3152,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderWarningTime"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderWarningTime {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderWarningTime",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderWarningBlocks {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
         /// action=5
-        Two(VarIntField),
+        Action5(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderWarningBlocks {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderWarningBlocks") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::Action5(value) => write!(f, "Action5({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1350,17 +3246,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::Action5(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderWarningBlocks"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderWarningBlocks {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                5 => Self::Action5(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                5 => "Action5",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::Action5(_) => "Action5",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderWarningBlocks",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x38]
     pub struct Camera {
@@ -1380,13 +3330,12 @@ pub mod client {
         pub name: StringField,
     }
 
-    /* TODO incomplete struct EntityMetadata
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3b]
     pub struct EntityMetadata {
         pub entity_id: VarIntField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<335>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3c]
@@ -1404,14 +3353,13 @@ pub mod client {
         pub velocity_z: ShortField,
     }
 
-    /* TODO incomplete struct EntityEquipment
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3e]
     pub struct EntityEquipment {
         pub entity_id: VarIntField,
         pub slot: VarIntField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<335>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3f]
@@ -1434,21 +3382,29 @@ pub mod client {
     pub struct ScoreboardObjective {
         pub name: StringField,
         pub action: ByteField,
+        #[depends_on = "action"]
         pub display_text: ScoreboardObjectiveDisplayText,
+        #[depends_on = "action"]
         pub r#type: ScoreboardObjectiveType,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardObjectiveDisplayText {
         /// action=0
-        One(StringField),
+        Action0(StringField),
         /// action=2
-        Two(StringField),
+        Action2(StringField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for ScoreboardObjectiveDisplayText {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardObjectiveDisplayText") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1461,28 +3417,90 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "ScoreboardObjectiveDisplayText",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for ScoreboardObjectiveDisplayText {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardObjectiveDisplayText",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardObjectiveType {
         /// action=0
-        One(StringField),
+        Action0(StringField),
         /// action=2
-        Two(StringField),
+        Action2(StringField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for ScoreboardObjectiveType {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardObjectiveType") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1495,17 +3513,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("ScoreboardObjectiveType"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for ScoreboardObjectiveType {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardObjectiveType",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x42]
     pub struct SetPassengers {
@@ -1518,27 +3590,41 @@ pub mod client {
     pub struct Teams {
         pub team: StringField,
         pub mode: ByteField,
+        #[depends_on = "mode"]
         pub name: TeamsName,
+        #[depends_on = "mode"]
         pub prefix: TeamsPrefix,
+        #[depends_on = "mode"]
         pub suffix: TeamsSuffix,
+        #[depends_on = "mode"]
         pub friendly_fire: TeamsFriendlyFire,
+        #[depends_on = "mode"]
         pub name_tag_visibility: TeamsNameTagVisibility,
+        #[depends_on = "mode"]
         pub collision_rule: TeamsCollisionRule,
+        #[depends_on = "mode"]
         pub color: TeamsColor,
+        #[depends_on = "mode"]
         pub players: TeamsPlayers,
     }
 
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsName {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsName {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsName") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1551,28 +3637,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsName"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsName {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsPrefix {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsPrefix {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsPrefix") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1585,28 +3731,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsPrefix"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsPrefix {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsPrefix",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsSuffix {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsSuffix {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsSuffix") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1619,28 +3825,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsSuffix"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsSuffix {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsSuffix",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsFriendlyFire {
         /// mode=0
-        One(ByteField),
+        Mode0(ByteField),
         /// mode=2
-        Two(ByteField),
+        Mode2(ByteField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsFriendlyFire {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsFriendlyFire") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1653,28 +3919,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsFriendlyFire"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsFriendlyFire {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsFriendlyFire",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsNameTagVisibility {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsNameTagVisibility {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsNameTagVisibility") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1687,28 +4013,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsNameTagVisibility"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsNameTagVisibility {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsNameTagVisibility",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsCollisionRule {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsCollisionRule {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsCollisionRule") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1721,28 +4107,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsCollisionRule"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsCollisionRule {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsCollisionRule",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsColor {
         /// mode=0
-        One(ByteField),
+        Mode0(ByteField),
         /// mode=2
-        Two(ByteField),
+        Mode2(ByteField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsColor {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsColor") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1755,30 +4201,91 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsColor"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsColor {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsColor",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsPlayers {
         /// mode=0
-        One(PrefixedArrayField<VarIntField, StringField>),
+        Mode0(PrefixedArrayField<VarIntField, StringField>),
         /// mode=3
-        Two(PrefixedArrayField<VarIntField, StringField>),
+        Mode3(PrefixedArrayField<VarIntField, StringField>),
         /// mode=4
-        Three(PrefixedArrayField<VarIntField, StringField>),
+        Mode4(PrefixedArrayField<VarIntField, StringField>),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsPlayers {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsPlayers") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode3(value) => write!(f, "Mode3({})", value.value()),
+                Self::Mode4(value) => write!(f, "Mode4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1791,35 +4298,101 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode3(value) => value.size(),
+                Self::Mode4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsPlayers"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode3(value) => value.write_field(w).await,
+                Self::Mode4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for TeamsPlayers {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                3 => Self::Mode3(Field::read_field(r).await?),
+                4 => Self::Mode4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                3 => "Mode3",
+                4 => "Mode4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode3(_) => "Mode3",
+                Self::Mode4(_) => "Mode4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsPlayers",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode3(value) => value.write_field(w).await,
+                Self::Mode4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x44]
     pub struct ScoreboardScore {
         pub item_name: StringField,
         pub action: ByteField,
         pub score_name: StringField,
+        #[depends_on = "action"]
         pub value: ScoreboardScoreValue,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardScoreValue {
-        // TODO Default(...)
         /// action=1
-        One,
+        Action1,
+        /// Any other action
+        Default(VarIntField),
     }
 
     impl Display for ScoreboardScoreValue {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardScoreValue") // TODO better display for autogenerated types
+            match self {
+                Self::Action1 => f.write_str("Action1"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -1832,17 +4405,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("ScoreboardScoreValue"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for ScoreboardScoreValue {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Action1 => "Action1",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardScoreValue",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x45]
     pub struct SpawnPosition {
@@ -1860,25 +4481,36 @@ pub mod client {
     #[packet_id = 0x47]
     pub struct Title {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub text: TitleText,
+        #[depends_on = "action"]
         pub fade_in: TitleFadeIn,
+        #[depends_on = "action"]
         pub stay: TitleStay,
+        #[depends_on = "action"]
         pub fade_out: TitleFadeOut,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleText {
         /// action=0
-        One(StringField),
+        Action0(ChatField<335>),
         /// action=1
-        Two(StringField),
+        Action1(ChatField<335>),
         /// action=2
-        Three(StringField),
+        Action2(ChatField<335>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleText {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleText") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1891,26 +4523,91 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleText"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleText {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1(_) => "Action1",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleText",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleFadeIn {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleFadeIn {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleFadeIn") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1923,26 +4620,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleFadeIn"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleFadeIn {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleFadeIn",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleStay {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleStay {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleStay") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1955,26 +4705,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleStay"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleStay {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleStay",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleFadeOut {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleFadeOut {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleFadeOut") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1987,17 +4790,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleFadeOut"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleFadeOut {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleFadeOut",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x48]
     pub struct SoundEffect {
@@ -2013,8 +4864,8 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x49]
     pub struct PlayerlistHeader {
-        pub header: StringField,
-        pub footer: StringField,
+        pub header: ChatField<335>,
+        pub footer: ChatField<335>,
     }
 
     #[derive(ClientBoundPacket)]
@@ -2037,23 +4888,234 @@ pub mod client {
         pub on_ground: BoolField,
     }
 
-    /* TODO incomplete struct Advancements
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4c]
     pub struct Advancements {
         pub reset: BoolField,
-        // TODO pub advancement_mapping: Array<Container>,
+        pub advancement_mapping:
+            PrefixedArrayField<VarIntField, AdvancementsAdvancementMappingEntry>,
         pub identifiers: PrefixedArrayField<VarIntField, StringField>,
-        // TODO pub progress_mapping: Array<Container>,
-    }*/
+        pub progress_mapping: PrefixedArrayField<VarIntField, AdvancementsProgressMappingEntry>,
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct AdvancementsAdvancementMappingEntryValueDisplayDataFlags(BitfieldField<4>);
+
+    impl AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        pub fn hidden(&self) -> bool {
+            self.0.unsigned(2, 1) != 0
+        }
+
+        pub fn set_hidden(&mut self, value: bool) {
+            self.0.set(2, 1, u64::from(value))
+        }
+
+        pub fn show_toast(&self) -> bool {
+            self.0.unsigned(1, 1) != 0
+        }
+
+        pub fn set_show_toast(&mut self, value: bool) {
+            self.0.set(1, 1, u64::from(value))
+        }
+
+        pub fn has_background_texture(&self) -> bool {
+            self.0.unsigned(0, 1) != 0
+        }
+
+        pub fn set_has_background_texture(&mut self, value: bool) {
+            self.0.set(0, 1, u64::from(value))
+        }
+    }
+
+    impl Display for AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "(hidden={}, show_toast={}, has_background_texture={})",
+                self.hidden(),
+                self.show_toast(),
+                self.has_background_texture()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl Field for AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/has_background_texture selects, or writing the packet fails
+    pub enum AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        /// flags/has_background_texture=1
+        HasBackgroundTexture(StringField),
+        /// Any other flags/has_background_texture
+        NotPresent,
+    }
+
+    impl Display for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::HasBackgroundTexture(value) => {
+                    write!(f, "HasBackgroundTexture({})", value.value())
+                }
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::HasBackgroundTexture(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::HasBackgroundTexture(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        type Dependency = AdvancementsAdvancementMappingEntryValueDisplayDataFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.has_background_texture() {
+                true => Self::HasBackgroundTexture(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.has_background_texture() {
+                true => "HasBackgroundTexture",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::HasBackgroundTexture(_) => "HasBackgroundTexture",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::HasBackgroundTexture(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValueDisplayData {
+        pub title: ChatField<335>,
+        pub description: ChatField<335>,
+        pub icon: SlotField<335>,
+        pub frame_type: VarIntField,
+        pub flags: AdvancementsAdvancementMappingEntryValueDisplayDataFlags,
+        #[depends_on = "flags"]
+        pub background_texture:
+            AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture,
+        pub x_cord: FloatField,
+        pub y_cord: FloatField,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValueCriteriaEntry {
+        pub key: StringField,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValue {
+        pub parent_id: OptionField<StringField>,
+        pub display_data: OptionField<AdvancementsAdvancementMappingEntryValueDisplayData>,
+        pub criteria:
+            PrefixedArrayField<VarIntField, AdvancementsAdvancementMappingEntryValueCriteriaEntry>,
+        pub requirements:
+            PrefixedArrayField<VarIntField, PrefixedArrayField<VarIntField, StringField>>,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntry {
+        pub key: StringField,
+        pub value: AdvancementsAdvancementMappingEntryValue,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsProgressMappingEntryValueEntry {
+        pub criterion_identifier: StringField,
+        pub criterion_progress: OptionField<LongField>,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsProgressMappingEntry {
+        pub key: StringField,
+        pub value: PrefixedArrayField<VarIntField, AdvancementsProgressMappingEntryValueEntry>,
+    }
 
-    /* TODO incomplete struct EntityUpdateAttributes
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4d]
     pub struct EntityUpdateAttributes {
         pub entity_id: VarIntField,
-        // TODO pub properties: Array<Container>,
-    }*/
+        pub properties: PrefixedArrayField<IntField, EntityUpdateAttributesProperty>,
+    }
+
+    #[derive(Field)]
+    pub struct EntityUpdateAttributesPropertyModifier {
+        pub uuid: UuidField,
+        pub amount: DoubleField,
+        pub operation: ByteField,
+    }
+
+    #[derive(Field)]
+    pub struct EntityUpdateAttributesProperty {
+        pub key: StringField,
+        pub value: DoubleField,
+        pub modifiers: PrefixedArrayField<VarIntField, EntityUpdateAttributesPropertyModifier>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4e]
@@ -2075,24 +5137,36 @@ pub mod server {
         pub teleport_id: VarIntField,
     }
 
-    /* TODO incomplete struct PrepareCraftingGrid
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x01]
     pub struct PrepareCraftingGrid {
         pub window_id: UByteField,
         pub action_number: UShortField,
-        // TODO pub return_entry: Array<Container>,
-        // TODO pub prepare_entry: Array<Container>,
-    }*/
+        pub return_entry: PrefixedArrayField<UShortField, PrepareCraftingGridReturnEntryEntry>,
+        pub prepare_entry: PrefixedArrayField<UShortField, PrepareCraftingGridPrepareEntryEntry>,
+    }
+
+    #[derive(Field)]
+    pub struct PrepareCraftingGridReturnEntryEntry {
+        pub item: SlotField<335>,
+        pub crafting_slot: UByteField,
+        pub player_slot: UByteField,
+    }
+
+    #[derive(Field)]
+    pub struct PrepareCraftingGridPrepareEntryEntry {
+        pub item: SlotField<335>,
+        pub crafting_slot: UByteField,
+        pub player_slot: UByteField,
+    }
 
-    /* TODO incomplete struct TabComplete
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x02]
     pub struct TabComplete {
         pub text: StringField,
         pub assume_command: BoolField,
-        // TODO pub looked_at_block: Option,
-    }*/
+        pub looked_at_block: OptionField<PositionField<335>>,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x03]
@@ -2132,7 +5206,6 @@ pub mod server {
         pub enchantment: ByteField,
     }
 
-    /* TODO incomplete struct WindowClick
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x08]
     pub struct WindowClick {
@@ -2141,8 +5214,8 @@ pub mod server {
         pub mouse_button: ByteField,
         pub action: ShortField,
         pub mode: ByteField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<335>,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x09]
@@ -2162,21 +5235,30 @@ pub mod server {
     pub struct UseEntity {
         pub target: VarIntField,
         pub mouse: VarIntField,
+        #[depends_on = "mouse"]
         pub x: UseEntityX,
+        #[depends_on = "mouse"]
         pub y: UseEntityY,
+        #[depends_on = "mouse"]
         pub z: UseEntityZ,
+        #[depends_on = "mouse"]
         pub hand: UseEntityHand,
     }
 
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityX {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityX") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2189,26 +5271,79 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityX {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityY {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityY {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityY") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2221,26 +5356,79 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityY"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityY {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityY",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityZ {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityZ {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityZ") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2253,28 +5441,82 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityZ"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityZ {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityZ",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityHand {
         /// mouse=0
-        One(VarIntField),
+        Mouse0(VarIntField),
         /// mouse=2
-        Two(VarIntField),
+        Mouse2(VarIntField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityHand {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityHand") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse0(value) => write!(f, "Mouse0({})", value.value()),
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2287,17 +5529,71 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse0(value) => value.size(),
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityHand"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse0(value) => value.write_field(w).await,
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for UseEntityHand {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mouse0(Field::read_field(r).await?),
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mouse0",
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse0(_) => "Mouse0",
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityHand",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse0(value) => value.write_field(w).await,
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x0c]
     pub struct KeepAlive {
@@ -2391,17 +5687,38 @@ pub mod server {
     #[packet_id = 0x17]
     pub struct CraftingBookData {
         pub r#type: VarIntField,
+        #[depends_on = "r#type"]
         pub anon: CraftingBookDataAnon,
     }
 
+    #[derive(Field)]
+    pub struct CraftingBookDataAnonType0 {
+        pub displayed_recipe: IntField,
+    }
+
+    #[derive(Field)]
+    pub struct CraftingBookDataAnonType1 {
+        pub crafting_book_open: BoolField,
+        pub crafting_filter: BoolField,
+    }
+
+    /// The variant must be the one type selects, or writing the packet fails
     pub enum CraftingBookDataAnon {
-        // TODO One(...)
-    // TODO Two(...)
+        /// type=0
+        Type0(CraftingBookDataAnonType0),
+        /// type=1
+        Type1(CraftingBookDataAnonType1),
+        /// Any other type
+        NotPresent,
     }
 
     impl Display for CraftingBookDataAnon {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CraftingBookDataAnon") // TODO better display for autogenerated types
+            match self {
+                Self::Type0(value) => write!(f, "Type0({})", value.value()),
+                Self::Type1(value) => write!(f, "Type1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2414,17 +5731,71 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Type0(value) => value.size(),
+                Self::Type1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CraftingBookDataAnon"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Type0(value) => value.write_field(w).await,
+                Self::Type1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for CraftingBookDataAnon {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Type0(Field::read_field(r).await?),
+                1 => Self::Type1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Type0",
+                1 => "Type1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Type0(_) => "Type0",
+                Self::Type1(_) => "Type1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CraftingBookDataAnon",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Type0(value) => value.write_field(w).await,
+                Self::Type1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x18]
     pub struct ResourcePackReceive {
@@ -2435,19 +5806,27 @@ pub mod server {
     #[packet_id = 0x19]
     pub struct AdvancementTab {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub tab_id: AdvancementTabTabId,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum AdvancementTabTabId {
         /// action=0
-        One(StringField),
+        Action0(StringField),
         /// action=1
-        Two,
+        Action1,
+        /// Any other action
+        NotPresent,
     }
 
     impl Display for AdvancementTabTabId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("AdvancementTabTabId") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1 => f.write_str("Action1"),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2460,30 +5839,83 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1 => 0,
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("AdvancementTabTabId"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1 => Ok(()),
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for AdvancementTabTabId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1,
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1 => "Action1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "AdvancementTabTabId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1 => Ok(()),
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1a]
     pub struct HeldItemSlot {
         pub slot_id: ShortField,
     }
 
-    /* TODO incomplete struct SetCreativeSlot
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1b]
     pub struct SetCreativeSlot {
         pub slot: ShortField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<335>,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1c]
@@ -2501,12 +5933,11 @@ pub mod server {
         pub hand: VarIntField,
     }
 
-    /* TODO incomplete struct Spectate
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1e]
     pub struct Spectate {
-        // TODO pub target: Uuid,
-    }*/
+        pub target: UuidField,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1f]
@@ -4,7 +4,7 @@
 use crate::types::*;
 use async_std::io::{prelude::*, Cursor};
 use async_trait::async_trait;
-use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+use minecraft_server_protocol_derive::{ClientBoundPacket, Field, ServerBoundPacket};
 use std::fmt::{Display, Formatter};
 
 pub mod client {
@@ -13,7 +13,7 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x00]
     pub struct Disconnect {
-        pub reason: StringField,
+        pub reason: ChatField<393>,
     }
 
     #[derive(ClientBoundPacket)]
@@ -31,6 +31,15 @@ pub mod client {
         pub username: StringField,
     }
 
+    impl Success {
+        pub fn new(uuid: UuidField, username: impl Into<String>) -> Self {
+            Self {
+                uuid: StringField::new(uuid.to_string()),
+                username: StringField::new(username.into()),
+            }
+        }
+    }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x03]
     pub struct Compress {
@@ -62,11 +71,10 @@ pub mod server {
         pub verify_token: VarIntThenByteArrayField,
     }
 
-    /* TODO incomplete struct LoginPluginResponse
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x02]
     pub struct LoginPluginResponse {
         pub message_id: VarIntField,
-        // TODO pub data: Option,
-    }*/
+        pub data: OptionField<RestOfPacketByteArrayField>,
+    }
 }
@@ -4,18 +4,17 @@
 use crate::types::*;
 use async_std::io::{prelude::*, Cursor};
 use async_trait::async_trait;
-use minecraft_server_protocol_derive::{ClientBoundPacket, ServerBoundPacket};
+use minecraft_server_protocol_derive::{ClientBoundPacket, Field, ServerBoundPacket};
 use std::fmt::{Display, Formatter};
 
 pub mod client {
     use super::*;
 
-    /* TODO incomplete struct SpawnEntity
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x00]
     pub struct SpawnEntity {
         pub entity_id: VarIntField,
-        // TODO pub object_uuid: Uuid,
+        pub object_uuid: UuidField,
         pub r#type: ByteField,
         pub x: DoubleField,
         pub y: DoubleField,
@@ -26,7 +25,7 @@ pub mod client {
         pub velocity_x: ShortField,
         pub velocity_y: ShortField,
         pub velocity_z: ShortField,
-    }*/
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x01]
@@ -48,12 +47,11 @@ pub mod client {
         pub z: DoubleField,
     }
 
-    /* TODO incomplete struct SpawnEntityLiving
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x03]
     pub struct SpawnEntityLiving {
         pub entity_id: VarIntField,
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub r#type: VarIntField,
         pub x: DoubleField,
         pub y: DoubleField,
@@ -64,33 +62,31 @@ pub mod client {
         pub velocity_x: ShortField,
         pub velocity_y: ShortField,
         pub velocity_z: ShortField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<393>,
+    }
 
-    /* TODO incomplete struct SpawnEntityPainting
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x04]
     pub struct SpawnEntityPainting {
         pub entity_id: VarIntField,
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub title: VarIntField,
         pub location: PositionField<393>,
         pub direction: UByteField,
-    }*/
+    }
 
-    /* TODO incomplete struct NamedEntitySpawn
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x05]
     pub struct NamedEntitySpawn {
         pub entity_id: VarIntField,
-        // TODO pub player_uuid: Uuid,
+        pub player_uuid: UuidField,
         pub x: DoubleField,
         pub y: DoubleField,
         pub z: DoubleField,
         pub yaw: ByteField,
         pub pitch: ByteField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<393>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x06]
@@ -99,12 +95,18 @@ pub mod client {
         pub animation: UByteField,
     }
 
-    /* TODO incomplete struct Statistics
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x07]
     pub struct Statistics {
-        // TODO pub entries: Array<Container>,
-    }*/
+        pub entries: PrefixedArrayField<VarIntField, StatisticsEntry>,
+    }
+
+    #[derive(Field)]
+    pub struct StatisticsEntry {
+        pub category_id: VarIntField,
+        pub statistic_id: VarIntField,
+        pub value: VarIntField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x08]
@@ -114,14 +116,13 @@ pub mod client {
         pub destroy_stage: ByteField,
     }
 
-    /* TODO incomplete struct TileEntityData
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x09]
     pub struct TileEntityData {
         pub location: PositionField<393>,
         pub action: UByteField,
-        // TODO pub nbt_data: OptionalNbt,
-    }*/
+        pub nbt_data: OptionalNbtField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0a]
@@ -139,30 +140,40 @@ pub mod client {
         pub r#type: VarIntField,
     }
 
-    /* TODO incomplete struct BossBar
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x0c]
     pub struct BossBar {
-        // TODO pub entity_uuid: Uuid,
+        pub entity_uuid: UuidField,
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub title: BossBarTitle,
+        #[depends_on = "action"]
         pub health: BossBarHealth,
+        #[depends_on = "action"]
         pub color: BossBarColor,
+        #[depends_on = "action"]
         pub dividers: BossBarDividers,
+        #[depends_on = "action"]
         pub flags: BossBarFlags,
-    }*/
+    }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarTitle {
         /// action=0
-        One(StringField),
+        Action0(ChatField<393>),
         /// action=3
-        Two(StringField),
+        Action3(ChatField<393>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for BossBarTitle {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarTitle") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -175,33 +186,1285 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("BossBarTitle"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for BossBarTitle {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarTitle",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum BossBarHealth {
         /// action=0
-        One(FloatField),
+        Action0(FloatField),
         /// action=2
-        Two(FloatField),
+        Action2(FloatField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for BossBarHealth {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for BossBarHealth {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("BossBarHealth"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for BossBarHealth {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarHealth",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum BossBarColor {
+        /// action=0
+        Action0(VarIntField),
+        /// action=4
+        Action4(VarIntField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for BossBarColor {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for BossBarColor {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("BossBarColor"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for BossBarColor {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarColor",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum BossBarDividers {
+        /// action=0
+        Action0(VarIntField),
+        /// action=4
+        Action4(VarIntField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for BossBarDividers {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for BossBarDividers {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("BossBarDividers"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for BossBarDividers {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarDividers",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum BossBarFlags {
+        /// action=0
+        Action0(UByteField),
+        /// action=5
+        Action5(UByteField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for BossBarFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action5(value) => write!(f, "Action5({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for BossBarFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action5(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("BossBarFlags"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for BossBarFlags {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                5 => Self::Action5(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                5 => "Action5",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action5(_) => "Action5",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "BossBarFlags",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x0d]
+    pub struct Difficulty {
+        pub difficulty: UByteField,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x0e]
+    pub struct Chat {
+        pub message: ChatField<393>,
+        pub position: ByteField,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x0f]
+    pub struct MultiBlockChange {
+        pub chunk_x: IntField,
+        pub chunk_z: IntField,
+        pub records: PrefixedArrayField<VarIntField, MultiBlockChangeRecord>,
+    }
+
+    #[derive(Field)]
+    pub struct MultiBlockChangeRecord {
+        pub horizontal_pos: UByteField,
+        pub y: UByteField,
+        pub block_id: VarIntField,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x10]
+    pub struct TabComplete {
+        pub transaction_id: VarIntField,
+        pub start: VarIntField,
+        pub length: VarIntField,
+        pub matches: PrefixedArrayField<VarIntField, TabCompleteMatch>,
+    }
+
+    #[derive(Field)]
+    pub struct TabCompleteMatch {
+        pub r#match: StringField,
+        pub tooltip: OptionField<ChatField<393>>,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x11]
+    pub struct DeclareCommands {
+        pub nodes: PrefixedArrayField<VarIntField, DeclareCommandsNode>,
+        pub root_index: VarIntField,
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct DeclareCommandsNodeFlags(BitfieldField<1>);
+
+    impl DeclareCommandsNodeFlags {
+        pub fn has_custom_suggestions(&self) -> bool {
+            self.0.unsigned(4, 1) != 0
+        }
+
+        pub fn set_has_custom_suggestions(&mut self, value: bool) {
+            self.0.set(4, 1, u64::from(value))
+        }
+
+        pub fn has_redirect_node(&self) -> bool {
+            self.0.unsigned(3, 1) != 0
+        }
+
+        pub fn set_has_redirect_node(&mut self, value: bool) {
+            self.0.set(3, 1, u64::from(value))
+        }
+
+        pub fn has_command(&self) -> bool {
+            self.0.unsigned(2, 1) != 0
+        }
+
+        pub fn set_has_command(&mut self, value: bool) {
+            self.0.set(2, 1, u64::from(value))
+        }
+
+        pub fn command_node_type(&self) -> u8 {
+            self.0.unsigned(0, 2) as u8
+        }
+
+        pub fn set_command_node_type(&mut self, value: u8) {
+            self.0.set(0, 2, u64::from(value))
+        }
+    }
+
+    impl Display for DeclareCommandsNodeFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "(has_custom_suggestions={}, has_redirect_node={}, has_command={}, command_node_type={})", self.has_custom_suggestions(), self.has_redirect_node(), self.has_command(), self.command_node_type())
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/has_redirect_node selects, or writing the packet fails
+    pub enum DeclareCommandsNodeRedirectNode {
+        /// flags/has_redirect_node=1
+        HasRedirectNode(VarIntField),
+        /// Any other flags/has_redirect_node
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeRedirectNode {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::HasRedirectNode(value) => write!(f, "HasRedirectNode({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeRedirectNode {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::HasRedirectNode(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeRedirectNode",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::HasRedirectNode(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeRedirectNode {
+        type Dependency = DeclareCommandsNodeFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.has_redirect_node() {
+                true => Self::HasRedirectNode(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.has_redirect_node() {
+                true => "HasRedirectNode",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::HasRedirectNode(_) => "HasRedirectNode",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeRedirectNode",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::HasRedirectNode(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags(
+        BitfieldField<1>,
+    );
+
+    impl DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags {
+        pub fn max_present(&self) -> bool {
+            self.0.unsigned(1, 1) != 0
+        }
+
+        pub fn set_max_present(&mut self, value: bool) {
+            self.0.set(1, 1, u64::from(value))
+        }
+
+        pub fn min_present(&self) -> bool {
+            self.0.unsigned(0, 1) != 0
+        }
+
+        pub fn set_min_present(&mut self, value: bool) {
+            self.0.set(0, 1, u64::from(value))
+        }
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "(max_present={}, min_present={})",
+                self.max_present(),
+                self.min_present()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/min_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin {
+        /// flags/min_present=1
+        MinPresent(DoubleField),
+        /// Any other flags/min_present
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MinPresent(value) => write!(f, "MinPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::MinPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.min_present() {
+                true => Self::MinPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.min_present() {
+                true => "MinPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MinPresent(_) => "MinPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one flags/max_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax {
+        /// flags/max_present=1
+        MaxPresent(DoubleField),
+        /// Any other flags/max_present
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MaxPresent(value) => write!(f, "MaxPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::MaxPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.max_present() {
+                true => Self::MaxPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.max_present() {
+                true => "MaxPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MaxPresent(_) => "MaxPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDouble {
+        pub flags: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleFlags,
+        #[depends_on = "flags"]
+        pub min: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMin,
+        #[depends_on = "flags"]
+        pub max: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDoubleMax,
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags(
+        BitfieldField<1>,
+    );
+
+    impl DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags {
+        pub fn max_present(&self) -> bool {
+            self.0.unsigned(1, 1) != 0
+        }
+
+        pub fn set_max_present(&mut self, value: bool) {
+            self.0.set(1, 1, u64::from(value))
+        }
+
+        pub fn min_present(&self) -> bool {
+            self.0.unsigned(0, 1) != 0
+        }
+
+        pub fn set_min_present(&mut self, value: bool) {
+            self.0.set(0, 1, u64::from(value))
+        }
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "(max_present={}, min_present={})",
+                self.max_present(),
+                self.min_present()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/min_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin {
+        /// flags/min_present=1
+        MinPresent(FloatField),
+        /// Any other flags/min_present
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MinPresent(value) => write!(f, "MinPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::MinPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.min_present() {
+                true => Self::MinPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.min_present() {
+                true => "MinPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MinPresent(_) => "MinPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one flags/max_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax {
+        /// flags/max_present=1
+        MaxPresent(FloatField),
+        /// Any other flags/max_present
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MaxPresent(value) => write!(f, "MaxPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::MaxPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.max_present() {
+                true => Self::MaxPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.max_present() {
+                true => "MaxPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MaxPresent(_) => "MaxPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloat {
+        pub flags: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatFlags,
+        #[depends_on = "flags"]
+        pub min: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMin,
+        #[depends_on = "flags"]
+        pub max: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloatMax,
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags(
+        BitfieldField<1>,
+    );
+
+    impl DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags {
+        pub fn max_present(&self) -> bool {
+            self.0.unsigned(1, 1) != 0
+        }
+
+        pub fn set_max_present(&mut self, value: bool) {
+            self.0.set(1, 1, u64::from(value))
+        }
+
+        pub fn min_present(&self) -> bool {
+            self.0.unsigned(0, 1) != 0
+        }
+
+        pub fn set_min_present(&mut self, value: bool) {
+            self.0.set(0, 1, u64::from(value))
+        }
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "(max_present={}, min_present={})",
+                self.max_present(),
+                self.min_present()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/min_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin {
+        /// flags/min_present=1
+        MinPresent(IntField),
+        /// Any other flags/min_present
+        NotPresent,
+    }
+
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MinPresent(value) => write!(f, "MinPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::MinPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.min_present() {
+                true => Self::MinPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.min_present() {
+                true => "MinPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MinPresent(_) => "MinPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MinPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one flags/max_present selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax {
+        /// flags/max_present=1
+        MaxPresent(IntField),
+        /// Any other flags/max_present
         NotPresent,
     }
 
-    impl Display for BossBarHealth {
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarHealth") // TODO better display for autogenerated types
+            match self {
+                Self::MaxPresent(value) => write!(f, "MaxPresent({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for BossBarHealth {
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -209,33 +1472,113 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::MaxPresent(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum BossBarColor {
-        /// action=0
-        One(VarIntField),
-        /// action=4
-        Two(VarIntField),
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax {
+        type Dependency = DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.max_present() {
+                true => Self::MaxPresent(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.max_present() {
+                true => "MaxPresent",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::MaxPresent(_) => "MaxPresent",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::MaxPresent(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesInteger {
+        pub flags: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerFlags,
+        #[depends_on = "flags"]
+        pub min: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMin,
+        #[depends_on = "flags"]
+        pub max: DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesIntegerMax,
+    }
+
+    /// The variant must be the one parser selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties {
+        /// parser=brigadier:bool
+        Bool,
+        /// parser=brigadier:double
+        Double(DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesDouble),
+        /// parser=brigadier:float
+        Float(DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesFloat),
+        /// parser=brigadier:integer
+        Integer(DeclareCommandsNodeExtraNodeDataCommandNodeType2PropertiesInteger),
+        /// parser=brigadier:string
+        String(VarIntField),
+        /// parser=minecraft:entity
+        Entity(ByteField),
+        /// parser=minecraft:score_holder
+        ScoreHolder(ByteField),
+        /// Any other parser
         NotPresent,
     }
 
-    impl Display for BossBarColor {
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarColor") // TODO better display for autogenerated types
+            match self {
+                Self::Bool => f.write_str("Bool"),
+                Self::Double(value) => write!(f, "Double({})", value.value()),
+                Self::Float(value) => write!(f, "Float({})", value.value()),
+                Self::Integer(value) => write!(f, "Integer({})", value.value()),
+                Self::String(value) => write!(f, "String({})", value.value()),
+                Self::Entity(value) => write!(f, "Entity({})", value.value()),
+                Self::ScoreHolder(value) => write!(f, "ScoreHolder({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for BossBarColor {
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -243,33 +1586,124 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Bool => 0,
+                Self::Double(value) => value.size(),
+                Self::Float(value) => value.size(),
+                Self::Integer(value) => value.size(),
+                Self::String(value) => value.size(),
+                Self::Entity(value) => value.size(),
+                Self::ScoreHolder(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Bool => Ok(()),
+                Self::Double(value) => value.write_field(w).await,
+                Self::Float(value) => value.write_field(w).await,
+                Self::Integer(value) => value.write_field(w).await,
+                Self::String(value) => value.write_field(w).await,
+                Self::Entity(value) => value.write_field(w).await,
+                Self::ScoreHolder(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum BossBarDividers {
-        /// action=0
-        One(VarIntField),
-        /// action=4
-        Two(VarIntField),
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties {
+        type Dependency = String;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.as_str() {
+                "brigadier:bool" => Self::Bool,
+                "brigadier:double" => Self::Double(Field::read_field(r).await?),
+                "brigadier:float" => Self::Float(Field::read_field(r).await?),
+                "brigadier:integer" => Self::Integer(Field::read_field(r).await?),
+                "brigadier:string" => Self::String(Field::read_field(r).await?),
+                "minecraft:entity" => Self::Entity(Field::read_field(r).await?),
+                "minecraft:score_holder" => Self::ScoreHolder(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.as_str() {
+                "brigadier:bool" => "Bool",
+                "brigadier:double" => "Double",
+                "brigadier:float" => "Float",
+                "brigadier:integer" => "Integer",
+                "brigadier:string" => "String",
+                "minecraft:entity" => "Entity",
+                "minecraft:score_holder" => "ScoreHolder",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Bool => "Bool",
+                Self::Double(_) => "Double",
+                Self::Float(_) => "Float",
+                Self::Integer(_) => "Integer",
+                Self::String(_) => "String",
+                Self::Entity(_) => "Entity",
+                Self::ScoreHolder(_) => "ScoreHolder",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Bool => Ok(()),
+                Self::Double(value) => value.write_field(w).await,
+                Self::Float(value) => value.write_field(w).await,
+                Self::Integer(value) => value.write_field(w).await,
+                Self::String(value) => value.write_field(w).await,
+                Self::Entity(value) => value.write_field(w).await,
+                Self::ScoreHolder(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one flags/has_custom_suggestions selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests {
+        /// flags/has_custom_suggestions=1
+        HasCustomSuggestions(StringField),
+        /// Any other flags/has_custom_suggestions
         NotPresent,
     }
 
-    impl Display for BossBarDividers {
+    impl Display for DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarDividers") // TODO better display for autogenerated types
+            match self {
+                Self::HasCustomSuggestions(value) => {
+                    write!(f, "HasCustomSuggestions({})", value.value())
+                }
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for BossBarDividers {
+    impl Field for DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -277,33 +1711,103 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::HasCustomSuggestions(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::HasCustomSuggestions(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum BossBarFlags {
-        /// action=0
-        One(UByteField),
-        /// action=5
-        Two(UByteField),
+
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests {
+        type Dependency = DeclareCommandsNodeFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.has_custom_suggestions() {
+                true => Self::HasCustomSuggestions(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.has_custom_suggestions() {
+                true => "HasCustomSuggestions",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::HasCustomSuggestions(_) => "HasCustomSuggestions",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::HasCustomSuggestions(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    #[parent_dependency = "DeclareCommandsNodeFlags"]
+    pub struct DeclareCommandsNodeExtraNodeDataCommandNodeType2 {
+        pub name: StringField,
+        pub parser: StringField,
+        #[depends_on = "parser"]
+        pub properties: DeclareCommandsNodeExtraNodeDataCommandNodeType2Properties,
+        #[depends_on = ".."]
+        pub suggests: DeclareCommandsNodeExtraNodeDataCommandNodeType2Suggests,
+    }
+
+    /// The variant must be the one flags/command_node_type selects, or writing the packet fails
+    pub enum DeclareCommandsNodeExtraNodeData {
+        /// flags/command_node_type=0
+        CommandNodeType0,
+        /// flags/command_node_type=1
+        CommandNodeType1(StringField),
+        /// flags/command_node_type=2
+        CommandNodeType2(DeclareCommandsNodeExtraNodeDataCommandNodeType2),
+        /// Any other flags/command_node_type
         NotPresent,
     }
 
-    impl Display for BossBarFlags {
+    impl Display for DeclareCommandsNodeExtraNodeData {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("BossBarFlags") // TODO better display for autogenerated types
+            match self {
+                Self::CommandNodeType0 => f.write_str("CommandNodeType0"),
+                Self::CommandNodeType1(value) => write!(f, "CommandNodeType1({})", value.value()),
+                Self::CommandNodeType2(value) => write!(f, "CommandNodeType2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for BossBarFlags {
+    impl Field for DeclareCommandsNodeExtraNodeData {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -311,57 +1815,89 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::CommandNodeType0 => 0,
+                Self::CommandNodeType1(value) => value.size(),
+                Self::CommandNodeType2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "DeclareCommandsNodeExtraNodeData",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::CommandNodeType0 => Ok(()),
+                Self::CommandNodeType1(value) => value.write_field(w).await,
+                Self::CommandNodeType2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x0d]
-    pub struct Difficulty {
-        pub difficulty: UByteField,
-    }
 
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x0e]
-    pub struct Chat {
-        pub message: StringField,
-        pub position: ByteField,
+    #[async_trait]
+    impl DependentField for DeclareCommandsNodeExtraNodeData {
+        type Dependency = DeclareCommandsNodeFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.command_node_type() {
+                0 => Self::CommandNodeType0,
+                1 => Self::CommandNodeType1(Field::read_field(r).await?),
+                2 => Self::CommandNodeType2(DependentField::read_dependent(r, dependency).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.command_node_type() {
+                0 => "CommandNodeType0",
+                1 => "CommandNodeType1",
+                2 => "CommandNodeType2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::CommandNodeType0 => "CommandNodeType0",
+                Self::CommandNodeType1(_) => "CommandNodeType1",
+                Self::CommandNodeType2(_) => "CommandNodeType2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "DeclareCommandsNodeExtraNodeData",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::CommandNodeType0 => Ok(()),
+                Self::CommandNodeType1(value) => value.write_field(w).await,
+                Self::CommandNodeType2(value) => value.write_dependent(w, dependency).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct DeclareCommandsNode {
+        pub flags: DeclareCommandsNodeFlags,
+        pub children: PrefixedArrayField<VarIntField, VarIntField>,
+        #[depends_on = "flags"]
+        pub redirect_node: DeclareCommandsNodeRedirectNode,
+        #[depends_on = "flags"]
+        pub extra_node_data: DeclareCommandsNodeExtraNodeData,
     }
 
-    /* TODO incomplete struct MultiBlockChange
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x0f]
-    pub struct MultiBlockChange {
-        pub chunk_x: IntField,
-        pub chunk_z: IntField,
-        // TODO pub records: Array<Container>,
-    }*/
-
-    /* TODO incomplete struct TabComplete
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x10]
-    pub struct TabComplete {
-        pub transaction_id: VarIntField,
-        pub start: VarIntField,
-        pub length: VarIntField,
-        // TODO pub matches: Array<Container>,
-    }*/
-
-    /* TODO incomplete struct DeclareCommands
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x11]
-    pub struct DeclareCommands {
-        // TODO pub nodes: Array<Container>,
-        pub root_index: VarIntField,
-    }*/
-
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x12]
     pub struct Transaction {
@@ -381,20 +1917,26 @@ pub mod client {
     pub struct OpenWindow {
         pub window_id: UByteField,
         pub inventory_type: StringField,
-        pub window_title: StringField,
+        pub window_title: ChatField<393>,
         pub slot_count: UByteField,
+        #[depends_on = "inventory_type"]
         pub entity_id: OpenWindowEntityId,
     }
 
+    /// The variant must be the one inventoryType selects, or writing the packet fails
     pub enum OpenWindowEntityId {
         /// inventoryType=EntityHorse
-        One(IntField),
+        EntityHorse(IntField),
+        /// Any other inventoryType
         NotPresent,
     }
 
     impl Display for OpenWindowEntityId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("OpenWindowEntityId") // TODO better display for autogenerated types
+            match self {
+                Self::EntityHorse(value) => write!(f, "EntityHorse({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -407,24 +1949,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::EntityHorse(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("OpenWindowEntityId"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::EntityHorse(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for OpenWindowEntityId {
+        type Dependency = String;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.as_str() {
+                "EntityHorse" => Self::EntityHorse(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.as_str() {
+                "EntityHorse" => "EntityHorse",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::EntityHorse(_) => "EntityHorse",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "OpenWindowEntityId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::EntityHorse(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    /* TODO incomplete struct WindowItems
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x15]
     pub struct WindowItems {
         pub window_id: UByteField,
-        // TODO pub items: Array<Slot>,
-    }*/
+        pub items: PrefixedArrayField<ShortField, SlotField<393>>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x16]
@@ -434,14 +2023,13 @@ pub mod client {
         pub value: ShortField,
     }
 
-    /* TODO incomplete struct SetSlot
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x17]
     pub struct SetSlot {
         pub window_id: ByteField,
         pub slot: ShortField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<393>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x18]
@@ -472,7 +2060,7 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1b]
     pub struct KickDisconnect {
-        pub reason: StringField,
+        pub reason: ChatField<393>,
     }
 
     #[derive(ClientBoundPacket)]
@@ -489,7 +2077,6 @@ pub mod client {
         pub nbt: NbtField,
     }
 
-    /* TODO incomplete struct Explosion
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1e]
     pub struct Explosion {
@@ -497,11 +2084,18 @@ pub mod client {
         pub y: FloatField,
         pub z: FloatField,
         pub radius: FloatField,
-        // TODO pub affected_block_offsets: Array<Container>,
+        pub affected_block_offsets: PrefixedArrayField<IntField, ExplosionAffectedBlockOffset>,
         pub player_motion_x: FloatField,
         pub player_motion_y: FloatField,
         pub player_motion_z: FloatField,
-    }*/
+    }
+
+    #[derive(Field)]
+    pub struct ExplosionAffectedBlockOffset {
+        pub x: ByteField,
+        pub y: ByteField,
+        pub z: ByteField,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x1f]
@@ -543,7 +2137,6 @@ pub mod client {
         pub global: BoolField,
     }
 
-    /* TODO incomplete struct WorldParticles
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x24]
     pub struct WorldParticles {
@@ -557,8 +2150,9 @@ pub mod client {
         pub offset_z: FloatField,
         pub particle_data: FloatField,
         pub particles: IntField,
-        // TODO pub data: ParticleData,
-    }*/
+        #[depends_on = "particle_id"]
+        pub data: ParticleDataField<393>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x25]
@@ -572,30 +2166,47 @@ pub mod client {
         pub reduced_debug_info: BoolField,
     }
 
-    /* TODO incomplete struct Map
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x26]
     pub struct Map {
         pub item_damage: VarIntField,
         pub scale: ByteField,
         pub tracking_position: BoolField,
-        // TODO pub icons: Array<Container>,
+        pub icons: PrefixedArrayField<VarIntField, MapIcon>,
         pub columns: ByteField,
+        #[depends_on = "columns"]
         pub rows: MapRows,
+        #[depends_on = "columns"]
         pub x: MapX,
+        #[depends_on = "columns"]
         pub y: MapY,
+        #[depends_on = "columns"]
         pub data: MapData,
-    }*/
+    }
+
+    #[derive(Field)]
+    pub struct MapIcon {
+        pub r#type: VarIntField,
+        pub x: ByteField,
+        pub y: ByteField,
+        pub direction: UByteField,
+        pub display_name: OptionField<ChatField<393>>,
+    }
 
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapRows {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapRows {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapRows") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -608,26 +2219,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapRows"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapRows {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapRows",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapX {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapX") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -640,26 +2304,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapX {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapY {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(ByteField),
     }
 
     impl Display for MapY {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapY") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -672,26 +2389,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapY"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for MapY {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapY",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
+        }
+    }
+
+    /// The variant must be the one columns selects, or writing the packet fails
     pub enum MapData {
-        // TODO Default(...)
         /// columns=0
-        One,
+        Columns0,
+        /// Any other columns
+        Default(VarIntThenByteArrayField),
     }
 
     impl Display for MapData {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("MapData") // TODO better display for autogenerated types
+            match self {
+                Self::Columns0 => f.write_str("Columns0"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -704,17 +2474,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Columns0 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("MapData"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for MapData {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Columns0,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Columns0",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Columns0 => "Columns0",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "MapData",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Columns0 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x27]
     pub struct Entity {
@@ -768,45 +2586,503 @@ pub mod client {
         pub location: PositionField<393>,
     }
 
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x2d]
-    pub struct CraftRecipeResponse {
-        pub window_id: ByteField,
-        pub recipe: StringField,
-    }
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x2d]
+    pub struct CraftRecipeResponse {
+        pub window_id: ByteField,
+        pub recipe: StringField,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x2e]
+    pub struct Abilities {
+        pub flags: ByteField,
+        pub flying_speed: FloatField,
+        pub walking_speed: FloatField,
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x2f]
+    pub struct CombatEvent {
+        pub event: VarIntField,
+        #[depends_on = "event"]
+        pub duration: CombatEventDuration,
+        #[depends_on = "event"]
+        pub player_id: CombatEventPlayerId,
+        #[depends_on = "event"]
+        pub entity_id: CombatEventEntityId,
+        #[depends_on = "event"]
+        pub message: CombatEventMessage,
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
+    pub enum CombatEventDuration {
+        /// event=1
+        Event1(VarIntField),
+        /// Any other event
+        NotPresent,
+    }
+
+    impl Display for CombatEventDuration {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Event1(value) => write!(f, "Event1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for CombatEventDuration {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Event1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("CombatEventDuration"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for CombatEventDuration {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Event1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Event1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event1(_) => "Event1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventDuration",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
+    pub enum CombatEventPlayerId {
+        /// event=2
+        Event2(VarIntField),
+        /// Any other event
+        NotPresent,
+    }
+
+    impl Display for CombatEventPlayerId {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for CombatEventPlayerId {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("CombatEventPlayerId"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for CombatEventPlayerId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventPlayerId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
+    pub enum CombatEventEntityId {
+        /// event=1
+        Event1(IntField),
+        /// event=2
+        Event2(IntField),
+        /// Any other event
+        NotPresent,
+    }
+
+    impl Display for CombatEventEntityId {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Event1(value) => write!(f, "Event1({})", value.value()),
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for CombatEventEntityId {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Event1(value) => value.size(),
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("CombatEventEntityId"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for CombatEventEntityId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Event1(Field::read_field(r).await?),
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Event1",
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event1(_) => "Event1",
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventEntityId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event1(value) => value.write_field(w).await,
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one event selects, or writing the packet fails
+    pub enum CombatEventMessage {
+        /// event=2
+        Event2(ChatField<393>),
+        /// Any other event
+        NotPresent,
+    }
+
+    impl Display for CombatEventMessage {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Event2(value) => write!(f, "Event2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for CombatEventMessage {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Event2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("CombatEventMessage"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for CombatEventMessage {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Event2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Event2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Event2(_) => "Event2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CombatEventMessage",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Event2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(ClientBoundPacket)]
+    #[packet_id = 0x30]
+    pub struct PlayerInfo {
+        pub action: VarIntField,
+        #[depends_on = "action"]
+        pub data: PrefixedArrayField<VarIntField, PlayerInfoDataEntry>,
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryName {
+        /// action=0
+        Action0(StringField),
+        /// Any other action
+        NotPresent,
+    }
+
+    impl Display for PlayerInfoDataEntryName {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for PlayerInfoDataEntryName {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency("PlayerInfoDataEntryName"))
+        }
 
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x2e]
-    pub struct Abilities {
-        pub flags: ByteField,
-        pub flying_speed: FloatField,
-        pub walking_speed: FloatField,
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
     }
 
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x2f]
-    pub struct CombatEvent {
-        pub event: VarIntField,
-        pub duration: CombatEventDuration,
-        pub player_id: CombatEventPlayerId,
-        pub entity_id: CombatEventEntityId,
-        pub message: CombatEventMessage,
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryName {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct PlayerInfoDataEntryPropertiesAction0Entry {
+        pub name: StringField,
+        pub value: StringField,
+        pub signature: OptionField<StringField>,
     }
 
-    pub enum CombatEventDuration {
-        /// event=1
-        One(VarIntField),
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryProperties {
+        /// action=0
+        Action0(PrefixedArrayField<VarIntField, PlayerInfoDataEntryPropertiesAction0Entry>),
+        /// Any other action
         NotPresent,
     }
 
-    impl Display for CombatEventDuration {
+    impl Display for PlayerInfoDataEntryProperties {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventDuration") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for CombatEventDuration {
+    impl Field for PlayerInfoDataEntryProperties {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -814,31 +3090,89 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryProperties",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum CombatEventPlayerId {
-        /// event=2
-        One(VarIntField),
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryProperties {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryProperties",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryGamemode {
+        /// action=0
+        Action0(VarIntField),
+        /// action=1
+        Action1(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
-    impl Display for CombatEventPlayerId {
+    impl Display for PlayerInfoDataEntryGamemode {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventPlayerId") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for CombatEventPlayerId {
+    impl Field for PlayerInfoDataEntryGamemode {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -846,33 +3180,95 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryGamemode",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum CombatEventEntityId {
-        /// event=1
-        One(IntField),
-        /// event=2
-        Two(IntField),
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryGamemode {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1(_) => "Action1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryGamemode",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryPing {
+        /// action=0
+        Action0(VarIntField),
+        /// action=2
+        Action2(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
-    impl Display for CombatEventEntityId {
+    impl Display for PlayerInfoDataEntryPing {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventEntityId") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for CombatEventEntityId {
+    impl Field for PlayerInfoDataEntryPing {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -880,31 +3276,93 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("PlayerInfoDataEntryPing"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    pub enum CombatEventMessage {
-        /// event=2
-        One(StringField),
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryPing {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryPing",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
+    pub enum PlayerInfoDataEntryDisplayName {
+        /// action=0
+        Action0(OptionField<ChatField<393>>),
+        /// action=3
+        Action3(OptionField<ChatField<393>>),
+        /// Any other action
         NotPresent,
     }
 
-    impl Display for CombatEventMessage {
+    impl Display for PlayerInfoDataEntryDisplayName {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CombatEventMessage") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
     #[async_trait]
-    impl Field for CombatEventMessage {
+    impl Field for PlayerInfoDataEntryDisplayName {
         type Displayable = Self;
 
         fn value(&self) -> &Self::Displayable {
@@ -912,24 +3370,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "PlayerInfoDataEntryDisplayName",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
-    /* TODO incomplete struct PlayerInfo
-    #[derive(ClientBoundPacket)]
-    #[packet_id = 0x30]
-    pub struct PlayerInfo {
-        pub action: VarIntField,
-        // TODO pub data: Array<Container>,
-    }*/
+
+    #[async_trait]
+    impl DependentField for PlayerInfoDataEntryDisplayName {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "PlayerInfoDataEntryDisplayName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    #[parent_dependency = "i32"]
+    pub struct PlayerInfoDataEntry {
+        pub uuid: UuidField,
+        #[depends_on = ".."]
+        pub name: PlayerInfoDataEntryName,
+        #[depends_on = ".."]
+        pub properties: PlayerInfoDataEntryProperties,
+        #[depends_on = ".."]
+        pub gamemode: PlayerInfoDataEntryGamemode,
+        #[depends_on = ".."]
+        pub ping: PlayerInfoDataEntryPing,
+        #[depends_on = ".."]
+        pub display_name: PlayerInfoDataEntryDisplayName,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x31]
@@ -939,19 +3461,26 @@ pub mod client {
         pub y: DoubleField,
         pub z: DoubleField,
         pub is_entity: BoolField,
+        #[depends_on = "is_entity"]
         pub entity_id: FacePlayerEntityId,
+        #[depends_on = "is_entity"]
         pub entity_feet_eyes: FacePlayerEntityFeetEyes,
     }
 
+    /// The variant must be the one isEntity selects, or writing the packet fails
     pub enum FacePlayerEntityId {
         /// isEntity=true
-        One(VarIntField),
+        IsEntity(VarIntField),
+        /// Any other isEntity
         NotPresent,
     }
 
     impl Display for FacePlayerEntityId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("FacePlayerEntityId") // TODO better display for autogenerated types
+            match self {
+                Self::IsEntity(value) => write!(f, "IsEntity({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -964,26 +3493,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::IsEntity(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("FacePlayerEntityId"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::IsEntity(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for FacePlayerEntityId {
+        type Dependency = bool;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                true => Self::IsEntity(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                true => "IsEntity",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::IsEntity(_) => "IsEntity",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "FacePlayerEntityId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::IsEntity(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one isEntity selects, or writing the packet fails
     pub enum FacePlayerEntityFeetEyes {
         /// isEntity=true
-        One(StringField),
+        IsEntity(StringField),
+        /// Any other isEntity
         NotPresent,
     }
 
     impl Display for FacePlayerEntityFeetEyes {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("FacePlayerEntityFeetEyes") // TODO better display for autogenerated types
+            match self {
+                Self::IsEntity(value) => write!(f, "IsEntity({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -996,17 +3578,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::IsEntity(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("FacePlayerEntityFeetEyes"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::IsEntity(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for FacePlayerEntityFeetEyes {
+        type Dependency = bool;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                true => Self::IsEntity(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                true => "IsEntity",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::IsEntity(_) => "IsEntity",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "FacePlayerEntityFeetEyes",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::IsEntity(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x32]
     pub struct Position {
@@ -1035,18 +3665,24 @@ pub mod client {
         pub smelting_book_open: BoolField,
         pub filtering_smeltable: BoolField,
         pub recipes_1: PrefixedArrayField<VarIntField, StringField>,
+        #[depends_on = "action"]
         pub recipes_2: UnlockRecipesRecipes2,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum UnlockRecipesRecipes2 {
         /// action=0
-        One(PrefixedArrayField<VarIntField, StringField>),
+        Action0(PrefixedArrayField<VarIntField, StringField>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for UnlockRecipesRecipes2 {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UnlockRecipesRecipes2") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1059,17 +3695,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UnlockRecipesRecipes2"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for UnlockRecipesRecipes2 {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UnlockRecipesRecipes2",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x35]
     pub struct EntityDestroy {
@@ -1106,37 +3790,50 @@ pub mod client {
         pub head_yaw: ByteField,
     }
 
-    /* TODO incomplete struct SelectAdvancementTab
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3a]
     pub struct SelectAdvancementTab {
-        // TODO pub id: Option,
-    }*/
+        pub id: OptionField<StringField>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3b]
     pub struct WorldBorder {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub radius: WorldBorderRadius,
+        #[depends_on = "action"]
         pub x: WorldBorderX,
+        #[depends_on = "action"]
         pub z: WorldBorderZ,
+        #[depends_on = "action"]
         pub old_radius: WorldBorderOldRadius,
+        #[depends_on = "action"]
         pub new_radius: WorldBorderNewRadius,
+        #[depends_on = "action"]
         pub speed: WorldBorderSpeed,
+        #[depends_on = "action"]
         pub portal_boundary: WorldBorderPortalBoundary,
+        #[depends_on = "action"]
         pub warning_time: WorldBorderWarningTime,
+        #[depends_on = "action"]
         pub warning_blocks: WorldBorderWarningBlocks,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderRadius {
         /// action=0
-        One(DoubleField),
+        Action0(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1149,28 +3846,82 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderX {
         /// action=2
-        One(DoubleField),
+        Action2(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderX") // TODO better display for autogenerated types
+            match self {
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1183,28 +3934,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action2(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderX {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Action2(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Action2",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action2(_) => "Action2",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderZ {
         /// action=2
-        One(DoubleField),
+        Action2(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderZ {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderZ") // TODO better display for autogenerated types
+            match self {
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1217,28 +4028,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action2(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderZ"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderZ {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Action2(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Action2",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action2(_) => "Action2",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderZ",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action2(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderOldRadius {
         /// action=1
-        One(DoubleField),
+        Action1(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderOldRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderOldRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1251,28 +4122,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderOldRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderOldRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderOldRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderNewRadius {
         /// action=1
-        One(DoubleField),
+        Action1(DoubleField),
         /// action=3
-        Two(DoubleField),
+        Action3(DoubleField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderNewRadius {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderNewRadius") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1285,28 +4216,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderNewRadius"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderNewRadius {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderNewRadius",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderSpeed {
         /// action=1
-        One(VarIntField),
+        Action1(VarIntField),
         /// action=3
-        Two(VarIntField),
+        Action3(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderSpeed {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderSpeed") // TODO better display for autogenerated types
+            match self {
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1319,26 +4310,85 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1(value) => value.size(),
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderSpeed"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderSpeed {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1(Field::read_field(r).await?),
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action1(_) => "Action1",
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderSpeed",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderPortalBoundary {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderPortalBoundary {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderPortalBoundary") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1351,28 +4401,82 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderPortalBoundary"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderPortalBoundary {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderPortalBoundary",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderWarningTime {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
         /// action=4
-        Two(VarIntField),
+        Action4(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderWarningTime {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderWarningTime") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::Action4(value) => write!(f, "Action4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1385,28 +4489,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::Action4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderWarningTime"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for WorldBorderWarningTime {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                4 => Self::Action4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                4 => "Action4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::Action4(_) => "Action4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderWarningTime",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum WorldBorderWarningBlocks {
         /// action=3
-        One(VarIntField),
+        Action3(VarIntField),
         /// action=5
-        Two(VarIntField),
+        Action5(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for WorldBorderWarningBlocks {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("WorldBorderWarningBlocks") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::Action5(value) => write!(f, "Action5({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1419,17 +4583,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::Action5(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("WorldBorderWarningBlocks"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for WorldBorderWarningBlocks {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                5 => Self::Action5(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                5 => "Action5",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::Action5(_) => "Action5",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "WorldBorderWarningBlocks",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::Action5(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3c]
     pub struct Camera {
@@ -1449,13 +4667,12 @@ pub mod client {
         pub name: StringField,
     }
 
-    /* TODO incomplete struct EntityMetadata
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x3f]
     pub struct EntityMetadata {
         pub entity_id: VarIntField,
-        // TODO pub metadata: EntityMetadata,
-    }*/
+        pub metadata: EntityMetadataField<393>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x40]
@@ -1473,14 +4690,13 @@ pub mod client {
         pub velocity_z: ShortField,
     }
 
-    /* TODO incomplete struct EntityEquipment
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x42]
     pub struct EntityEquipment {
         pub entity_id: VarIntField,
         pub slot: VarIntField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<393>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x43]
@@ -1503,21 +4719,29 @@ pub mod client {
     pub struct ScoreboardObjective {
         pub name: StringField,
         pub action: ByteField,
+        #[depends_on = "action"]
         pub display_text: ScoreboardObjectiveDisplayText,
+        #[depends_on = "action"]
         pub r#type: ScoreboardObjectiveType,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardObjectiveDisplayText {
         /// action=0
-        One(StringField),
+        Action0(ChatField<393>),
         /// action=2
-        Two(StringField),
+        Action2(ChatField<393>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for ScoreboardObjectiveDisplayText {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardObjectiveDisplayText") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1530,28 +4754,90 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency(
+                "ScoreboardObjectiveDisplayText",
+            ))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for ScoreboardObjectiveDisplayText {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardObjectiveDisplayText",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardObjectiveType {
         /// action=0
-        One(VarIntField),
+        Action0(VarIntField),
         /// action=2
-        Two(VarIntField),
+        Action2(VarIntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for ScoreboardObjectiveType {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardObjectiveType") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1564,17 +4850,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("ScoreboardObjectiveType"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for ScoreboardObjectiveType {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardObjectiveType",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x46]
     pub struct SetPassengers {
@@ -1587,27 +4927,41 @@ pub mod client {
     pub struct Teams {
         pub team: StringField,
         pub mode: ByteField,
+        #[depends_on = "mode"]
         pub name: TeamsName,
+        #[depends_on = "mode"]
         pub friendly_fire: TeamsFriendlyFire,
+        #[depends_on = "mode"]
         pub name_tag_visibility: TeamsNameTagVisibility,
+        #[depends_on = "mode"]
         pub collision_rule: TeamsCollisionRule,
+        #[depends_on = "mode"]
         pub formatting: TeamsFormatting,
+        #[depends_on = "mode"]
         pub prefix: TeamsPrefix,
+        #[depends_on = "mode"]
         pub suffix: TeamsSuffix,
+        #[depends_on = "mode"]
         pub players: TeamsPlayers,
     }
 
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsName {
         /// mode=0
-        One(StringField),
+        Mode0(ChatField<393>),
         /// mode=2
-        Two(StringField),
+        Mode2(ChatField<393>),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsName {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsName") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1620,28 +4974,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsName"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsName {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsName",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsFriendlyFire {
         /// mode=0
-        One(ByteField),
+        Mode0(ByteField),
         /// mode=2
-        Two(ByteField),
+        Mode2(ByteField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsFriendlyFire {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsFriendlyFire") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1654,28 +5068,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsFriendlyFire"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsFriendlyFire {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsFriendlyFire",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsNameTagVisibility {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsNameTagVisibility {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsNameTagVisibility") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1688,28 +5162,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsNameTagVisibility"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsNameTagVisibility {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsNameTagVisibility",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsCollisionRule {
         /// mode=0
-        One(StringField),
+        Mode0(StringField),
         /// mode=2
-        Two(StringField),
+        Mode2(StringField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsCollisionRule {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsCollisionRule") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1722,28 +5256,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsCollisionRule"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsCollisionRule {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsCollisionRule",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsFormatting {
         /// mode=0
-        One(VarIntField),
+        Mode0(VarIntField),
         /// mode=2
-        Two(VarIntField),
+        Mode2(VarIntField),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsFormatting {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsFormatting") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1756,28 +5350,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsFormatting"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsFormatting {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsFormatting",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsPrefix {
         /// mode=0
-        One(StringField),
+        Mode0(ChatField<393>),
         /// mode=2
-        Two(StringField),
+        Mode2(ChatField<393>),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsPrefix {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsPrefix") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1790,28 +5444,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsPrefix"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsPrefix {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsPrefix",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsSuffix {
         /// mode=0
-        One(StringField),
+        Mode0(ChatField<393>),
         /// mode=2
-        Two(StringField),
+        Mode2(ChatField<393>),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsSuffix {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsSuffix") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode2(value) => write!(f, "Mode2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1824,30 +5538,91 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsSuffix"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TeamsSuffix {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                2 => Self::Mode2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                2 => "Mode2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode2(_) => "Mode2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsSuffix",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mode selects, or writing the packet fails
     pub enum TeamsPlayers {
         /// mode=0
-        One(PrefixedArrayField<VarIntField, StringField>),
+        Mode0(PrefixedArrayField<VarIntField, StringField>),
         /// mode=3
-        Two(PrefixedArrayField<VarIntField, StringField>),
+        Mode3(PrefixedArrayField<VarIntField, StringField>),
         /// mode=4
-        Three(PrefixedArrayField<VarIntField, StringField>),
+        Mode4(PrefixedArrayField<VarIntField, StringField>),
+        /// Any other mode
         NotPresent,
     }
 
     impl Display for TeamsPlayers {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TeamsPlayers") // TODO better display for autogenerated types
+            match self {
+                Self::Mode0(value) => write!(f, "Mode0({})", value.value()),
+                Self::Mode3(value) => write!(f, "Mode3({})", value.value()),
+                Self::Mode4(value) => write!(f, "Mode4({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1860,35 +5635,101 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mode0(value) => value.size(),
+                Self::Mode3(value) => value.size(),
+                Self::Mode4(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TeamsPlayers"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode3(value) => value.write_field(w).await,
+                Self::Mode4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for TeamsPlayers {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mode0(Field::read_field(r).await?),
+                3 => Self::Mode3(Field::read_field(r).await?),
+                4 => Self::Mode4(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mode0",
+                3 => "Mode3",
+                4 => "Mode4",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mode0(_) => "Mode0",
+                Self::Mode3(_) => "Mode3",
+                Self::Mode4(_) => "Mode4",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TeamsPlayers",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mode0(value) => value.write_field(w).await,
+                Self::Mode3(value) => value.write_field(w).await,
+                Self::Mode4(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x48]
     pub struct ScoreboardScore {
         pub item_name: StringField,
         pub action: ByteField,
         pub score_name: StringField,
+        #[depends_on = "action"]
         pub value: ScoreboardScoreValue,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum ScoreboardScoreValue {
-        // TODO Default(...)
         /// action=1
-        One,
+        Action1,
+        /// Any other action
+        Default(VarIntField),
     }
 
     impl Display for ScoreboardScoreValue {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("ScoreboardScoreValue") // TODO better display for autogenerated types
+            match self {
+                Self::Action1 => f.write_str("Action1"),
+                Self::Default(value) => write!(f, "Default({})", value.value()),
+            }
         }
     }
 
@@ -1901,17 +5742,65 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action1 => 0,
+                Self::Default(value) => value.size(),
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("ScoreboardScoreValue"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action1 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for ScoreboardScoreValue {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Action1,
+                _ => Self::Default(Field::read_field(r).await?),
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Action1",
+                _ => "Default",
+            };
+            let variant = match self {
+                Self::Action1 => "Action1",
+                Self::Default(_) => "Default",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "ScoreboardScoreValue",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action1 => Ok(()),
+                Self::Default(value) => value.write_field(w).await,
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x49]
     pub struct SpawnPosition {
@@ -1929,25 +5818,36 @@ pub mod client {
     #[packet_id = 0x4b]
     pub struct Title {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub text: TitleText,
+        #[depends_on = "action"]
         pub fade_in: TitleFadeIn,
+        #[depends_on = "action"]
         pub stay: TitleStay,
+        #[depends_on = "action"]
         pub fade_out: TitleFadeOut,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleText {
         /// action=0
-        One(StringField),
+        Action0(ChatField<393>),
         /// action=1
-        Two(StringField),
+        Action1(ChatField<393>),
         /// action=2
-        Three(StringField),
+        Action2(ChatField<393>),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleText {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleText") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1(value) => write!(f, "Action1({})", value.value()),
+                Self::Action2(value) => write!(f, "Action2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1960,26 +5860,91 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1(value) => value.size(),
+                Self::Action2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleText"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleText {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1(Field::read_field(r).await?),
+                2 => Self::Action2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                2 => "Action2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1(_) => "Action1",
+                Self::Action2(_) => "Action2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleText",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1(value) => value.write_field(w).await,
+                Self::Action2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleFadeIn {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleFadeIn {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleFadeIn") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -1992,26 +5957,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleFadeIn"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleFadeIn {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleFadeIn",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleStay {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleStay {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleStay") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2024,26 +6042,79 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleStay"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for TitleStay {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleStay",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum TitleFadeOut {
         /// action=3
-        One(IntField),
+        Action3(IntField),
+        /// Any other action
         NotPresent,
     }
 
     impl Display for TitleFadeOut {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("TitleFadeOut") // TODO better display for autogenerated types
+            match self {
+                Self::Action3(value) => write!(f, "Action3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2056,36 +6127,92 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("TitleFadeOut"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for TitleFadeOut {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                3 => Self::Action3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                3 => "Action3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action3(_) => "Action3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "TitleFadeOut",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4c]
     pub struct StopSound {
         pub flags: ByteField,
+        #[depends_on = "flags"]
         pub source: StopSoundSource,
+        #[depends_on = "flags"]
         pub sound: StopSoundSound,
     }
 
+    /// The variant must be the one flags selects, or writing the packet fails
     pub enum StopSoundSource {
         /// flags=1
-        One(VarIntField),
+        Flags1(VarIntField),
         /// flags=3
-        Two(VarIntField),
+        Flags3(VarIntField),
+        /// Any other flags
         NotPresent,
     }
 
     impl Display for StopSoundSource {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("StopSoundSource") // TODO better display for autogenerated types
+            match self {
+                Self::Flags1(value) => write!(f, "Flags1({})", value.value()),
+                Self::Flags3(value) => write!(f, "Flags3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2098,28 +6225,88 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Flags1(value) => value.size(),
+                Self::Flags3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("StopSoundSource"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Flags1(value) => value.write_field(w).await,
+                Self::Flags3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for StopSoundSource {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                1 => Self::Flags1(Field::read_field(r).await?),
+                3 => Self::Flags3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                1 => "Flags1",
+                3 => "Flags3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Flags1(_) => "Flags1",
+                Self::Flags3(_) => "Flags3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "StopSoundSource",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Flags1(value) => value.write_field(w).await,
+                Self::Flags3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one flags selects, or writing the packet fails
     pub enum StopSoundSound {
         /// flags=2
-        One(StringField),
+        Flags2(StringField),
         /// flags=3
-        Two(StringField),
+        Flags3(StringField),
+        /// Any other flags
         NotPresent,
     }
 
     impl Display for StopSoundSound {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("StopSoundSound") // TODO better display for autogenerated types
+            match self {
+                Self::Flags2(value) => write!(f, "Flags2({})", value.value()),
+                Self::Flags3(value) => write!(f, "Flags3({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2132,17 +6319,71 @@ pub mod client {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Flags2(value) => value.size(),
+                Self::Flags3(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("StopSoundSound"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Flags2(value) => value.write_field(w).await,
+                Self::Flags3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for StopSoundSound {
+        type Dependency = i8;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Flags2(Field::read_field(r).await?),
+                3 => Self::Flags3(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Flags2",
+                3 => "Flags3",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Flags2(_) => "Flags2",
+                Self::Flags3(_) => "Flags3",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "StopSoundSound",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Flags2(value) => value.write_field(w).await,
+                Self::Flags3(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4d]
     pub struct SoundEffect {
@@ -2158,8 +6399,8 @@ pub mod client {
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x4e]
     pub struct PlayerlistHeader {
-        pub header: StringField,
-        pub footer: StringField,
+        pub header: ChatField<393>,
+        pub footer: ChatField<393>,
     }
 
     #[derive(ClientBoundPacket)]
@@ -2182,23 +6423,234 @@ pub mod client {
         pub on_ground: BoolField,
     }
 
-    /* TODO incomplete struct Advancements
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x51]
     pub struct Advancements {
         pub reset: BoolField,
-        // TODO pub advancement_mapping: Array<Container>,
+        pub advancement_mapping:
+            PrefixedArrayField<VarIntField, AdvancementsAdvancementMappingEntry>,
         pub identifiers: PrefixedArrayField<VarIntField, StringField>,
-        // TODO pub progress_mapping: Array<Container>,
-    }*/
+        pub progress_mapping: PrefixedArrayField<VarIntField, AdvancementsProgressMappingEntry>,
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    pub struct AdvancementsAdvancementMappingEntryValueDisplayDataFlags(BitfieldField<4>);
+
+    impl AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        pub fn hidden(&self) -> bool {
+            self.0.unsigned(2, 1) != 0
+        }
+
+        pub fn set_hidden(&mut self, value: bool) {
+            self.0.set(2, 1, u64::from(value))
+        }
+
+        pub fn show_toast(&self) -> bool {
+            self.0.unsigned(1, 1) != 0
+        }
+
+        pub fn set_show_toast(&mut self, value: bool) {
+            self.0.set(1, 1, u64::from(value))
+        }
+
+        pub fn has_background_texture(&self) -> bool {
+            self.0.unsigned(0, 1) != 0
+        }
+
+        pub fn set_has_background_texture(&mut self, value: bool) {
+            self.0.set(0, 1, u64::from(value))
+        }
+    }
+
+    impl Display for AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "(hidden={}, show_toast={}, has_background_texture={})",
+                self.hidden(),
+                self.show_toast(),
+                self.has_background_texture()
+            )
+        }
+    }
+
+    #[async_trait]
+    impl Field for AdvancementsAdvancementMappingEntryValueDisplayDataFlags {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(r: &mut R) -> PacketResult<Self> {
+            BitfieldField::read_field(r).await.map(Self)
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            self.0.write_field(w).await
+        }
+    }
+
+    /// The variant must be the one flags/has_background_texture selects, or writing the packet fails
+    pub enum AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        /// flags/has_background_texture=1
+        HasBackgroundTexture(StringField),
+        /// Any other flags/has_background_texture
+        NotPresent,
+    }
+
+    impl Display for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::HasBackgroundTexture(value) => {
+                    write!(f, "HasBackgroundTexture({})", value.value())
+                }
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Field for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        type Displayable = Self;
+
+        fn value(&self) -> &Self::Displayable {
+            self
+        }
+
+        fn size(&self) -> usize {
+            match self {
+                Self::HasBackgroundTexture(value) => value.size(),
+                Self::NotPresent => 0,
+            }
+        }
+
+        async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
+            Err(PacketError::MissingDependency(
+                "AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture",
+            ))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::HasBackgroundTexture(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DependentField for AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture {
+        type Dependency = AdvancementsAdvancementMappingEntryValueDisplayDataFlags;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match dependency.has_background_texture() {
+                true => Self::HasBackgroundTexture(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match dependency.has_background_texture() {
+                true => "HasBackgroundTexture",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::HasBackgroundTexture(_) => "HasBackgroundTexture",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::HasBackgroundTexture(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValueDisplayData {
+        pub title: ChatField<393>,
+        pub description: ChatField<393>,
+        pub icon: SlotField<393>,
+        pub frame_type: VarIntField,
+        pub flags: AdvancementsAdvancementMappingEntryValueDisplayDataFlags,
+        #[depends_on = "flags"]
+        pub background_texture:
+            AdvancementsAdvancementMappingEntryValueDisplayDataBackgroundTexture,
+        pub x_cord: FloatField,
+        pub y_cord: FloatField,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValueCriteriaEntry {
+        pub key: StringField,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntryValue {
+        pub parent_id: OptionField<StringField>,
+        pub display_data: OptionField<AdvancementsAdvancementMappingEntryValueDisplayData>,
+        pub criteria:
+            PrefixedArrayField<VarIntField, AdvancementsAdvancementMappingEntryValueCriteriaEntry>,
+        pub requirements:
+            PrefixedArrayField<VarIntField, PrefixedArrayField<VarIntField, StringField>>,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsAdvancementMappingEntry {
+        pub key: StringField,
+        pub value: AdvancementsAdvancementMappingEntryValue,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsProgressMappingEntryValueEntry {
+        pub criterion_identifier: StringField,
+        pub criterion_progress: OptionField<LongField>,
+    }
+
+    #[derive(Field)]
+    pub struct AdvancementsProgressMappingEntry {
+        pub key: StringField,
+        pub value: PrefixedArrayField<VarIntField, AdvancementsProgressMappingEntryValueEntry>,
+    }
 
-    /* TODO incomplete struct EntityUpdateAttributes
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x52]
     pub struct EntityUpdateAttributes {
         pub entity_id: VarIntField,
-        // TODO pub properties: Array<Container>,
-    }*/
+        pub properties: PrefixedArrayField<IntField, EntityUpdateAttributesProperty>,
+    }
+
+    #[derive(Field)]
+    pub struct EntityUpdateAttributesPropertyModifier {
+        pub uuid: UuidField,
+        pub amount: DoubleField,
+        pub operation: ByteField,
+    }
+
+    #[derive(Field)]
+    pub struct EntityUpdateAttributesProperty {
+        pub key: StringField,
+        pub value: DoubleField,
+        pub modifiers: PrefixedArrayField<VarIntField, EntityUpdateAttributesPropertyModifier>,
+    }
 
     #[derive(ClientBoundPacket)]
     #[packet_id = 0x53]
@@ -2288,7 +6740,6 @@ pub mod server {
         pub enchantment: ByteField,
     }
 
-    /* TODO incomplete struct WindowClick
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x08]
     pub struct WindowClick {
@@ -2297,8 +6748,8 @@ pub mod server {
         pub mouse_button: ByteField,
         pub action: ShortField,
         pub mode: ByteField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<393>,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x09]
@@ -2313,13 +6764,12 @@ pub mod server {
         pub data: RestOfPacketByteArrayField,
     }
 
-    /* TODO incomplete struct EditBook
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x0b]
     pub struct EditBook {
-        // TODO pub new_book: Slot,
+        pub new_book: SlotField<393>,
         pub signing: BoolField,
-    }*/
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x0c]
@@ -2333,21 +6783,30 @@ pub mod server {
     pub struct UseEntity {
         pub target: VarIntField,
         pub mouse: VarIntField,
+        #[depends_on = "mouse"]
         pub x: UseEntityX,
+        #[depends_on = "mouse"]
         pub y: UseEntityY,
+        #[depends_on = "mouse"]
         pub z: UseEntityZ,
+        #[depends_on = "mouse"]
         pub hand: UseEntityHand,
     }
 
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityX {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityX {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityX") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2360,26 +6819,79 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityX"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityX {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityX",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityY {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityY {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityY") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2392,26 +6904,79 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityY"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityY {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityY",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityZ {
         /// mouse=2
-        One(FloatField),
+        Mouse2(FloatField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityZ {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityZ") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2424,28 +6989,82 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityZ"))
         }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
+    #[async_trait]
+    impl DependentField for UseEntityZ {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityZ",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
+        }
+    }
+
+    /// The variant must be the one mouse selects, or writing the packet fails
     pub enum UseEntityHand {
         /// mouse=0
-        One(VarIntField),
+        Mouse0(VarIntField),
         /// mouse=2
-        Two(VarIntField),
+        Mouse2(VarIntField),
+        /// Any other mouse
         NotPresent,
     }
 
     impl Display for UseEntityHand {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("UseEntityHand") // TODO better display for autogenerated types
+            match self {
+                Self::Mouse0(value) => write!(f, "Mouse0({})", value.value()),
+                Self::Mouse2(value) => write!(f, "Mouse2({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2458,17 +7077,71 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Mouse0(value) => value.size(),
+                Self::Mouse2(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("UseEntityHand"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Mouse0(value) => value.write_field(w).await,
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for UseEntityHand {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Mouse0(Field::read_field(r).await?),
+                2 => Self::Mouse2(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Mouse0",
+                2 => "Mouse2",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Mouse0(_) => "Mouse0",
+                Self::Mouse2(_) => "Mouse2",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "UseEntityHand",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Mouse0(value) => value.write_field(w).await,
+                Self::Mouse2(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x0e]
     pub struct KeepAlive {
@@ -2576,17 +7249,40 @@ pub mod server {
     #[packet_id = 0x1b]
     pub struct CraftingBookData {
         pub r#type: VarIntField,
+        #[depends_on = "r#type"]
         pub anon: CraftingBookDataAnon,
     }
 
+    #[derive(Field)]
+    pub struct CraftingBookDataAnonType0 {
+        pub displayed_recipe: StringField,
+    }
+
+    #[derive(Field)]
+    pub struct CraftingBookDataAnonType1 {
+        pub crafting_book_open: BoolField,
+        pub crafting_filter: BoolField,
+        pub smelting_book_open: BoolField,
+        pub smelting_filter: BoolField,
+    }
+
+    /// The variant must be the one type selects, or writing the packet fails
     pub enum CraftingBookDataAnon {
-        // TODO One(...)
-    // TODO Two(...)
+        /// type=0
+        Type0(CraftingBookDataAnonType0),
+        /// type=1
+        Type1(CraftingBookDataAnonType1),
+        /// Any other type
+        NotPresent,
     }
 
     impl Display for CraftingBookDataAnon {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("CraftingBookDataAnon") // TODO better display for autogenerated types
+            match self {
+                Self::Type0(value) => write!(f, "Type0({})", value.value()),
+                Self::Type1(value) => write!(f, "Type1({})", value.value()),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2599,17 +7295,71 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Type0(value) => value.size(),
+                Self::Type1(value) => value.size(),
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("CraftingBookDataAnon"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Type0(value) => value.write_field(w).await,
+                Self::Type1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for CraftingBookDataAnon {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Type0(Field::read_field(r).await?),
+                1 => Self::Type1(Field::read_field(r).await?),
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Type0",
+                1 => "Type1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Type0(_) => "Type0",
+                Self::Type1(_) => "Type1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "CraftingBookDataAnon",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Type0(value) => value.write_field(w).await,
+                Self::Type1(value) => value.write_field(w).await,
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1c]
     pub struct NameItem {
@@ -2626,19 +7376,27 @@ pub mod server {
     #[packet_id = 0x1e]
     pub struct AdvancementTab {
         pub action: VarIntField,
+        #[depends_on = "action"]
         pub tab_id: AdvancementTabTabId,
     }
 
+    /// The variant must be the one action selects, or writing the packet fails
     pub enum AdvancementTabTabId {
         /// action=0
-        One(StringField),
+        Action0(StringField),
         /// action=1
-        Two,
+        Action1,
+        /// Any other action
+        NotPresent,
     }
 
     impl Display for AdvancementTabTabId {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            f.write_str("AdvancementTabTabId") // TODO better display for autogenerated types
+            match self {
+                Self::Action0(value) => write!(f, "Action0({})", value.value()),
+                Self::Action1 => f.write_str("Action1"),
+                Self::NotPresent => f.write_str("NotPresent"),
+            }
         }
     }
 
@@ -2651,17 +7409,71 @@ pub mod server {
         }
 
         fn size(&self) -> usize {
-            todo!()
+            match self {
+                Self::Action0(value) => value.size(),
+                Self::Action1 => 0,
+                Self::NotPresent => 0,
+            }
         }
 
         async fn read_field<R: Read + Unpin + Send>(_r: &mut R) -> PacketResult<Self> {
-            todo!()
+            Err(PacketError::MissingDependency("AdvancementTabTabId"))
+        }
+
+        async fn write_field<W: Write + Unpin + Send>(&self, w: &mut W) -> PacketResult<()> {
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1 => Ok(()),
+                Self::NotPresent => Ok(()),
+            }
         }
+    }
 
-        async fn write_field<W: Write + Unpin + Send>(&self, _w: &mut W) -> PacketResult<()> {
-            todo!()
+    #[async_trait]
+    impl DependentField for AdvancementTabTabId {
+        type Dependency = i32;
+
+        async fn read_dependent<R: Read + Unpin + Send>(
+            r: &mut R,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<Self> {
+            Ok(match *dependency {
+                0 => Self::Action0(Field::read_field(r).await?),
+                1 => Self::Action1,
+                _ => Self::NotPresent,
+            })
+        }
+
+        async fn write_dependent<W: Write + Unpin + Send>(
+            &self,
+            w: &mut W,
+            dependency: &Self::Dependency,
+        ) -> PacketResult<()> {
+            let selected = match *dependency {
+                0 => "Action0",
+                1 => "Action1",
+                _ => "NotPresent",
+            };
+            let variant = match self {
+                Self::Action0(_) => "Action0",
+                Self::Action1 => "Action1",
+                Self::NotPresent => "NotPresent",
+            };
+            if variant != selected {
+                return Err(PacketError::SwitchMismatch {
+                    field: "AdvancementTabTabId",
+                    variant,
+                });
+            }
+
+            match self {
+                Self::Action0(value) => value.write_field(w).await,
+                Self::Action1 => Ok(()),
+                Self::NotPresent => Ok(()),
+            }
         }
     }
+
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x1f]
     pub struct SelectTrade {
@@ -2698,13 +7510,12 @@ pub mod server {
         pub track_output: BoolField,
     }
 
-    /* TODO incomplete struct SetCreativeSlot
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x24]
     pub struct SetCreativeSlot {
         pub slot: ShortField,
-        // TODO pub item: Slot,
-    }*/
+        pub item: SlotField<393>,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x25]
@@ -2743,12 +7554,11 @@ pub mod server {
         pub hand: VarIntField,
     }
 
-    /* TODO incomplete struct Spectate
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x28]
     pub struct Spectate {
-        // TODO pub target: Uuid,
-    }*/
+        pub target: UuidField,
+    }
 
     #[derive(ServerBoundPacket)]
     #[packet_id = 0x29]